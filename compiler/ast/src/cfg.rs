@@ -0,0 +1,456 @@
+use crate::node::Node;
+use crate::state::{ParseError, Parser};
+use std::collections::HashSet;
+use std::ops::Deref;
+
+// 控制流图节点的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CfgNodeKind {
+    Entry,
+    Exit,
+    Block,      // 顺序执行的语句
+    Condition,  // `if` 条件
+    LoopHeader, // 循环头（`loop`、`while` 条件、`for` 区间）
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CfgNode {
+    pub kind: CfgNodeKind,
+    pub statements: Vec<String>, // 节点中语句（或条件）的源码
+    pub position: (usize, usize),
+}
+
+// 控制流图边的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CfgEdgeKind {
+    Normal,
+    True,
+    False,
+    Back, // 循环体结束后回到循环头
+    Break,
+    Continue,
+    Return,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CfgEdge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: CfgEdgeKind,
+}
+
+// 单个函数的控制流图，`nodes[0]` 为入口，`nodes[1]` 为出口
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionCfg {
+    pub name: String,
+    pub nodes: Vec<CfgNode>,
+    pub edges: Vec<CfgEdge>,
+}
+
+// 为源码中的每个函数构建控制流图
+pub fn build_cfg(source: &str) -> Result<Vec<FunctionCfg>, ParseError> {
+    let program = Parser::try_parse(source)?;
+    Ok(build_program_cfg(source, &program))
+}
+
+pub fn build_program_cfg(source: &str, program: &Node) -> Vec<FunctionCfg> {
+    let chars: Vec<char> = source.chars().collect();
+    let body = match program {
+        Node::Program { body, .. } => body,
+        _ => return vec![],
+    };
+    body.iter()
+        .filter_map(|stat| match stat.deref() {
+            Node::FunctionDeclaration {
+                id, body, position, ..
+            } => {
+                let (name, ..) = id.read_identifier();
+                Some(CfgBuilder::build(&chars, name, body, *position))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// 当前所在循环，用于确定 break / continue 的目标
+struct LoopTarget {
+    label: Option<String>,
+    header: usize,
+    after: usize,
+}
+
+struct CfgBuilder<'a> {
+    chars: &'a [char],
+    nodes: Vec<CfgNode>,
+    edges: Vec<CfgEdge>,
+    current: Option<usize>, // 当前正在追加语句的块，为 None 时之后的语句不可达
+    loops: Vec<LoopTarget>,
+}
+
+const ENTRY: usize = 0;
+const EXIT: usize = 1;
+
+impl<'a> CfgBuilder<'a> {
+    fn build(
+        chars: &'a [char],
+        name: &str,
+        body: &Node,
+        position: (usize, usize),
+    ) -> FunctionCfg {
+        let mut builder = CfgBuilder {
+            chars,
+            nodes: vec![],
+            edges: vec![],
+            current: None,
+            loops: vec![],
+        };
+        builder.add_node(CfgNodeKind::Entry, vec![], position);
+        builder.add_node(CfgNodeKind::Exit, vec![], position);
+        let first = builder.add_node(CfgNodeKind::Block, vec![], body.read_position());
+        builder.add_edge(ENTRY, first, CfgEdgeKind::Normal);
+        builder.current = Some(first);
+
+        builder.build_statements(body.read_block_body());
+        if let Some(current) = builder.current {
+            builder.add_edge(current, EXIT, CfgEdgeKind::Normal);
+        }
+        builder.remove_empty_blocks();
+
+        FunctionCfg {
+            name: name.to_string(),
+            nodes: builder.nodes,
+            edges: builder.edges,
+        }
+    }
+
+    fn add_node(
+        &mut self,
+        kind: CfgNodeKind,
+        statements: Vec<String>,
+        position: (usize, usize),
+    ) -> usize {
+        self.nodes.push(CfgNode {
+            kind,
+            statements,
+            position,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, kind: CfgEdgeKind) {
+        self.edges.push(CfgEdge { from, to, kind });
+    }
+
+    // 源码片段，多行时合并为一行
+    fn text(&self, start: usize, end: usize) -> String {
+        let text: String = self.chars[start..end].iter().collect();
+        text.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    // 获取当前块，不可达时新建一个没有前驱的块
+    fn current_block(&mut self, position: (usize, usize)) -> usize {
+        match self.current {
+            Some(current) => current,
+            None => {
+                let block = self.add_node(CfgNodeKind::Block, vec![], position);
+                self.current = Some(block);
+                block
+            }
+        }
+    }
+
+    // 结束当前块并跳转到目标节点，之后的语句不可达
+    fn jump(&mut self, to: usize, kind: CfgEdgeKind) {
+        if let Some(current) = self.current.take() {
+            self.add_edge(current, to, kind);
+        }
+    }
+
+    // 将语句源码追加到当前块
+    fn push_statement(&mut self, stat: &Node) {
+        let position = stat.read_position();
+        let text = self.text(position.0, position.1);
+        let block = self.current_block(position);
+        self.nodes[block].statements.push(text);
+    }
+
+    fn build_statements(&mut self, statements: &[Box<Node>]) {
+        for stat in statements.iter() {
+            self.build_statement(stat.deref());
+        }
+    }
+
+    fn build_statement(&mut self, stat: &Node) {
+        match stat {
+            Node::BlockStatement { body, .. } => self.build_statements(body),
+            Node::IfStatement {
+                condition,
+                consequent,
+                alternate,
+                position,
+            } => {
+                let header_end = consequent.read_position().0;
+                let text = self.text(position.0, header_end);
+                let condition_node = self.add_node(
+                    CfgNodeKind::Condition,
+                    vec![text],
+                    condition.read_position(),
+                );
+                let block = self.current_block(*position);
+                self.add_edge(block, condition_node, CfgEdgeKind::Normal);
+                let after = self.add_node(CfgNodeKind::Block, vec![], *position);
+
+                // then 分支
+                self.current = Some(self.branch(condition_node, CfgEdgeKind::True));
+                self.build_statement(consequent);
+                self.jump(after, CfgEdgeKind::Normal);
+
+                // else 分支，没有 else 时直接跳到 if 之后
+                self.current = Some(self.branch(condition_node, CfgEdgeKind::False));
+                if let Some(alternate) = alternate {
+                    self.build_statement(alternate);
+                }
+                self.jump(after, CfgEdgeKind::Normal);
+                self.current = Some(after);
+            }
+            Node::LoopStatement {
+                label,
+                body,
+                position,
+            } => self.build_loop(label, body, *position, false),
+            Node::WhileStatement {
+                label,
+                body,
+                position,
+                ..
+            }
+            | Node::ForStatement {
+                label,
+                body,
+                position,
+                ..
+            } => self.build_loop(label, body, *position, true),
+            Node::BreakStatement { label, .. } => {
+                self.push_statement(stat);
+                let target = self.find_loop(label).map(|target| target.after);
+                match target {
+                    Some(after) => self.jump(after, CfgEdgeKind::Break),
+                    None => self.current = None,
+                }
+            }
+            Node::ContinueStatement { label, .. } => {
+                self.push_statement(stat);
+                let target = self.find_loop(label).map(|target| target.header);
+                match target {
+                    Some(header) => self.jump(header, CfgEdgeKind::Continue),
+                    None => self.current = None,
+                }
+            }
+            Node::ReturnStatement { .. } => {
+                self.push_statement(stat);
+                self.jump(EXIT, CfgEdgeKind::Return);
+            }
+            _ => self.push_statement(stat),
+        }
+    }
+
+    // 从条件节点引出一个分支块
+    fn branch(&mut self, from: usize, kind: CfgEdgeKind) -> usize {
+        let position = self.nodes[from].position;
+        let block = self.add_node(CfgNodeKind::Block, vec![], position);
+        self.add_edge(from, block, kind);
+        block
+    }
+
+    // 构建循环，`has_condition` 为 true 时（while / for）循环头有跳出循环的 false 分支
+    fn build_loop(
+        &mut self,
+        label: &Option<String>,
+        body: &Node,
+        position: (usize, usize),
+        has_condition: bool,
+    ) {
+        let text = self.text(position.0, body.read_position().0);
+        let header = self.add_node(CfgNodeKind::LoopHeader, vec![text], position);
+        let block = self.current_block(position);
+        self.add_edge(block, header, CfgEdgeKind::Normal);
+        let after = self.add_node(CfgNodeKind::Block, vec![], position);
+
+        let body_kind = if has_condition {
+            CfgEdgeKind::True
+        } else {
+            CfgEdgeKind::Normal
+        };
+        self.current = Some(self.branch(header, body_kind));
+        self.loops.push(LoopTarget {
+            label: label.clone(),
+            header,
+            after,
+        });
+        self.build_statement(body);
+        self.loops.pop();
+        self.jump(header, CfgEdgeKind::Back);
+
+        if has_condition {
+            self.add_edge(header, after, CfgEdgeKind::False);
+        }
+        self.current = Some(after);
+    }
+
+    // 查找 break / continue 的目标循环，没有标签时为最内层的循环
+    fn find_loop(&self, label: &Option<String>) -> Option<&LoopTarget> {
+        match label {
+            Some(_) => self
+                .loops
+                .iter()
+                .rev()
+                .find(|target| target.label == *label),
+            None => self.loops.last(),
+        }
+    }
+
+    // 移除空的块：将其前驱直接连接到其唯一的后继，没有前驱的空块直接删除
+    fn remove_empty_blocks(&mut self) {
+        let mut removed = HashSet::new();
+        for index in 0..self.nodes.len() {
+            let node = &self.nodes[index];
+            if node.kind != CfgNodeKind::Block || !node.statements.is_empty() {
+                continue;
+            }
+            let outgoing = self
+                .edges
+                .iter()
+                .filter(|edge| edge.from == index)
+                .map(|edge| (edge.to, edge.kind))
+                .collect::<Vec<(usize, CfgEdgeKind)>>();
+            let has_incoming = self.edges.iter().any(|edge| edge.to == index);
+            if has_incoming && outgoing.len() != 1 {
+                continue;
+            }
+            if let Some((to, kind)) = outgoing.first().copied() {
+                for edge in self.edges.iter_mut().filter(|edge| edge.to == index) {
+                    edge.to = to;
+                    if edge.kind == CfgEdgeKind::Normal {
+                        edge.kind = kind;
+                    }
+                }
+            }
+            self.edges.retain(|edge| edge.from != index);
+            removed.insert(index);
+        }
+
+        // 重新编号剩余的节点
+        let mut new_index = vec![0; self.nodes.len()];
+        let mut nodes = vec![];
+        for (index, node) in self.nodes.drain(..).enumerate() {
+            if !removed.contains(&index) {
+                new_index[index] = nodes.len();
+                nodes.push(node);
+            }
+        }
+        self.nodes = nodes;
+        for edge in self.edges.iter_mut() {
+            edge.from = new_index[edge.from];
+            edge.to = new_index[edge.to];
+        }
+    }
+}
+
+impl FunctionCfg {
+    // 从入口可以到达的节点
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.nodes.len()];
+        let mut stack = vec![ENTRY];
+        while let Some(index) = stack.pop() {
+            if reachable[index] {
+                continue;
+            }
+            reachable[index] = true;
+            for edge in self.edges.iter().filter(|edge| edge.from == index) {
+                stack.push(edge.to);
+            }
+        }
+        reachable
+    }
+}
+
+// 转义 DOT 字符串中的特殊字符
+fn escape_dot(text: &str) -> String {
+    let mut result = String::new();
+    for ch in text.chars() {
+        match ch {
+            '"' | '\\' => {
+                result.push('\\');
+                result.push(ch);
+            }
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+// 将控制流图输出为 Graphviz DOT 格式，每个函数为一个子图，不可达的节点以虚线表示
+pub fn to_dot(cfgs: &[FunctionCfg]) -> String {
+    let mut lines = vec![
+        String::from("digraph cfg {"),
+        String::from("    node [shape=box, fontname=\"monospace\"];"),
+    ];
+    for (fn_index, cfg) in cfgs.iter().enumerate() {
+        let id = |index: usize| format!("f{}_{}", fn_index, index);
+        let reachable = cfg.reachable();
+        lines.push(format!("    subgraph cluster_{} {{", fn_index));
+        lines.push(format!("        label=\"fn {}\";", escape_dot(&cfg.name)));
+        for (index, node) in cfg.nodes.iter().enumerate() {
+            let (label, shape) = match node.kind {
+                CfgNodeKind::Entry => (String::from("entry"), "ellipse"),
+                CfgNodeKind::Exit => (String::from("exit"), "ellipse"),
+                CfgNodeKind::Block => {
+                    let mut label = String::new();
+                    for stat in node.statements.iter() {
+                        label.push_str(&escape_dot(stat));
+                        label.push_str("\\l");
+                    }
+                    (label, "box")
+                }
+                CfgNodeKind::Condition => (escape_dot(&node.statements[0]), "diamond"),
+                CfgNodeKind::LoopHeader => (escape_dot(&node.statements[0]), "hexagon"),
+            };
+            let style = if reachable[index] {
+                ""
+            } else {
+                ", style=dashed"
+            };
+            lines.push(format!(
+                "        {} [label=\"{}\", shape={}{}];",
+                id(index),
+                label,
+                shape,
+                style
+            ));
+        }
+        for edge in cfg.edges.iter() {
+            let attrs = match edge.kind {
+                CfgEdgeKind::Normal => "",
+                CfgEdgeKind::True => " [label=\"true\"]",
+                CfgEdgeKind::False => " [label=\"false\"]",
+                CfgEdgeKind::Back => " [style=dashed]",
+                CfgEdgeKind::Break => " [label=\"break\"]",
+                CfgEdgeKind::Continue => " [label=\"continue\", style=dashed]",
+                CfgEdgeKind::Return => " [label=\"return\"]",
+            };
+            lines.push(format!(
+                "        {} -> {}{};",
+                id(edge.from),
+                id(edge.to),
+                attrs
+            ));
+        }
+        lines.push(String::from("    }"));
+    }
+    lines.push(String::from("}"));
+    lines.join("\n") + "\n"
+}
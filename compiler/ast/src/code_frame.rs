@@ -1,6 +1,7 @@
 use ansi_term::Colour;
 use std::cmp::max;
 use crate::externs;
+use crate::line_index::LineIndex;
 
 pub enum CodeFrameMessageType {
     Warn,
@@ -43,22 +44,16 @@ pub fn pad_str(value: &str, width: usize, pad_char: char) -> String {
     result
 }
 
-// 返回在源代码中的行列信息
-pub fn get_source_position(source: &str, index: usize) -> Option<(usize, usize)> {
-    let mut position = (1usize, 1usize);
-    let source_chars = source.chars().collect::<Vec<char>>();
-    for (i, ch) in source_chars.iter().enumerate() {
-        if i == index {
-            return Some(position);
-        }
-        if *ch == '\n' {
-            position.0 += 1;
-            position.1 = 1;
-        } else {
-            position.1 += 1;
-        }
+// 返回在源代码中的行列信息（从 1 开始），超出源码范围时返回 None
+pub fn get_source_position(
+    line_index: &LineIndex,
+    index: usize,
+) -> Option<(usize, usize)> {
+    if index >= line_index.len() {
+        return None;
     }
-    None
+    let position = line_index.line_col(index);
+    Some((position.line + 1, position.column + 1))
 }
 
 // 打印代码帧信息，返回目标位置的行列信息
@@ -67,6 +62,17 @@ pub fn print_code_frame(
     pos: usize,
     message: &str,
     msg_type: CodeFrameMessageType,
+) -> Option<(usize, usize)> {
+    print_code_frame_with_index(source, &LineIndex::new(source), pos, message, msg_type)
+}
+
+// 使用已构建的行索引打印代码帧信息，同一份源码打印多个代码帧时不需要重复构建
+pub fn print_code_frame_with_index(
+    source: &str,
+    line_index: &LineIndex,
+    pos: usize,
+    message: &str,
+    msg_type: CodeFrameMessageType,
 ) -> Option<(usize, usize)> {
     let mut before_lines = vec![];
     let mut after_lines = vec![];
@@ -74,8 +80,8 @@ pub fn print_code_frame(
     // 分割提示信息的前后代码片段（打印目标位置，上面3行，下面2行）
     let code_lines = source.lines().collect::<Vec<&str>>();
     let last_line = code_lines.len();
-    let last_column = code_lines.last().map_or(0, |s| s.len());
-    let source_target = get_source_position(source, pos);
+    let last_column = code_lines.last().map_or(0, |s| s.chars().count());
+    let source_target = get_source_position(line_index, pos);
     let target = source_target.map_or((last_line, last_column + 1), |v| v);
     let target_line = target.0 as isize;
     for (i, str) in code_lines.iter().enumerate() {
@@ -0,0 +1,418 @@
+use crate::lexer::Lexer;
+use crate::module::{is_exported, resolve_import, Module};
+use crate::shared::{KindName, BUILD_IN_FN_SIGNATURES, KEYWORDS};
+use crate::state::Parser;
+use crate::symbols::{SymbolKind, SymbolTable};
+use crate::token::{Token, TokenType};
+
+// 源码不完整时，最多尝试截断的次数
+const MAX_RECOVER_ATTEMPTS: usize = 8;
+
+// 可以用于标注的类型
+const KIND_NAMES: [KindName; 6] = [
+    KindName::Number,
+    KindName::Int32,
+    KindName::Int64,
+    KindName::UInt8,
+    KindName::Boolean,
+    KindName::String,
+];
+
+// 补全项的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CompletionKind {
+    Keyword,
+    Kind,
+    Function,
+    Argument,
+    Variable,
+    Label,
+    Import,
+}
+
+// 补全项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    pub detail: Option<String>, // 签名，如: `fn add(a: num) -> num`
+    pub doc: Option<String>,
+}
+
+impl CompletionItem {
+    fn new(label: &str, kind: CompletionKind) -> Self {
+        CompletionItem {
+            label: label.to_string(),
+            kind,
+            detail: None,
+            doc: None,
+        }
+    }
+}
+
+// 单个文件中某个位置（字符索引）的补全项，源码可以是编辑中不完整的代码
+pub fn complete(source: &str, offset: usize) -> Vec<CompletionItem> {
+    complete_in_modules(&[Module { path: "", source }], 0, offset)
+}
+
+// 在多个模块中补全，`import mod.{...}` 中会补全被导入模块公开的名称
+pub fn complete_in_modules(
+    modules: &[Module],
+    module: usize,
+    offset: usize,
+) -> Vec<CompletionItem> {
+    let source = modules[module].source;
+    let (tokens, error) = tokenize(source);
+
+    // 光标位于注释或字符串中
+    if tokens.iter().any(|token| is_inside(token, offset)) {
+        return vec![];
+    }
+    // 分析出错（如: 未闭合的字符串）之后的源码不再产生 token
+    if error.is_some() {
+        let last_end = tokens.last().map_or(0, |token| token.end);
+        let rest = source
+            .chars()
+            .skip(last_end)
+            .take(offset.saturating_sub(last_end));
+        if rest.collect::<String>().contains('"') {
+            return vec![];
+        }
+    }
+
+    let tokens = tokens
+        .iter()
+        .filter(|token| !is_trivia(token))
+        .collect::<Vec<&Token>>();
+    let mut before = tokens
+        .iter()
+        .copied()
+        .filter(|token| token.end <= offset)
+        .collect::<Vec<&Token>>();
+    // 光标前正在输入的名称
+    let mut prefix = "";
+    if let Some(token) = before.last() {
+        if token.end == offset
+            && matches!(
+                token.token_type,
+                TokenType::Identifier | TokenType::Keyword | TokenType::Boolean
+            )
+        {
+            prefix = &token.value;
+            before.pop();
+        }
+    }
+
+    let mut items = match context(source, &before) {
+        Context::None => vec![],
+        Context::Kind { allow_void } => complete_kinds(allow_void),
+        Context::Import {
+            source: import_source,
+            listed,
+        } => complete_imports(modules, module, &import_source, &listed),
+        Context::Label => match recover(source, &tokens, &before, offset) {
+            Some((table, offset)) => complete_symbols(&table, offset, true),
+            None => vec![],
+        },
+        Context::Statement => {
+            let mut items = match recover(source, &tokens, &before, offset) {
+                Some((table, offset)) => complete_symbols(&table, offset, false),
+                None => vec![],
+            };
+            for keyword in KEYWORDS.iter() {
+                items.push(CompletionItem::new(keyword, CompletionKind::Keyword));
+            }
+            items
+        }
+    };
+    items.retain(|item| item.label.starts_with(prefix));
+    items
+}
+
+// 光标所在的上下文
+enum Context {
+    None,                                           // 不需要补全，如: 声明的名称、属性
+    Statement,                                      // 语句或表达式
+    Label,                                          // `break`、`continue` 之后
+    Kind { allow_void: bool },                      // 类型标注，如: `let a: `、`-> `
+    Import { source: String, listed: Vec<String> }, // `import mod.{...}` 中
+}
+
+// 根据光标前的 token 判断上下文
+fn context(source: &str, before: &[&Token]) -> Context {
+    let last = match before.last() {
+        Some(token) => token,
+        None => return Context::Statement,
+    };
+    let is_keyword = |token: &Token, keywords: &[&str]| {
+        token.token_type == TokenType::Keyword && keywords.contains(&token.value.as_str())
+    };
+
+    if let Some(context) = import_context(source, before) {
+        return context;
+    }
+    match last.token_type {
+        TokenType::ReturnSym => Context::Kind { allow_void: true },
+        TokenType::Colon if before.len() >= 3 => {
+            // 变量及参数的类型标注，其他情况为循环标签
+            let declaration = before[before.len() - 3];
+            if before[before.len() - 2].token_type == TokenType::Identifier
+                && (is_keyword(declaration, &["let", "var", "const", "for"])
+                    || matches!(
+                        declaration.token_type,
+                        TokenType::ParenL | TokenType::Comma
+                    ))
+            {
+                Context::Kind { allow_void: false }
+            } else {
+                Context::Statement
+            }
+        }
+        TokenType::Dot | TokenType::Number => Context::None,
+        TokenType::Keyword if is_keyword(last, &["break", "continue"]) => Context::Label,
+        TokenType::Keyword
+            if is_keyword(
+                last,
+                &["fn", "let", "var", "const", "for", "import", "as"],
+            ) =>
+        {
+            Context::None
+        }
+        _ => Context::Statement,
+    }
+}
+
+// 是否位于 `import mod.{...}` 的花括号中，如: `import foo.{a, `
+fn import_context(source: &str, before: &[&Token]) -> Option<Context> {
+    let mut listed = vec![];
+    let mut index = before.len();
+    while index > 0 {
+        let token = before[index - 1];
+        match token.token_type {
+            TokenType::Identifier => listed.push(token.value.clone()),
+            TokenType::Comma | TokenType::Star => {}
+            TokenType::Keyword if token.value == "as" => {}
+            TokenType::BraceL => break,
+            _ => return None,
+        }
+        index -= 1;
+    }
+    if index < 2 || before[index - 2].token_type != TokenType::Dot {
+        return None;
+    }
+
+    // 导入路径，如: `../mod/b`、`<std/string>`
+    let dot = before[index - 2];
+    let mut index = index - 2;
+    while index > 0 {
+        let token = before[index - 1];
+        match token.token_type {
+            TokenType::Keyword if token.value == "import" => {
+                let import_source = source
+                    .chars()
+                    .skip(token.end)
+                    .take(dot.start - token.end)
+                    .collect::<String>();
+                return Some(Context::Import {
+                    source: import_source.trim().to_string(),
+                    listed,
+                });
+            }
+            TokenType::Identifier
+            | TokenType::Div
+            | TokenType::Range
+            | TokenType::Dot
+            | TokenType::LT
+            | TokenType::GT => index -= 1,
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn complete_kinds(allow_void: bool) -> Vec<CompletionItem> {
+    let mut items = KIND_NAMES
+        .iter()
+        .map(|kind_name| {
+            CompletionItem::new(&kind_name.to_string(), CompletionKind::Kind)
+        })
+        .collect::<Vec<CompletionItem>>();
+    if allow_void {
+        items.push(CompletionItem::new(
+            &KindName::Void.to_string(),
+            CompletionKind::Kind,
+        ));
+    }
+    items
+}
+
+// 某个位置可见的变量、参数、函数及导入的名称（或循环标签）
+fn complete_symbols(
+    table: &SymbolTable,
+    offset: usize,
+    is_label: bool,
+) -> Vec<CompletionItem> {
+    let symbols = table
+        .visible_at(offset)
+        .into_iter()
+        .filter(|symbol| (symbol.kind == SymbolKind::Label) == is_label)
+        .collect::<Vec<_>>();
+    let mut items = symbols
+        .iter()
+        .map(|symbol| CompletionItem {
+            label: symbol.name.to_string(),
+            kind: match symbol.kind {
+                SymbolKind::Function => CompletionKind::Function,
+                SymbolKind::Argument => CompletionKind::Argument,
+                SymbolKind::Variable => CompletionKind::Variable,
+                SymbolKind::Label => CompletionKind::Label,
+                SymbolKind::Import => CompletionKind::Import,
+            },
+            detail: Some(symbol.detail.to_string()),
+            doc: symbol.doc.clone(),
+        })
+        .collect::<Vec<CompletionItem>>();
+
+    // 未被同名符号覆盖的内置函数
+    if !is_label {
+        for (name, signature) in BUILD_IN_FN_SIGNATURES.iter() {
+            if symbols.iter().all(|symbol| symbol.name != *name) {
+                items.push(CompletionItem {
+                    label: name.to_string(),
+                    kind: CompletionKind::Function,
+                    detail: Some(signature.to_string()),
+                    doc: None,
+                });
+            }
+        }
+    }
+    items
+}
+
+// 被导入模块公开的名称，已导入的名称不再返回
+fn complete_imports(
+    modules: &[Module],
+    module: usize,
+    import_source: &str,
+    listed: &[String],
+) -> Vec<CompletionItem> {
+    let target = match resolve_import(modules, module, import_source) {
+        Some(target) if !import_source.starts_with('<') => target,
+        _ => return vec![],
+    };
+    let program = match Parser::try_parse(modules[target].source) {
+        Ok(program) => program,
+        Err(_) => return vec![],
+    };
+    let table = SymbolTable::new(&program);
+    let scope = match table.scopes.first() {
+        Some(scope) => scope,
+        None => return vec![],
+    };
+    scope
+        .symbols
+        .iter()
+        .map(|index| &table.symbols[*index])
+        .filter(|symbol| is_exported(&program, symbol) && !listed.contains(&symbol.name))
+        .map(|symbol| CompletionItem {
+            label: symbol.name.to_string(),
+            kind: match symbol.kind {
+                SymbolKind::Function => CompletionKind::Function,
+                _ => CompletionKind::Variable,
+            },
+            detail: Some(symbol.detail.to_string()),
+            doc: symbol.doc.clone(),
+        })
+        .collect()
+}
+
+// 构建光标处的符号表，源码无法解析时（如: 正在输入的语句不完整），
+// 从光标前的语句边界（`;`、`{`、`}`）处截断并补全缺少的 `}`，
+// 之后的顶层函数仍会保留，返回符号表及用于查询的位置
+fn recover(
+    source: &str,
+    tokens: &[&Token],
+    before: &[&Token],
+    offset: usize,
+) -> Option<(SymbolTable, usize)> {
+    if let Ok(program) = Parser::try_parse(source) {
+        return Some((SymbolTable::new(&program), offset));
+    }
+
+    let chars = source.chars().collect::<Vec<char>>();
+    // 光标之后位于行首的第一个顶层声明
+    let next_item = tokens.iter().find(|token| {
+        token.start >= offset
+            && token.token_type == TokenType::Keyword
+            && (token.value == "fn" || token.value == "pub")
+            && (token.start == 0 || chars[token.start - 1] == '\n')
+    });
+    let rest = next_item.map(|token| chars[token.start..].iter().collect::<String>());
+
+    let boundaries = before
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, token)| {
+            matches!(
+                token.token_type,
+                TokenType::Semi | TokenType::BraceL | TokenType::BraceR
+            )
+        })
+        .take(MAX_RECOVER_ATTEMPTS);
+    for (index, boundary) in boundaries {
+        let depth =
+            before[..=index]
+                .iter()
+                .fold(0, |depth, token| match token.token_type {
+                    TokenType::BraceL => depth + 1,
+                    TokenType::BraceR => depth - (depth > 0) as usize,
+                    _ => depth,
+                });
+        // 截断处之后补一个空格，查询位置在已闭合的块语句之外
+        let mut text = chars[..boundary.end].iter().collect::<String>();
+        text.push(' ');
+        text.push_str(&"}".repeat(depth));
+        for rest in rest.iter().map(|rest| rest.as_str()).chain(Some("")) {
+            let text = format!("{}\n{}", text, rest);
+            if let Ok(program) = Parser::try_parse(&text) {
+                return Some((SymbolTable::new(&program), boundary.end + 1));
+            }
+        }
+    }
+    None
+}
+
+// 分析整个源码（包含空白及注释），出错时返回出错之前的 token 及出错位置
+fn tokenize(source: &str) -> (Vec<Token>, Option<usize>) {
+    let mut tokens = vec![];
+    for token in Lexer::new(source, true) {
+        if token.token_type == TokenType::Error {
+            return (tokens, Some(token.start));
+        }
+        tokens.push(token);
+    }
+    (tokens, None)
+}
+
+fn is_trivia(token: &Token) -> bool {
+    matches!(
+        token.token_type,
+        TokenType::Whitespace | TokenType::Comment | TokenType::DocComment
+    )
+}
+
+// 位置是否位于注释或字符串内部，行注释及未闭合的块注释包含结尾处
+fn is_inside(token: &Token, offset: usize) -> bool {
+    match token.token_type {
+        TokenType::Comment | TokenType::DocComment => {
+            token.start < offset
+                && (offset < token.end
+                    || offset == token.end && !token.value.ends_with("*/"))
+        }
+        TokenType::String | TokenType::Template => {
+            token.start < offset && offset < token.end
+        }
+        _ => false,
+    }
+}
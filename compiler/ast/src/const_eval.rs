@@ -0,0 +1,97 @@
+use crate::node::Node;
+use std::ops::Deref;
+
+// 计算由字面量组成的布尔表达式的值，如: `true`、`!false`、`1 > 2`
+pub fn eval_const_bool(node: &Node) -> Option<bool> {
+    match node {
+        Node::BooleanLiteral { value, .. } => Some(*value),
+        Node::UnaryExpression {
+            argument, operator, ..
+        } if operator == "!" => eval_const_bool(argument).map(|value| !value),
+        Node::BinaryExpression {
+            left,
+            right,
+            operator,
+            ..
+        } => {
+            if let (
+                Node::NumberLiteral { value: a, .. },
+                Node::NumberLiteral { value: b, .. },
+            ) = (left.deref(), right.deref())
+            {
+                return match operator.as_str() {
+                    "==" => Some(a == b),
+                    "!=" => Some(a != b),
+                    "<" => Some(a < b),
+                    "<=" => Some(a <= b),
+                    ">" => Some(a > b),
+                    ">=" => Some(a >= b),
+                    _ => None,
+                };
+            }
+            let (a, b) = (eval_const_bool(left)?, eval_const_bool(right)?);
+            match operator.as_str() {
+                "&&" => Some(a && b),
+                "||" => Some(a || b),
+                "==" => Some(a == b),
+                "!=" => Some(a != b),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// 计算只由整数字面量组成的常量表达式，如: `255`、`2 * 8 - 1`
+pub fn eval_int_literal(node: &Node) -> Option<i128> {
+    match node {
+        // 整数按原始文本计算，避免超过 2^53 的值经过 f64 时丢失精度
+        Node::NumberLiteral { value, raw, .. } => match parse_int_raw(raw) {
+            Some(value) if value.unsigned_abs() <= u64::MAX as u128 => Some(value),
+            Some(_) => None,
+            None if value.fract() == 0.0 && value.abs() <= u64::MAX as f64 => {
+                Some(*value as i128)
+            }
+            None => None,
+        },
+        Node::BinaryExpression {
+            left,
+            right,
+            operator,
+            ..
+        } => {
+            let left = eval_int_literal(left)?;
+            let right = eval_int_literal(right)?;
+            match operator.as_str() {
+                "+" => left.checked_add(right),
+                "-" => left.checked_sub(right),
+                "*" => left.checked_mul(right),
+                "/" => left.checked_div(right),
+                "%" => left.checked_rem(right),
+                "&" => Some(left & right),
+                "|" => Some(left | right),
+                "^" => Some(left ^ right),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// 解析整数字面量的原始文本，如: `-1_000`、`0xff`，包含小数或指数部分时返回 None
+fn parse_int_raw(raw: &str) -> Option<i128> {
+    let raw = raw.replace('_', "");
+    let (is_negative, digits) = match raw.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, raw.as_str()),
+    };
+    let value = match digits.get(..2) {
+        Some("0x") => i128::from_str_radix(&digits[2..], 16),
+        Some("0o") => i128::from_str_radix(&digits[2..], 8),
+        Some("0b") => i128::from_str_radix(&digits[2..], 2),
+        _ => digits.parse::<i128>(),
+    };
+    value
+        .ok()
+        .map(|value| if is_negative { -value } else { value })
+}
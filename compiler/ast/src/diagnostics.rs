@@ -0,0 +1,86 @@
+use crate::code_frame::{print_code_frame_with_index, CodeFrameMessageType};
+use crate::line_index::LineIndex;
+
+// 诊断信息的级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+// 不会中止解析及编译的诊断信息
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub position: (usize, usize),
+}
+
+// 解析及编译过程中收集的诊断信息
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    pub items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Diagnostics { items: vec![] }
+    }
+
+    // 添加一条警告
+    pub fn warn(&mut self, position: (usize, usize), message: &str) {
+        self.items.push(Diagnostic {
+            severity: Severity::Warning,
+            message: message.to_string(),
+            position,
+        });
+    }
+
+    // 合并其他诊断信息（如: 编译时合并解析阶段的警告）
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.items.extend(other.items);
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.severity == severity)
+            .count()
+    }
+
+    // 将所有警告提升为错误
+    pub fn deny_warnings(&mut self) {
+        for item in self.items.iter_mut() {
+            item.severity = Severity::Error;
+        }
+    }
+
+    // 按位置顺序打印所有诊断信息的代码帧及数量统计，没有诊断信息时不打印
+    pub fn print(&self, source: &str) {
+        if self.items.is_empty() {
+            return;
+        }
+        let line_index = LineIndex::new(source);
+        let mut items = self.items.iter().collect::<Vec<&Diagnostic>>();
+        items.sort_by_key(|item| item.position);
+        for item in items {
+            let msg_type = match item.severity {
+                Severity::Warning => CodeFrameMessageType::Warn,
+                Severity::Error => CodeFrameMessageType::Error,
+            };
+            print_code_frame_with_index(
+                source,
+                &line_index,
+                item.position.0,
+                &item.message,
+                msg_type,
+            );
+            println!();
+        }
+        println!(
+            "{} warnings, {} errors",
+            self.count(Severity::Warning),
+            self.count(Severity::Error)
+        );
+    }
+}
@@ -1,30 +1,75 @@
 use crate::node::Node;
-use crate::shared::Kind;
-use crate::state::Parser;
-use crate::token::{Token, TokenType};
+use crate::shared::{Kind, KindName};
+use crate::state::{ParseResult, Parser};
+use crate::token::TokenType;
+
+// 类型转换 `as` 的优先级，高于二元运算符，低于一元运算符
+const CAST_PRECEDENCE: i8 = 16;
 
 impl<'a> Parser<'a> {
     // 解析表达式
-    pub(crate) fn parse_expression(&mut self) -> Option<Node> {
-        self.parse_maybe_binary_expression(-1)
+    pub(crate) fn parse_expression(&mut self) -> ParseResult<Option<Node>> {
+        match self.parse_maybe_binary_expression(-1)? {
+            Some(left) => Ok(Some(self.parse_maybe_assignment_expression(left)?)),
+            None => Ok(None),
+        }
+    }
+
+    // 解析赋值表达式（可能），赋值运算符优先级最低且为右结合，如: `a = b = 3`
+    pub(crate) fn parse_maybe_assignment_expression(
+        &mut self,
+        left: Node,
+    ) -> ParseResult<Node> {
+        if !self.current_token.is_assign() {
+            return Ok(left);
+        }
+
+        // 验证赋值目标
+        match left {
+            Node::Identifier { .. } | Node::MemberExpression { .. } => {}
+            _ => {
+                return self
+                    .unexpected_err(left.read_position().0, "Invalid assignment target")
+            }
+        }
+
+        let operator = self.current_token.value.to_string();
+        let mark_pos = self.current_token.start;
+        self.next_token()?;
+
+        let right = match self.parse_expression()? {
+            Some(right) => right,
+            None => return self.unexpected_err(mark_pos, "Missing initial value"),
+        };
+        Ok(Node::AssignmentExpression {
+            position: (left.read_position().0, right.read_position().1),
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
     }
 
     // 解析一个二元表达式（可能）
     pub(crate) fn parse_maybe_binary_expression(
         &mut self,
         current_precedence: i8,
-    ) -> Option<Node> {
+    ) -> ParseResult<Option<Node>> {
         if self.is_token(TokenType::ParenL) {
-            self.next_token();
-            let expr = self.parse_expression();
-            if expr.is_none() {
-                self.unexpected(Some("Missing expression"));
-            }
-            self.consume_or_panic(TokenType::ParenR);
-            self.parse_binary_expression_precedence(expr.unwrap(), current_precedence)
+            self.next_token()?;
+            let expr = match self.parse_expression()? {
+                Some(expr) => expr,
+                None => return self.unexpected(Some("Missing expression")),
+            };
+            self.consume_or_err(TokenType::ParenR)?;
+            let expr = self.parse_maybe_member_expression(expr)?;
+            self.parse_binary_expression_precedence(expr, current_precedence)
         } else {
-            let left = self.parse_maybe_unary_expression(current_precedence)?;
-            self.parse_binary_expression_precedence(left, current_precedence)
+            match self.parse_maybe_unary_expression(current_precedence)? {
+                Some(left) => {
+                    self.parse_binary_expression_precedence(left, current_precedence)
+                }
+                None => Ok(None),
+            }
         }
     }
 
@@ -32,29 +77,31 @@ impl<'a> Parser<'a> {
     pub(crate) fn parse_maybe_unary_expression(
         &mut self,
         current_precedence: i8,
-    ) -> Option<Node> {
+    ) -> ParseResult<Option<Node>> {
         if self.current_token.precedence <= current_precedence {
             return self.parse_atom_expression();
         }
 
         match self.current_token.token_type {
-            TokenType::Sub | TokenType::Plus => {
-                panic!("TODO: No implement")
-            }
+            TokenType::Sub | TokenType::Plus => self.unexpected(Some(&format!(
+                "Unary `{}` is not supported",
+                self.current_token.value
+            ))),
             TokenType::LogicNot | TokenType::BitNot => {
                 let operator = self.current_token.value.to_string();
                 let start = self.current_token.start;
-                self.next_token();
-                let argument = self.parse_expression();
-                if argument.is_none() {
-                    self.unexpected_err(start, "Incomplete unary expression");
-                }
-                let argument = argument.unwrap();
-                Some(Node::UnaryExpression {
+                self.next_token()?;
+                let argument = match self.parse_expression()? {
+                    Some(argument) => argument,
+                    None => {
+                        return self.unexpected_err(start, "Incomplete unary expression")
+                    }
+                };
+                Ok(Some(Node::UnaryExpression {
                     position: (start, argument.read_position().1),
                     operator,
                     argument: Box::new(argument),
-                })
+                }))
             }
             _ => self.parse_atom_expression(),
         }
@@ -65,32 +112,54 @@ impl<'a> Parser<'a> {
         &mut self,
         left: Node,
         current_precedence: i8,
-    ) -> Option<Node> {
+    ) -> ParseResult<Option<Node>> {
+        // 类型转换，如: `a as i32`，优先级高于所有二元运算符
+        if self.is_keyword("as") && CAST_PRECEDENCE > current_precedence {
+            self.next_token()?;
+            self.expect(TokenType::Identifier)?;
+            let kind_name = match KindName::from(&self.current_token.value, false) {
+                Some(kind_name) => kind_name,
+                None => return self.unexpected_kind(&self.current_token),
+            };
+            let node = Node::CastExpression {
+                position: (left.read_position().0, self.current_token.end),
+                expression: Box::new(left),
+                kind: kind_name.into(),
+            };
+            self.next_token()?;
+            return self.parse_binary_expression_precedence(node, current_precedence);
+        }
+
         let precedence = self.current_token.precedence;
 
         // 如果当前二元运算符优先级比当前上下文优先级高，优先组合（只有二元运算符优先级会大于0）
-        if precedence > 0 && precedence > current_precedence {
+        // 赋值运算符交由 `parse_maybe_assignment_expression` 处理
+        if precedence > 0
+            && precedence > current_precedence
+            && !self.current_token.is_assign()
+        {
             let operator = self.current_token.value.to_string();
-            if operator == "=" {
-                self.unexpected(None);
-            }
             let mark_pos = self.current_token.start;
-            self.next_token();
+            self.next_token()?;
 
             // 解析可能更高优先级的右侧表达式，如: `1 + 2 * 3` 将解析 `2 * 3` 作为右值
-            let maybe_higher_precedence_expr =
-                self.parse_maybe_binary_expression(precedence);
-            if maybe_higher_precedence_expr.is_none() {
-                self.unexpected_err(mark_pos, "Incomplete binary expression");
-            }
-            let right = self.parse_binary_expression_precedence(
-                maybe_higher_precedence_expr.unwrap(),
+            let maybe_higher_precedence_expr = match self
+                .parse_maybe_binary_expression(precedence)?
+            {
+                Some(expr) => expr,
+                None => {
+                    return self.unexpected_err(mark_pos, "Incomplete binary expression")
+                }
+            };
+            let right = match self.parse_binary_expression_precedence(
+                maybe_higher_precedence_expr,
                 precedence,
-            );
-            if right.is_none() {
-                self.unexpected_err(mark_pos, "Incomplete binary expression");
-            }
-            let right = right.unwrap();
+            )? {
+                Some(right) => right,
+                None => {
+                    return self.unexpected_err(mark_pos, "Incomplete binary expression")
+                }
+            };
             let node = Node::BinaryExpression {
                 position: (left.read_position().0, right.read_position().1),
                 operator,
@@ -102,90 +171,180 @@ impl<'a> Parser<'a> {
             // 如: `1 + 2 + 3`, 当前已经解析 `1 + 2`, 然后将该节点作为左值递归解析表达式优先级
             self.parse_binary_expression_precedence(node, current_precedence)
         } else {
-            Some(left)
+            Ok(Some(left))
+        }
+    }
+
+    // 解析一个原子表达式，如: `foo()`, `3.14`, `var1`, `true`, `"str"`, `s.len()`
+    pub(crate) fn parse_atom_expression(&mut self) -> ParseResult<Option<Node>> {
+        match self.parse_primary_expression()? {
+            Some(atom) => Ok(Some(self.parse_maybe_member_expression(atom)?)),
+            None => Ok(None),
         }
     }
 
-    // 解析一个原子表达式，如: `foo()`, `3.14`, `var1`, `var2 = expr`, `true`, `"str"`
-    pub(crate) fn parse_atom_expression(&mut self) -> Option<Node> {
+    // 解析一个基础表达式（不包含成员访问）
+    pub(crate) fn parse_primary_expression(&mut self) -> ParseResult<Option<Node>> {
         let token = self.current_token.clone();
-        match self.current_token.token_type {
+        let node = match self.current_token.token_type {
             TokenType::Identifier => {
-                self.next_token();
-                let next_token = &self.current_token;
-                let next_value = next_token.value.to_string();
-
-                match next_token.token_type {
-                    TokenType::ParenL => self.parse_call_expression(token),
-                    // 赋值表达式
-                    TokenType::Assign => {
-                        let left = self.gen_identifier(token, Kind::Infer);
-                        let mark_pos = self.current_token.start;
-                        self.next_token();
-
-                        let right = self.parse_expression();
-                        if right.is_none() {
-                            self.unexpected_err(mark_pos, "Missing initial value");
-                        }
-                        let right = right.unwrap();
-                        Some(Node::AssignmentExpression {
-                            position: (left.read_position().0, right.read_position().1),
-                            operator: next_value,
-                            left: Box::new(left),
-                            right: Box::new(right),
-                        })
-                    }
-                    _ => Some(self.gen_identifier(token, Kind::Infer)),
+                self.next_token()?;
+                if self.is_token(TokenType::ParenL) {
+                    let callee = self.gen_identifier(token, Kind::None);
+                    self.parse_call_expression(callee)?
+                } else {
+                    self.gen_identifier(token, Kind::Infer)
                 }
             }
             TokenType::Number => {
-                self.next_token();
-                Some(Node::NumberLiteral {
+                self.next_token()?;
+                Node::NumberLiteral {
                     position: (token.start, token.end),
+                    raw: self.read_source(token.start, token.end),
                     value: token.value.parse().unwrap(),
-                })
+                }
             }
             TokenType::Boolean => {
-                self.next_token();
-                Some(Node::BooleanLiteral {
+                self.next_token()?;
+                Node::BooleanLiteral {
                     position: (token.start, token.end),
                     value: token.value == "true",
-                })
+                }
             }
             TokenType::String => {
-                self.next_token();
-                Some(Node::StringLiteral {
+                self.next_token()?;
+                let is_raw = *self.chars.get(token.start).unwrap() == 'r';
+                let raw_start = if is_raw { 2 } else { 1 };
+                Node::StringLiteral {
                     position: (token.start, token.end),
-                    is_raw: *self.chars.get(token.start).unwrap() == 'r',
+                    raw: self.read_source(token.start + raw_start, token.end - 1),
+                    is_raw,
                     value: token.value,
-                })
+                }
+            }
+            TokenType::Template => self.parse_template_literal()?,
+            _ => return Ok(None),
+        };
+        Ok(Some(node))
+    }
+
+    // 解析模板字符串，如: `"a + b = ${a + b}"`
+    pub(crate) fn parse_template_literal(&mut self) -> ParseResult<Node> {
+        let start = self.current_token.start;
+        let mut quasis = vec![];
+        let mut raw_quasis = vec![];
+        let mut expressions = vec![];
+        loop {
+            // 每一部分以 `"` 或 `}` 开始，以 `${` 或 `"` 结束
+            let token = self.current_token.clone();
+            let is_end = token.token_type == TokenType::String;
+            let raw_end = if is_end { token.end - 1 } else { token.end - 2 };
+            raw_quasis.push(self.read_source(token.start + 1, raw_end));
+            quasis.push(token.value);
+            if is_end {
+                self.next_token()?;
+                return Ok(Node::TemplateLiteral {
+                    quasis,
+                    raw_quasis,
+                    expressions,
+                    position: (start, token.end),
+                });
             }
-            _ => None,
+
+            // `${` 之后解析插值表达式
+            let mark_pos = token.end;
+            self.next_token()?;
+            let expression = match self.parse_expression()? {
+                Some(expression) => expression,
+                None => {
+                    return self.unexpected_err(
+                        mark_pos,
+                        "Missing expression in template literal",
+                    )
+                }
+            };
+            self.expect(TokenType::BraceR)?;
+            expressions.push(Box::new(expression));
+
+            // `}` 之后继续读取字符串剩余的部分
+            let brace_pos = self.current_token.start;
+            self.current_token = self.read_string_part(brace_pos, false)?;
+        }
+    }
+
+    // 解析区间表达式，如: `0..10`（目前只能用于 for 循环）
+    pub(crate) fn parse_range_expression(&mut self) -> ParseResult<Node> {
+        let mark_pos = self.current_token.start;
+        let start = match self.parse_expression()? {
+            Some(start) => start,
+            None => return self.unexpected_err(mark_pos, "Missing range start"),
+        };
+        if !self.is_token(TokenType::Range) {
+            return self.unexpected(Some(
+                "Only range expressions can be iterated, such as: `0..10`",
+            ));
         }
+        let mark_pos = self.current_token.start;
+        self.next_token()?;
+
+        let end = match self.parse_expression()? {
+            Some(end) => end,
+            None => return self.unexpected_err(mark_pos, "Missing range end"),
+        };
+        Ok(Node::RangeExpression {
+            position: (start.read_position().0, end.read_position().1),
+            start: Box::new(start),
+            end: Box::new(end),
+        })
+    }
+
+    // 解析成员访问及方法调用（可能），如: `s.len()`, `n.floor().to_str()`
+    pub(crate) fn parse_maybe_member_expression(
+        &mut self,
+        object: Node,
+    ) -> ParseResult<Node> {
+        let mut object = object;
+        while self.is_token(TokenType::Dot) {
+            self.next_token()?;
+            self.expect(TokenType::Identifier)?;
+            let property_token = self.current_token.clone();
+            self.next_token()?;
+
+            let member = Node::MemberExpression {
+                position: (object.read_position().0, property_token.end),
+                object: Box::new(object),
+                property: Box::new(self.gen_identifier(property_token, Kind::None)),
+            };
+            object = if self.is_token(TokenType::ParenL) {
+                self.parse_call_expression(member)?
+            } else {
+                member
+            };
+        }
+        Ok(object)
     }
 
     // 解析函数调用
-    pub(crate) fn parse_call_expression(&mut self, callee_token: Token) -> Option<Node> {
-        let start = callee_token.start;
-        let callee = Box::new(self.gen_identifier(callee_token, Kind::None));
+    pub(crate) fn parse_call_expression(&mut self, callee: Node) -> ParseResult<Node> {
+        let start = callee.read_position().0;
 
         // arguments
         let mut arguments = vec![];
-        self.consume_or_panic(TokenType::ParenL);
+        self.consume_or_err(TokenType::ParenL)?;
         while self.check_valid_index() && !self.is_token(TokenType::ParenR) {
-            let arg = self.parse_expression();
-            if arg.is_none() {
-                self.unexpected(Some("Invalid argument"))
-            }
-            arguments.push(Box::new(arg.unwrap()));
-            self.consume(TokenType::Comma);
+            let arg = match self.parse_expression()? {
+                Some(arg) => arg,
+                None => return self.unexpected(Some("Invalid argument")),
+            };
+            arguments.push(Box::new(arg));
+            self.consume(TokenType::Comma)?;
         }
         let end = self.current_token.end;
-        self.consume_or_panic(TokenType::ParenR);
+        self.consume_or_err(TokenType::ParenR)?;
 
-        Some(Node::CallExpression {
+        Ok(Node::CallExpression {
             position: (start, end),
-            callee,
+            callee: Box::new(callee),
             arguments,
         })
     }
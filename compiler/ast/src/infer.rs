@@ -0,0 +1,411 @@
+use crate::const_eval::eval_int_literal;
+use crate::node::Node;
+use crate::shared::{Kind, KindName, BUILD_IN_FNS, BUILD_IN_METHODS};
+use crate::symbols::{SymbolKind, SymbolTable};
+use crate::visitor::Visitor;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+// 未标注类型的变量推断出的类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VariableKind {
+    pub name: String,
+    pub position: (usize, usize), // 变量名的位置
+    pub symbol: usize,            // 在符号表中的下标
+    pub kind: KindName,
+}
+
+// 表达式的类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExpressionKind {
+    pub position: (usize, usize),
+    pub kind: KindName,
+}
+
+// 与标注类型不一致的变量初始值或返回值，如: `let a: str = 1;`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KindMismatch {
+    pub position: (usize, usize), // 初始值或返回值表达式的位置
+    pub expected: KindName,
+    pub found: KindName,
+}
+
+// 推断出的类型（与编译时的推断规则一致），用于编辑器的类型提示等，
+// 无法推断的（如: 类型不匹配、调用未定义的函数）不会包含在内
+#[derive(Debug, Serialize)]
+pub struct InferredKinds {
+    pub variables: Vec<VariableKind>, // 未标注类型的变量（包括 for 循环的变量）
+    pub expressions: Vec<ExpressionKind>, // 所有表达式，外层在前
+    pub mismatches: Vec<KindMismatch>, // 编译时会报错的类型不匹配
+}
+
+impl InferredKinds {
+    pub fn new(program: &Node, symbols: &SymbolTable) -> Self {
+        let resolver = RefCell::new(Resolver {
+            symbols,
+            variable_kinds: HashMap::new(),
+            return_kind: None,
+            result: InferredKinds {
+                variables: vec![],
+                expressions: vec![],
+                mismatches: vec![],
+            },
+        });
+        Visitor::walk_with_exit(
+            program,
+            &mut |node, _| resolver.borrow_mut().enter(node),
+            &mut |node, _| {
+                if let Node::FunctionDeclaration { .. } = node {
+                    resolver.borrow_mut().return_kind = None;
+                }
+            },
+        );
+        resolver.into_inner().result
+    }
+
+    // 未标注类型的变量推断出的类型
+    pub fn symbol_kind(&self, symbol: usize) -> Option<KindName> {
+        self.variables
+            .iter()
+            .find(|variable| variable.symbol == symbol)
+            .map(|variable| variable.kind)
+    }
+
+    // 包含某个位置的最内层表达式的类型
+    pub fn expression_at(&self, offset: usize) -> Option<&ExpressionKind> {
+        self.expressions
+            .iter()
+            .filter(|expr| expr.position.0 <= offset && offset <= expr.position.1)
+            .min_by_key(|expr| expr.position.1 - expr.position.0)
+    }
+}
+
+struct Resolver<'a> {
+    symbols: &'a SymbolTable,
+    variable_kinds: HashMap<usize, KindName>, // 符号下标 -> 推断出的类型
+    return_kind: Option<Kind>,                // 当前函数的返回类型
+    result: InferredKinds,
+}
+
+impl<'a> Resolver<'a> {
+    // 按源码顺序处理语句，变量只能引用之前声明的变量
+    fn enter(&mut self, node: &Node) {
+        match node {
+            Node::FunctionDeclaration { return_kind, .. } => {
+                self.return_kind = Some(*return_kind);
+            }
+            Node::VariableDeclaration { id, init, .. } => {
+                let (_, kind, _) = id.read_identifier();
+                match kind {
+                    Kind::Some(kind_name) => {
+                        self.check(init, *kind_name);
+                        self.record(init, Some(*kind_name));
+                    }
+                    _ => {
+                        let kind_name = self.kind_of(init, None);
+                        self.record(init, None);
+                        self.declare(id, kind_name);
+                    }
+                }
+            }
+            Node::ForStatement { id, iterable, .. } => {
+                if let Node::RangeExpression { start, end, .. } = iterable.deref() {
+                    let (start_kind, end_kind) = self.binary_kinds(start, end);
+                    self.record(start, start_kind);
+                    self.record(end, end_kind);
+                    if !id.read_identifier().1.is_exact() {
+                        self.declare(id, start_kind.filter(|_| start_kind == end_kind));
+                    }
+                }
+            }
+            Node::ReturnStatement {
+                argument: Some(argument),
+                ..
+            } => {
+                let expected = self
+                    .return_kind
+                    .and_then(|kind| kind.read_kind_name().copied());
+                if let Some(expected) = expected {
+                    self.check(argument, expected);
+                }
+                self.record(argument, expected);
+            }
+            Node::ExpressionStatement { expression, .. } => self.record(expression, None),
+            Node::IfStatement { condition, .. }
+            | Node::WhileStatement { condition, .. } => self.record(condition, None),
+            _ => {}
+        }
+    }
+
+    fn declare(&mut self, id: &Node, kind_name: Option<KindName>) {
+        let (name, _, start) = id.read_identifier();
+        if let (Some(kind_name), Some(symbol)) =
+            (kind_name, self.symbols.symbol_at(start))
+        {
+            self.variable_kinds.insert(symbol, kind_name);
+            self.result.variables.push(VariableKind {
+                name: name.to_string(),
+                position: id.read_position(),
+                symbol,
+                kind: kind_name,
+            });
+        }
+    }
+
+    // 表达式能推断出类型且与期望的类型不同时记录为类型不匹配
+    fn check(&mut self, node: &Node, expected: KindName) {
+        if let Some(found) = self.kind_of(node, Some(expected)) {
+            if found != expected {
+                self.result.mismatches.push(KindMismatch {
+                    position: node.read_position(),
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    // 记录表达式及其子表达式的类型，`expected` 为期望的类型（影响整数字面量的类型）
+    fn record(&mut self, node: &Node, expected: Option<KindName>) {
+        let kind_name = match self.kind_of(node, expected) {
+            Some(kind_name) => kind_name,
+            None => {
+                // 自身无法推断时，子表达式仍可能推断出类型，如: 类型不一致的二元表达式
+                self.record_children(node, None);
+                return;
+            }
+        };
+        self.result.expressions.push(ExpressionKind {
+            position: node.read_position(),
+            kind: kind_name,
+        });
+
+        // 整数字面量组成的常量表达式整体按期望的类型编译
+        if kind_name.is_integer()
+            && Some(kind_name) == expected
+            && eval_int_literal(node).is_some()
+        {
+            self.record_children(node, expected);
+            return;
+        }
+        self.record_children(node, None);
+    }
+
+    fn record_children(&mut self, node: &Node, expected: Option<KindName>) {
+        match node {
+            Node::BinaryExpression { left, right, .. } => match expected {
+                Some(_) => {
+                    self.record(left, expected);
+                    self.record(right, expected);
+                }
+                None => {
+                    let (left_kind, right_kind) = self.binary_kinds(left, right);
+                    self.record(left, left_kind);
+                    self.record(right, right_kind);
+                }
+            },
+            Node::UnaryExpression { argument, .. } => self.record(argument, None),
+            Node::CastExpression { expression, .. } => self.record(expression, None),
+            Node::AssignmentExpression { left, right, .. } => {
+                let left_kind = self.kind_of(left, None);
+                self.record(left, None);
+                self.record(right, left_kind);
+            }
+            Node::CallExpression {
+                callee, arguments, ..
+            } => {
+                let argument_kinds = self.argument_kinds(callee);
+                if let Node::MemberExpression { object, .. } = callee.deref() {
+                    self.record(object, None);
+                }
+                for (index, argument) in arguments.iter().enumerate() {
+                    let expected = argument_kinds.get(index).copied().flatten();
+                    self.record(argument, expected);
+                }
+            }
+            Node::TemplateLiteral { expressions, .. } => {
+                for expression in expressions.iter() {
+                    self.record(expression, None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // 推断表达式的类型，与 codegen 中 `infer_expression_kind_as` 的规则一致
+    fn kind_of(&self, node: &Node, expected: Option<KindName>) -> Option<KindName> {
+        if let Some(kind_name) = expected {
+            if kind_name.is_integer() && eval_int_literal(node).is_some() {
+                return Some(kind_name);
+            }
+        }
+
+        match node {
+            Node::NumberLiteral { .. } => Some(KindName::Number),
+            Node::BooleanLiteral { .. } => Some(KindName::Boolean),
+            Node::StringLiteral { .. } | Node::TemplateLiteral { .. } => {
+                Some(KindName::String)
+            }
+            Node::Identifier { kind, position, .. } => match kind {
+                Kind::Some(kind_name) => Some(*kind_name),
+                _ => self.identifier_kind(position.0),
+            },
+            Node::CallExpression { callee, .. } => match callee.deref() {
+                // 内置类型的方法调用
+                Node::MemberExpression {
+                    object, property, ..
+                } => {
+                    let object_kind = self.kind_of(object, None)?;
+                    let (method, ..) = property.read_identifier();
+                    BUILD_IN_METHODS
+                        .iter()
+                        .find(|(kind_name, name, ..)| {
+                            *kind_name == object_kind && *name == method
+                        })
+                        .map(|(.., return_kind_name)| *return_kind_name)
+                }
+                Node::Identifier { name, position, .. } => {
+                    match self.symbols.definition_at(position.0) {
+                        Some(symbol) if symbol.kind == SymbolKind::Function => {
+                            match symbol.value_kind {
+                                Kind::Some(KindName::Void) => None,
+                                Kind::Some(kind_name) => Some(kind_name),
+                                _ => None,
+                            }
+                        }
+                        Some(_) => None,
+                        None => BUILD_IN_FNS
+                            .iter()
+                            .find(|(fn_name, _)| fn_name == name)
+                            .map(|(_, kind_name)| *kind_name),
+                    }
+                }
+                _ => None,
+            },
+            Node::BinaryExpression {
+                left,
+                right,
+                operator,
+                ..
+            } => {
+                let (left_kind, right_kind) = self.binary_kinds(left, right);
+                let kind_name = left_kind?;
+                if right_kind != Some(kind_name) {
+                    return None;
+                }
+                match (kind_name, operator.as_str()) {
+                    (_, "==" | "!=") => Some(KindName::Boolean),
+                    (_, "<" | "<=" | ">" | ">=") if kind_name.is_numeric() => {
+                        Some(KindName::Boolean)
+                    }
+                    (_, "+" | "-" | "*" | "/" | "%" | "&" | "|" | "^")
+                        if kind_name.is_numeric() =>
+                    {
+                        Some(kind_name)
+                    }
+                    (KindName::Boolean, "&&" | "||") => Some(KindName::Boolean),
+                    (KindName::String, "+") => Some(KindName::String),
+                    _ => None,
+                }
+            }
+            Node::UnaryExpression {
+                argument, operator, ..
+            } => {
+                let kind_name = self.kind_of(argument, None)?;
+                match operator.as_str() {
+                    "~" if kind_name.is_numeric() => Some(kind_name),
+                    "!" if kind_name == KindName::Boolean => Some(KindName::Boolean),
+                    _ => None,
+                }
+            }
+            Node::CastExpression {
+                expression, kind, ..
+            } => {
+                // 只支持数字及布尔类型之间的转换
+                let is_castable = |k: KindName| k.is_numeric() || k == KindName::Boolean;
+                let from_kind = self.kind_of(expression, None)?;
+                let to_kind = *kind.read_kind_name()?;
+                match is_castable(from_kind) && is_castable(to_kind) {
+                    true => Some(to_kind),
+                    false => None,
+                }
+            }
+            Node::AssignmentExpression { left, .. } => self.kind_of(left, None),
+            _ => None,
+        }
+    }
+
+    // 二元表达式两端的类型，字面量一端跟随另一端的整数类型，如: `a + 1`
+    fn binary_kinds(
+        &self,
+        left: &Node,
+        right: &Node,
+    ) -> (Option<KindName>, Option<KindName>) {
+        let left_kind = self.kind_of(left, None);
+        let right_kind = self.kind_of(right, left_kind);
+        let left_kind = self.kind_of(left, right_kind);
+        (left_kind, right_kind)
+    }
+
+    // 变量或参数的类型
+    fn identifier_kind(&self, offset: usize) -> Option<KindName> {
+        let index = self.symbols.symbol_at(offset)?;
+        let symbol = &self.symbols.symbols[index];
+        match symbol.kind {
+            SymbolKind::Variable | SymbolKind::Argument => match symbol.value_kind {
+                Kind::Some(kind_name) => Some(kind_name),
+                _ => self.variable_kinds.get(&index).copied(),
+            },
+            _ => None,
+        }
+    }
+
+    // 被调用函数各参数的类型
+    fn argument_kinds(&self, callee: &Node) -> Vec<Option<KindName>> {
+        match callee {
+            Node::MemberExpression {
+                object, property, ..
+            } => {
+                let object_kind = self.kind_of(object, None);
+                let (method, ..) = property.read_identifier();
+                BUILD_IN_METHODS
+                    .iter()
+                    .find(|(kind_name, name, ..)| {
+                        Some(*kind_name) == object_kind && *name == method
+                    })
+                    .map(|(_, _, argument_kinds, _)| {
+                        argument_kinds
+                            .iter()
+                            .map(|kind_name| Some(*kind_name))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            Node::Identifier { position, .. } => {
+                let symbol = match self.symbols.definition_at(position.0) {
+                    Some(symbol) if symbol.kind == SymbolKind::Function => symbol,
+                    _ => return vec![],
+                };
+                // 参数声明在函数的作用域中
+                let scope = self
+                    .symbols
+                    .scopes
+                    .iter()
+                    .find(|scope| scope.position == symbol.declaration);
+                scope
+                    .map(|scope| {
+                        scope
+                            .symbols
+                            .iter()
+                            .map(|index| &self.symbols.symbols[*index])
+                            .filter(|symbol| symbol.kind == SymbolKind::Argument)
+                            .map(|symbol| symbol.value_kind.read_kind_name().copied())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            _ => vec![],
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::shared::is_identifier_char;
+use crate::state::{ParseResult, Parser};
+use crate::token::{CommentKind, Token, TokenType};
+
+// 词法分析器的状态，保存后可以从对应位置恢复分析（如: 编辑器按行增量高亮）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LexerState {
+    pub allow_expr: bool, // 当前上下文是否允许表达式（影响 `-1`、`*` 的识别）
+    pub template_depths: Vec<usize>, // 每层模板插值中尚未闭合的 `{` 数量
+    pub comment_depth: usize, // 尚未闭合的块注释层数
+}
+
+impl LexerState {
+    pub fn new() -> Self {
+        LexerState {
+            allow_expr: true,
+            template_depths: vec![],
+            comment_depth: 0,
+        }
+    }
+}
+
+impl Default for LexerState {
+    fn default() -> Self {
+        LexerState::new()
+    }
+}
+
+// 对外公开的词法分析器，逐个产出 token（位置均为字符索引）
+pub struct Lexer<'a> {
+    parser: Parser<'a>,
+    state: LexerState,
+    include_trivia: bool, // 是否产出空白及注释 token
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str, include_trivia: bool) -> Self {
+        Lexer::resume(input, 0, LexerState::new(), include_trivia)
+    }
+
+    // 从指定位置及状态处继续分析
+    pub fn resume(
+        input: &'a str,
+        offset: usize,
+        state: LexerState,
+        include_trivia: bool,
+    ) -> Self {
+        let mut parser = Parser::new(input);
+        parser.is_start = false;
+        parser.index = offset;
+        parser.allow_expr = state.allow_expr;
+        parser.current_char = parser.look_behind(0);
+        Lexer {
+            parser,
+            state,
+            include_trivia,
+        }
+    }
+
+    // 当前位置的状态，配合 `offset()` 可以在之后恢复分析
+    pub fn state(&self) -> LexerState {
+        LexerState {
+            allow_expr: self.parser.allow_expr,
+            ..self.state.clone()
+        }
+    }
+
+    // 当前光标位置
+    pub fn offset(&self) -> usize {
+        self.parser.index
+    }
+
+    // 读取空白或注释，不是则返回 None
+    fn read_trivia(&mut self) -> Option<Token> {
+        let p = &mut self.parser;
+        let start = p.index;
+        let token_type = if self.state.comment_depth > 0 {
+            // 上次分析结束时块注释尚未闭合
+            let (_, depth) = p.read_block_comment_body(self.state.comment_depth);
+            self.state.comment_depth = depth;
+            TokenType::Comment
+        } else if p.is_space_char() {
+            while p.is_space_char() {
+                p.move_index(1);
+            }
+            TokenType::Whitespace
+        } else if p.current_char == '/' && p.look_behind(1) == '/' {
+            match p.read_line_comment(start).kind {
+                CommentKind::Doc => TokenType::DocComment,
+                _ => TokenType::Comment,
+            }
+        } else if p.current_char == '/' && p.look_behind(1) == '*' {
+            p.move_index(2);
+            let (_, depth) = p.read_block_comment_body(1);
+            self.state.comment_depth = depth;
+            TokenType::Comment
+        } else {
+            return None;
+        };
+
+        Some(Token {
+            token_type,
+            value: p.read_source(start, p.index),
+            precedence: -1,
+            start,
+            end: p.index,
+        })
+    }
+
+    // 读取下一个 token，插值结束的 `}` 与之后的模板字符串合并为一个 token（与解析时一致）
+    fn read_token(&mut self) -> ParseResult<Token> {
+        self.parser.next_token()?;
+        let token = self.parser.current_token.clone();
+        let depths = &mut self.state.template_depths;
+        match token.token_type {
+            TokenType::Template => depths.push(0),
+            TokenType::BraceL => {
+                if let Some(depth) = depths.last_mut() {
+                    *depth += 1;
+                }
+            }
+            TokenType::BraceR => match depths.last().copied() {
+                Some(0) => {
+                    depths.pop();
+                    let part = self.parser.read_string_part(token.start, false)?;
+                    if part.token_type == TokenType::Template {
+                        self.state.template_depths.push(0);
+                    }
+                    return Ok(part);
+                }
+                Some(depth) => *depths.last_mut().unwrap() = depth - 1,
+                None => {}
+            },
+            _ => {}
+        }
+        Ok(token)
+    }
+
+    // 无法识别的字符或不完整的字面量产生一个 Error token，之后从出错处继续分析，
+    // 错误的数字字面量包含之后紧跟的字符，如: `1.2.3`、`12px`
+    fn error_token(&mut self, start: usize) -> Token {
+        let p = &mut self.parser;
+        let mut end = p.index.max(start + 1).min(p.chars.len());
+        if p.chars[start].is_ascii_digit() {
+            while end < p.chars.len()
+                && (is_identifier_char(p.chars[end]) || p.chars[end] == '.')
+            {
+                end += 1;
+            }
+        }
+        p.index = end;
+        p.current_char = p.look_behind(0);
+        p.allow_expr = false;
+        Token {
+            token_type: TokenType::Error,
+            value: p.read_source(start, end),
+            precedence: -1,
+            start,
+            end,
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        while self.parser.index < self.parser.chars.len() {
+            match self.read_trivia() {
+                Some(token) if self.include_trivia => return Some(token),
+                Some(_) => continue,
+                None => {
+                    let start = self.parser.index;
+                    return match self.read_token() {
+                        Ok(token) if token.token_type == TokenType::EOF => None,
+                        Ok(token) => Some(token),
+                        Err(_) => Some(self.error_token(start)),
+                    };
+                }
+            }
+        }
+        None
+    }
+}
@@ -1,12 +1,24 @@
 #[macro_use]
 extern crate serde_derive;
 
+pub mod cfg;
 pub mod code_frame;
+pub mod completion;
+pub mod const_eval;
+pub mod diagnostics;
 pub mod expression;
+pub mod lexer;
+pub mod infer;
+pub mod line_index;
+pub mod lint;
+pub mod module;
 pub mod node;
+pub mod rename;
 pub mod shared;
 pub mod state;
 pub mod statements;
+pub mod symbols;
+pub mod text_edit;
 pub mod token;
 pub mod visitor;
 mod externs;
@@ -0,0 +1,187 @@
+// 非 ASCII 字符的位置信息，用于在各种偏移之间换算
+#[derive(Debug, Clone, Copy)]
+struct WideChar {
+    index: usize, // 字符索引
+    byte: usize,  // UTF-8 字节偏移
+    utf16: usize, // UTF-16 偏移
+    len_utf8: usize,
+    len_utf16: usize,
+}
+
+// 行列位置（从 0 开始）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+// 源码的行索引，在字符索引（AST 中的位置）、UTF-8 字节偏移、UTF-16 偏移及行列之间换算，
+// 构建时扫描一遍源码，之后每次换算的复杂度为 O(log n)
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,   // 每行开始位置的字符索引
+    wide_chars: Vec<WideChar>, // 所有非 ASCII 字符，按位置排序
+    len: usize,                // 字符总数
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars = vec![];
+        let mut utf16 = 0;
+        let mut len = 0;
+        for (index, (byte, ch)) in source.char_indices().enumerate() {
+            if ch == '\n' {
+                line_starts.push(index + 1);
+            }
+            if !ch.is_ascii() {
+                wide_chars.push(WideChar {
+                    index,
+                    byte,
+                    utf16,
+                    len_utf8: ch.len_utf8(),
+                    len_utf16: ch.len_utf16(),
+                });
+            }
+            utf16 += ch.len_utf16();
+            len = index + 1;
+        }
+
+        LineIndex {
+            line_starts,
+            wide_chars,
+            len,
+        }
+    }
+
+    // 字符总数
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    // 源码是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 总行数
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    // 某一行开始位置的字符索引
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line).copied()
+    }
+
+    // 某一行结束位置（不含换行符）的字符索引
+    pub fn line_end(&self, line: usize) -> Option<usize> {
+        self.line_start(line)?;
+        match self.line_start(line + 1) {
+            Some(next) => Some(next - 1),
+            None => Some(self.len),
+        }
+    }
+
+    // 字符索引 -> 行列（列为字符数）
+    pub fn line_col(&self, index: usize) -> LineCol {
+        let index = index.min(self.len);
+        let line = self.line_starts.partition_point(|start| *start <= index) - 1;
+        LineCol {
+            line,
+            column: index - self.line_starts[line],
+        }
+    }
+
+    // 行列（列为字符数） -> 字符索引，超出该行时返回 None
+    pub fn index_of(&self, position: LineCol) -> Option<usize> {
+        let start = self.line_start(position.line)?;
+        let index = start + position.column;
+        if index > self.line_end(position.line)? {
+            return None;
+        }
+        Some(index)
+    }
+
+    // 字符索引 -> 行列（列为 UTF-16 长度，如: LSP、VSCode）
+    pub fn line_col_utf16(&self, index: usize) -> LineCol {
+        let position = self.line_col(index);
+        let start = self.line_starts[position.line];
+        LineCol {
+            line: position.line,
+            column: self.char_to_utf16(start + position.column)
+                - self.char_to_utf16(start),
+        }
+    }
+
+    // 行列（列为 UTF-16 长度） -> 字符索引，超出该行时返回 None
+    pub fn index_of_utf16(&self, position: LineCol) -> Option<usize> {
+        let start = self.line_start(position.line)?;
+        let end = self.line_end(position.line)?;
+        let index = self.utf16_to_char(self.char_to_utf16(start) + position.column);
+        if index > end {
+            return None;
+        }
+        Some(index)
+    }
+
+    // 字符索引 -> UTF-8 字节偏移
+    pub fn char_to_byte(&self, index: usize) -> usize {
+        let index = index.min(self.len);
+        let n = self.wide_chars.partition_point(|ch| ch.index < index);
+        match n {
+            0 => index,
+            _ => {
+                let ch = &self.wide_chars[n - 1];
+                ch.byte + ch.len_utf8 + (index - ch.index - 1)
+            }
+        }
+    }
+
+    // UTF-8 字节偏移 -> 字符索引，位于字符中间时返回该字符的索引
+    pub fn byte_to_char(&self, byte: usize) -> usize {
+        let n = self.wide_chars.partition_point(|ch| ch.byte < byte);
+        let index = match n {
+            0 => byte,
+            _ => {
+                let ch = &self.wide_chars[n - 1];
+                if byte < ch.byte + ch.len_utf8 {
+                    ch.index
+                } else {
+                    ch.index + 1 + (byte - ch.byte - ch.len_utf8)
+                }
+            }
+        };
+        index.min(self.len)
+    }
+
+    // 字符索引 -> UTF-16 偏移
+    pub fn char_to_utf16(&self, index: usize) -> usize {
+        let index = index.min(self.len);
+        let n = self.wide_chars.partition_point(|ch| ch.index < index);
+        match n {
+            0 => index,
+            _ => {
+                let ch = &self.wide_chars[n - 1];
+                ch.utf16 + ch.len_utf16 + (index - ch.index - 1)
+            }
+        }
+    }
+
+    // UTF-16 偏移 -> 字符索引，位于代理对中间时返回该字符的索引
+    pub fn utf16_to_char(&self, utf16: usize) -> usize {
+        let n = self.wide_chars.partition_point(|ch| ch.utf16 < utf16);
+        let index = match n {
+            0 => utf16,
+            _ => {
+                let ch = &self.wide_chars[n - 1];
+                if utf16 < ch.utf16 + ch.len_utf16 {
+                    ch.index
+                } else {
+                    ch.index + 1 + (utf16 - ch.utf16 - ch.len_utf16)
+                }
+            }
+        };
+        index.min(self.len)
+    }
+}
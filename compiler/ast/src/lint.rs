@@ -0,0 +1,653 @@
+use crate::const_eval::eval_const_bool;
+use crate::lexer::Lexer;
+use crate::module::is_exported;
+use crate::node::Node;
+use crate::shared::is_identifier_char;
+use crate::state::{ParseError, Parser};
+use crate::symbols::{SymbolKind, SymbolTable};
+use crate::text_edit::{apply_edits, TextEdit};
+use crate::token::TokenType;
+use crate::visitor::Visitor;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+// 规则的级别：忽略、警告、报错
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+// 检查出的问题
+#[derive(Debug, Clone, Serialize)]
+pub struct LintDiagnostic {
+    pub rule: &'static str,
+    pub level: LintLevel,
+    pub message: String,
+    pub position: (usize, usize),
+    pub fix: Option<Fix>,
+}
+
+// 可以自动修复的问题的修改建议
+#[derive(Debug, Clone, Serialize)]
+pub struct Fix {
+    pub title: String, // 如: `Remove unused variable `a``
+    pub edits: Vec<TextEdit>,
+}
+
+// 检查规则，`level` 为未配置时的默认级别
+pub struct LintRule {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub level: LintLevel,
+    check: fn(&mut LintContext),
+}
+
+// 内置的检查规则
+pub const RULES: [LintRule; 10] = [
+    LintRule {
+        name: "unused-variable",
+        description: "Variables that are declared but never used",
+        level: LintLevel::Warn,
+        check: check_unused_variables,
+    },
+    LintRule {
+        name: "unused-argument",
+        description: "Function arguments that are never used",
+        level: LintLevel::Warn,
+        check: check_unused_arguments,
+    },
+    LintRule {
+        name: "unused-function",
+        description: "Private functions that are never called",
+        level: LintLevel::Warn,
+        check: check_unused_functions,
+    },
+    LintRule {
+        name: "unused-import",
+        description: "Import specifiers that are never used",
+        level: LintLevel::Warn,
+        check: check_unused_imports,
+    },
+    LintRule {
+        name: "unreachable-code",
+        description: "Statements after `return`, `break` or `continue`",
+        level: LintLevel::Warn,
+        check: check_unreachable_code,
+    },
+    LintRule {
+        name: "shadowed-variable",
+        description: "Declarations that shadow a visible declaration with the same name",
+        level: LintLevel::Warn,
+        check: check_shadowed_variables,
+    },
+    LintRule {
+        name: "constant-condition",
+        description: "`if` conditions that are always true or always false",
+        level: LintLevel::Warn,
+        check: check_constant_conditions,
+    },
+    LintRule {
+        name: "empty-block",
+        description: "Empty blocks without comments (function bodies are allowed)",
+        level: LintLevel::Warn,
+        check: check_empty_blocks,
+    },
+    LintRule {
+        name: "unused-label",
+        description: "Loop labels that are never used by `break` or `continue`",
+        level: LintLevel::Warn,
+        check: check_unused_labels,
+    },
+    LintRule {
+        name: "redundant-parens",
+        description: "Parentheses around `while` conditions, initial values, return values and assigned values",
+        level: LintLevel::Warn,
+        check: check_redundant_parens,
+    },
+];
+
+// 规则配置，如: `{ "rules": { "unused-variable": "allow", "empty-block": "deny" } }`
+#[derive(Debug, Default, Deserialize)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: HashMap<String, LintLevel>,
+}
+
+impl LintConfig {
+    // 规则的级别，未配置时使用默认级别
+    pub fn level(&self, rule: &LintRule) -> LintLevel {
+        *self.rules.get(rule.name).unwrap_or(&rule.level)
+    }
+
+    // 检查配置中的规则名称是否都存在
+    pub fn validate(&self) -> Result<(), String> {
+        for name in self.rules.keys() {
+            if !RULES.iter().any(|rule| rule.name == name) {
+                return Err(format!("Unknown lint rule: `{}`", name));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 检查源码，返回按位置排序的问题列表
+pub fn lint(
+    source: &str,
+    config: &LintConfig,
+) -> Result<Vec<LintDiagnostic>, ParseError> {
+    let program = Parser::try_parse(source)?;
+    Ok(lint_program(source, &program, config))
+}
+
+// 检查已解析的程序
+pub fn lint_program(
+    source: &str,
+    program: &Node,
+    config: &LintConfig,
+) -> Vec<LintDiagnostic> {
+    let symbols = SymbolTable::new(program);
+    let mut context = LintContext {
+        source: source.chars().collect(),
+        program,
+        symbols: &symbols,
+        rule: &RULES[0],
+        level: LintLevel::Allow,
+        diagnostics: vec![],
+    };
+    for rule in RULES.iter() {
+        let level = config.level(rule);
+        if level == LintLevel::Allow {
+            continue;
+        }
+        context.rule = rule;
+        context.level = level;
+        (rule.check)(&mut context);
+    }
+
+    let mut diagnostics = context.diagnostics;
+    diagnostics.sort_by_key(|diagnostic| diagnostic.position);
+    diagnostics
+}
+
+// 选出互不重叠的修复（按位置顺序，与之前选出的修复重叠的会被跳过）
+pub fn non_overlapping_fixes(diagnostics: &[LintDiagnostic]) -> Vec<&Fix> {
+    let mut fixes: Vec<&Fix> = vec![];
+    for fix in diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.fix.as_ref())
+    {
+        let overlapped = fixes.iter().flat_map(|fix| fix.edits.iter()).any(|a| {
+            fix.edits.iter().any(|b| {
+                a.position.0 == b.position.0
+                    || (a.position.0 < b.position.1 && b.position.0 < a.position.1)
+            })
+        });
+        if !overlapped {
+            fixes.push(fix);
+        }
+    }
+    fixes
+}
+
+// 自动修复所有可修复的问题，返回修复后的源码及修复的数量，
+// 一次应用互不重叠的修复，重复检查直到没有可修复的问题
+pub fn fix(source: &str, config: &LintConfig) -> Result<(String, usize), ParseError> {
+    let mut source = source.to_string();
+    let mut count = 0;
+    // 避免修复后仍产生相同问题时无限循环
+    for _ in 0..10 {
+        let diagnostics = lint(&source, config)?;
+        let fixes = non_overlapping_fixes(&diagnostics);
+        if fixes.is_empty() {
+            break;
+        }
+        count += fixes.len();
+        let edits = fixes
+            .into_iter()
+            .flat_map(|fix| fix.edits.iter().cloned())
+            .collect::<Vec<TextEdit>>();
+        source = apply_edits(&source, &edits);
+    }
+    Ok((source, count))
+}
+
+// 规则检查时的上下文
+struct LintContext<'a> {
+    source: Vec<char>,
+    program: &'a Node,
+    symbols: &'a SymbolTable,
+    rule: &'static LintRule,
+    level: LintLevel,
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl<'a> LintContext<'a> {
+    fn report(&mut self, position: (usize, usize), message: String) {
+        self.report_fix(position, message, None);
+    }
+
+    fn report_fix(
+        &mut self,
+        position: (usize, usize),
+        message: String,
+        fix: Option<Fix>,
+    ) {
+        self.diagnostics.push(LintDiagnostic {
+            rule: self.rule.name,
+            level: self.level,
+            message,
+            position,
+            fix,
+        });
+    }
+
+    // 跳过空格及制表符
+    fn skip_spaces(&self, mut index: usize) -> usize {
+        while index < self.source.len() && matches!(self.source[index], ' ' | '\t') {
+            index += 1;
+        }
+        index
+    }
+
+    // 语句的结束位置，包含结尾的分号
+    fn statement_end(&self, mut end: usize) -> usize {
+        // 表达式的位置不包含外层的括号，如: `return (a);`
+        while self.source.get(self.skip_spaces(end)) == Some(&')') {
+            end = self.skip_spaces(end) + 1;
+        }
+        let index = self.skip_spaces(end);
+        match self.source.get(index) {
+            Some(';') => index + 1,
+            _ => end,
+        }
+    }
+
+    // 在未使用的声明名称前加上 `_`，同一作用域中已有同名声明时不修复
+    fn prefix_underscore(&self, index: usize) -> Option<Fix> {
+        let symbol = &self.symbols.symbols[index];
+        let name = format!("_{}", symbol.name);
+        if self
+            .symbols
+            .symbols
+            .iter()
+            .any(|item| item.scope == symbol.scope && item.name == name)
+        {
+            return None;
+        }
+        Some(Fix {
+            title: format!("Rename to `{}`", name),
+            edits: vec![TextEdit::insert(symbol.position.0, "_")],
+        })
+    }
+
+    // 删除一条语句，语句独占一行时删除整行
+    fn delete_statement(&self, (start, end): (usize, usize)) -> TextEdit {
+        let end = self.statement_end(end);
+        let mut line_start = start;
+        while line_start > 0 && matches!(self.source[line_start - 1], ' ' | '\t') {
+            line_start -= 1;
+        }
+        let line_end = self.skip_spaces(end);
+        let is_line_start = line_start == 0 || self.source[line_start - 1] == '\n';
+        match self.source.get(line_end) {
+            Some('\n') if is_line_start => {
+                TextEdit::replace((line_start, line_end + 1), "")
+            }
+            None if is_line_start => TextEdit::replace((line_start, line_end), ""),
+            _ => TextEdit::replace((start, end), ""),
+        }
+    }
+
+    // 未被引用的符号下标，忽略以 `_` 开头的名称
+    fn unused_symbols(&self, kind: SymbolKind) -> Vec<usize> {
+        let table = self.symbols;
+        (0..table.symbols.len())
+            .filter(|index| {
+                let symbol = &table.symbols[*index];
+                symbol.kind == kind
+                    && !symbol.name.starts_with('_')
+                    && !table.references.iter().any(|reference| {
+                        // 函数内部对自身的递归调用不算
+                        reference.symbol == *index
+                            && !(kind == SymbolKind::Function
+                                && symbol.declaration.0 <= reference.position.0
+                                && reference.position.1 <= symbol.declaration.1)
+                    })
+            })
+            .collect()
+    }
+}
+
+fn check_unused_variables(context: &mut LintContext) {
+    // 没有副作用（不包含调用及赋值）的变量声明可以直接删除
+    let mut removable = vec![];
+    Visitor::walk(context.program, &mut |node, _| {
+        if let Node::VariableDeclaration { init, position, .. } = node {
+            if !has_side_effects(init) {
+                removable.push(*position);
+            }
+        }
+    });
+
+    for index in context.unused_symbols(SymbolKind::Variable) {
+        let symbol = &context.symbols.symbols[index];
+        if is_exported(context.program, symbol) {
+            continue;
+        }
+        let message = format!("Unused variable `{}`", symbol.name);
+        let fix = if removable.contains(&symbol.declaration) {
+            Some(Fix {
+                title: format!("Remove unused variable `{}`", symbol.name),
+                edits: vec![context.delete_statement(symbol.declaration)],
+            })
+        } else if symbol.declaration == symbol.position {
+            // for 循环的变量
+            context.prefix_underscore(index)
+        } else {
+            None
+        };
+        context.report_fix(symbol.position, message, fix);
+    }
+}
+
+fn check_unused_arguments(context: &mut LintContext) {
+    for index in context.unused_symbols(SymbolKind::Argument) {
+        let symbol = &context.symbols.symbols[index];
+        let message = format!("Unused argument `{}`", symbol.name);
+        let fix = context.prefix_underscore(index);
+        context.report_fix(symbol.position, message, fix);
+    }
+}
+
+fn check_unused_functions(context: &mut LintContext) {
+    for index in context.unused_symbols(SymbolKind::Function) {
+        let symbol = &context.symbols.symbols[index];
+        if symbol.name == "main" || is_exported(context.program, symbol) {
+            continue;
+        }
+        let message = format!("Function `{}` is never called", symbol.name);
+        context.report(symbol.position, message);
+    }
+}
+
+// 未使用的导入，全部未使用时删除整个导入语句
+fn check_unused_imports(context: &mut LintContext) {
+    let unused = context.unused_symbols(SymbolKind::Import);
+    let body = match context.program {
+        Node::Program { body, .. } => body,
+        _ => return,
+    };
+    for stat in body.iter() {
+        let (specifiers, position) = match stat.deref() {
+            Node::ImportDeclaration {
+                specifiers: Some(specifiers),
+                position,
+                ..
+            } => (specifiers, *position),
+            _ => continue,
+        };
+        let positions = specifiers
+            .iter()
+            .map(|specifier| specifier.read_position())
+            .collect::<Vec<(usize, usize)>>();
+        let symbols = positions
+            .iter()
+            .map(|position| {
+                unused.iter().find(|index| {
+                    context.symbols.symbols[**index].declaration == *position
+                })
+            })
+            .collect::<Vec<Option<&usize>>>();
+        let is_all_unused = symbols.iter().all(|symbol| symbol.is_some());
+
+        for (i, symbol) in symbols.iter().enumerate() {
+            let symbol = match symbol {
+                Some(index) => &context.symbols.symbols[**index],
+                None => continue,
+            };
+            // 连同相邻的逗号一起删除
+            let edit = if is_all_unused {
+                context.delete_statement(position)
+            } else if i + 1 < positions.len() {
+                TextEdit::replace((positions[i].0, positions[i + 1].0), "")
+            } else {
+                TextEdit::replace((positions[i - 1].1, positions[i].1), "")
+            };
+            let fix = Fix {
+                title: format!("Remove unused import `{}`", symbol.name),
+                edits: vec![edit],
+            };
+            let message = format!("Unused import `{}`", symbol.name);
+            context.report_fix(symbol.position, message, Some(fix));
+        }
+    }
+}
+
+fn check_unused_labels(context: &mut LintContext) {
+    for index in context.unused_symbols(SymbolKind::Label) {
+        let symbol = &context.symbols.symbols[index];
+        let message = format!("Unused label `{}`", symbol.name);
+        // 删除标签及其后的 `:`，如: `outer: loop {}` -> `loop {}`
+        let colon = context.skip_spaces(symbol.position.1);
+        let fix = Fix {
+            title: format!("Remove label `{}`", symbol.name),
+            edits: vec![TextEdit::replace(
+                (symbol.position.0, context.skip_spaces(colon + 1)),
+                "",
+            )],
+        };
+        context.report_fix(symbol.position, message, Some(fix));
+    }
+}
+
+// 块中位于 `return`、`break`、`continue` 之后的语句
+fn check_unreachable_code(context: &mut LintContext) {
+    let mut reports = vec![];
+    Visitor::walk(context.program, &mut |node, _| {
+        if let Node::BlockStatement { body, .. } = node {
+            let terminator = body.iter().position(|stat| {
+                matches!(
+                    stat.deref(),
+                    Node::ReturnStatement { .. }
+                        | Node::BreakStatement { .. }
+                        | Node::ContinueStatement { .. }
+                )
+            });
+            if let Some(index) = terminator {
+                if index + 1 < body.len() {
+                    let keyword = match body[index].deref() {
+                        Node::ReturnStatement { .. } => "return",
+                        Node::BreakStatement { .. } => "break",
+                        _ => "continue",
+                    };
+                    let start = body[index + 1].read_position().0;
+                    let end = body.last().unwrap().read_position().1;
+                    let terminator_end = body[index].read_position().1;
+                    reports.push(((start, end), terminator_end, keyword));
+                }
+            }
+        }
+    });
+    for (position, terminator_end, keyword) in reports {
+        // 删除终止语句之后的所有语句（包括之间的空白）
+        let fix = Fix {
+            title: "Remove unreachable code".to_string(),
+            edits: vec![TextEdit::replace(
+                (
+                    context.statement_end(terminator_end),
+                    context.statement_end(position.1),
+                ),
+                "",
+            )],
+        };
+        let message = format!("Unreachable code after `{}`", keyword);
+        context.report_fix(position, message, Some(fix));
+    }
+}
+
+// 与声明处可见的同名符号（外层作用域或同一作用域中之前的声明）重名
+fn check_shadowed_variables(context: &mut LintContext) {
+    let table = context.symbols;
+    for symbol in table.symbols.iter() {
+        if !matches!(symbol.kind, SymbolKind::Variable | SymbolKind::Argument)
+            || symbol.name.starts_with('_')
+        {
+            continue;
+        }
+        let shadowed = table
+            .visible_at(symbol.position.0)
+            .into_iter()
+            .find(|item| {
+                item.name == symbol.name
+                    && item.kind != SymbolKind::Label
+                    && item.position != symbol.position
+            });
+        if let Some(shadowed) = shadowed {
+            let kind = match shadowed.kind {
+                SymbolKind::Function => "a function",
+                SymbolKind::Argument => "an argument",
+                SymbolKind::Import => "an import",
+                _ => "a variable",
+            };
+            let message =
+                format!("`{}` shadows {} with the same name", symbol.name, kind);
+            context.report(symbol.position, message);
+        }
+    }
+}
+
+fn check_constant_conditions(context: &mut LintContext) {
+    let mut reports = vec![];
+    Visitor::walk(context.program, &mut |node, _| {
+        if let Node::IfStatement { condition, .. } = node {
+            if let Some(value) = eval_const_bool(condition) {
+                reports.push((condition.read_position(), value));
+            }
+        }
+    });
+    for (position, value) in reports {
+        context.report(position, format!("Condition is always `{}`", value));
+    }
+}
+
+// 块语句（不包括函数体）中没有任何语句及注释
+fn check_empty_blocks(context: &mut LintContext) {
+    let mut function_bodies = vec![];
+    let mut blocks = vec![];
+    Visitor::walk(context.program, &mut |node, _| match node {
+        Node::FunctionDeclaration { body, .. } => {
+            function_bodies.push(body.read_position());
+        }
+        Node::BlockStatement { body, position } if body.is_empty() => {
+            blocks.push(*position);
+        }
+        _ => {}
+    });
+    for position in blocks {
+        // 块语句的位置不包含结尾的 `}`
+        let inner = &context.source[position.0 + 1..position.1];
+        if function_bodies.contains(&position)
+            || inner.iter().any(|ch| !ch.is_whitespace())
+        {
+            continue;
+        }
+        context.report((position.0, position.1 + 1), "Empty block".to_string());
+    }
+}
+
+// `while` 条件、初始值、返回值及赋值右侧多余的括号，如: `while (a > b) {}`、`return (a);`
+// （`if` 条件的括号与格式化的风格一致，不检查）
+fn check_redundant_parens(context: &mut LintContext) {
+    let mut expressions = vec![];
+    Visitor::walk(context.program, &mut |node, _| match node {
+        Node::WhileStatement { condition, .. } => {
+            expressions.push(condition.read_position())
+        }
+        Node::VariableDeclaration { init, .. } => expressions.push(init.read_position()),
+        Node::ReturnStatement {
+            argument: Some(argument),
+            ..
+        } => expressions.push(argument.read_position()),
+        Node::AssignmentExpression { right, .. } => {
+            expressions.push(right.read_position());
+        }
+        _ => {}
+    });
+    if expressions.is_empty() {
+        return;
+    }
+
+    // 括号的配对关系：`(` 的位置 -> `)` 的位置
+    let source = context.source.iter().collect::<String>();
+    let tokens = Lexer::new(&source, false).collect::<Vec<_>>();
+    let mut parens = HashMap::new();
+    let mut stack = vec![];
+    for token in tokens.iter() {
+        match token.token_type {
+            TokenType::ParenL => stack.push(token.start),
+            TokenType::ParenR => {
+                if let Some(start) = stack.pop() {
+                    parens.insert(start, token.start);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (start, end) in expressions {
+        let index = tokens.partition_point(|token| token.start < start);
+        let (prev, next) = match (index.checked_sub(1), tokens.get(index)) {
+            (Some(prev), Some(_)) => (
+                &tokens[prev],
+                tokens.get(tokens.partition_point(|token| token.start < end)),
+            ),
+            _ => continue,
+        };
+        let next = match next {
+            Some(next) if next.token_type == TokenType::ParenR => next,
+            _ => continue,
+        };
+        if prev.token_type != TokenType::ParenL
+            || parens.get(&prev.start) != Some(&next.start)
+        {
+            continue;
+        }
+
+        // 删除括号后与相邻的标识符之间需保留空格，如: `return(a)` -> `return a`
+        let is_word = |ch: Option<&char>| ch.is_some_and(|ch| is_identifier_char(*ch));
+        let open = if prev.start > 0 && is_word(context.source.get(prev.start - 1)) {
+            " "
+        } else {
+            ""
+        };
+        let close = if is_word(context.source.get(next.end)) {
+            " "
+        } else {
+            ""
+        };
+        let fix = Fix {
+            title: "Remove redundant parentheses".to_string(),
+            edits: vec![
+                TextEdit::replace((prev.start, prev.end), open),
+                TextEdit::replace((next.start, next.end), close),
+            ],
+        };
+        let message = "Redundant parentheses".to_string();
+        context.report_fix((prev.start, next.end), message, Some(fix));
+    }
+}
+
+// 是否包含调用或赋值
+fn has_side_effects(node: &Node) -> bool {
+    let mut result = false;
+    Visitor::walk(node, &mut |node, visitor| {
+        if let Node::CallExpression { .. } | Node::AssignmentExpression { .. } = node {
+            result = true;
+            visitor.stop();
+        }
+    });
+    result
+}
@@ -0,0 +1,57 @@
+use crate::node::Node;
+use crate::symbols::Symbol;
+use std::ops::Deref;
+
+// 参与分析的模块（文件），`path` 为模块路径，如: `src/main.x`
+#[derive(Debug, Clone, Copy)]
+pub struct Module<'a> {
+    pub path: &'a str,
+    pub source: &'a str,
+}
+
+// 根据导入路径查找模块，路径相对于导入方所在的目录，如: `src/a.x` 中的 `../mod/b` -> `mod/b.x`
+pub fn resolve_import(
+    modules: &[Module],
+    importer: usize,
+    source: &str,
+) -> Option<usize> {
+    let importer = modules[importer].path;
+    let dir = match importer.rfind('/') {
+        Some(index) => &importer[..index],
+        None => "",
+    };
+    let path = normalize_path(&format!("{}/{}.x", dir, source));
+    modules
+        .iter()
+        .position(|module| normalize_path(module.path) == path)
+}
+
+// 是否为被 `pub` 修饰的顶层函数或变量（可以被其他模块导入）
+pub fn is_exported(program: &Node, symbol: &Symbol) -> bool {
+    let body = match program {
+        Node::Program { body, .. } => body,
+        _ => return false,
+    };
+    body.iter().any(|stat| match stat.deref() {
+        Node::FunctionDeclaration { id, is_pub, .. }
+        | Node::VariableDeclaration { id, is_pub, .. } => {
+            *is_pub && id.read_position() == symbol.position
+        }
+        _ => false,
+    })
+}
+
+// 去掉路径中的 `.` 及 `..`
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = vec![];
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." if !parts.is_empty() && parts.last() != Some(&"..") => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
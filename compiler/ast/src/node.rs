@@ -34,14 +34,16 @@ pub enum Node {
         body: Box<Node>,
         return_kind: Kind,
         is_pub: bool,
+        doc: Option<String>, // 文档注释，如: `/// Returns the sum`
         position: (usize, usize),
-        // comments: Vec<Comment>
     },
     VariableDeclaration {
         id: Box<Node>,
         init: Box<Node>,
+        keyword: String, // `var` | `let` | `const`
+        is_pub: bool,
+        doc: Option<String>,
         position: (usize, usize),
-        // comments: Vec<Comment>
     },
     BlockStatement {
         body: Vec<Box<Node>>,
@@ -71,6 +73,21 @@ pub enum Node {
         position: (usize, usize),
         // comments: Vec<Comment>
     },
+    WhileStatement {
+        label: Option<String>,
+        condition: Box<Node>,
+        body: Box<Node>,
+        position: (usize, usize),
+        // comments: Vec<Comment>
+    },
+    ForStatement {
+        label: Option<String>,
+        id: Box<Node>,
+        iterable: Box<Node>,
+        body: Box<Node>,
+        position: (usize, usize),
+        // comments: Vec<Comment>
+    },
     BreakStatement {
         label: Option<String>,
         position: (usize, usize),
@@ -88,11 +105,26 @@ pub enum Node {
         local: Option<String>,
         position: (usize, usize),
     },
+    CastExpression {
+        expression: Box<Node>,
+        kind: Kind,
+        position: (usize, usize),
+    },
+    RangeExpression {
+        start: Box<Node>,
+        end: Box<Node>,
+        position: (usize, usize),
+    },
     CallExpression {
         callee: Box<Node>,
         arguments: Vec<Box<Node>>,
         position: (usize, usize),
     },
+    MemberExpression {
+        object: Box<Node>,
+        property: Box<Node>,
+        position: (usize, usize),
+    },
     BinaryExpression {
         left: Box<Node>,
         right: Box<Node>,
@@ -117,6 +149,7 @@ pub enum Node {
     },
     NumberLiteral {
         value: f64,
+        raw: String, // 源码中的原始文本，如: `0xff`、`1_000`
         position: (usize, usize),
     },
     BooleanLiteral {
@@ -124,10 +157,18 @@ pub enum Node {
         position: (usize, usize),
     },
     StringLiteral {
-        value: String,
+        value: String, // 转义后的值
+        raw: String,   // 源码中的原始文本（不含引号），用于格式化时保留原始写法
         is_raw: bool,
         position: (usize, usize),
     },
+    // 模板字符串，如: `"a + b = ${a + b}"`，quasis 比 expressions 多一项
+    TemplateLiteral {
+        quasis: Vec<String>,
+        raw_quasis: Vec<String>,
+        expressions: Vec<Box<Node>>,
+        position: (usize, usize),
+    },
 }
 
 impl Node {
@@ -179,10 +220,15 @@ impl Node {
             Node::ExpressionStatement { position, .. } => *position,
             Node::IfStatement { position, .. } => *position,
             Node::LoopStatement { position, .. } => *position,
+            Node::WhileStatement { position, .. } => *position,
+            Node::ForStatement { position, .. } => *position,
             Node::BreakStatement { position, .. } => *position,
             Node::ContinueStatement { position, .. } => *position,
             Node::ImportSpecifier { position, .. } => *position,
+            Node::CastExpression { position, .. } => *position,
+            Node::RangeExpression { position, .. } => *position,
             Node::CallExpression { position, .. } => *position,
+            Node::MemberExpression { position, .. } => *position,
             Node::BinaryExpression { position, .. } => *position,
             Node::UnaryExpression { position, .. } => *position,
             Node::AssignmentExpression { position, .. } => *position,
@@ -190,6 +236,7 @@ impl Node {
             Node::NumberLiteral { position, .. } => *position,
             Node::BooleanLiteral { position, .. } => *position,
             Node::StringLiteral { position, .. } => *position,
+            Node::TemplateLiteral { position, .. } => *position,
         }
     }
 }
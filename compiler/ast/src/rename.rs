@@ -0,0 +1,437 @@
+use crate::module::{is_exported, resolve_import, Module};
+use crate::node::Node;
+use crate::shared::{is_identifier_char, is_identifier_start, is_keyword_str};
+use crate::state::{ParseError, Parser};
+use crate::symbols::{SymbolKind, SymbolTable};
+use crate::text_edit::{apply_edits, map_index, TextEdit};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+
+// 无法重命名的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    Parse {
+        module: usize,
+        error: ParseError,
+    },
+    NotFound,
+    InvalidName(String),
+    Keyword(String),
+    // 新名称与已有的声明冲突，或会改变已有引用的指向，`position` 为冲突处在原源码中的位置
+    Conflict {
+        name: String,
+        module: usize,
+        position: (usize, usize),
+    },
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenameError::Parse { error, .. } => write!(f, "{}", error.message),
+            RenameError::NotFound => write!(f, "No symbol to rename at this position"),
+            RenameError::InvalidName(name) => {
+                write!(f, "`{}` is not a valid identifier", name)
+            }
+            RenameError::Keyword(name) => {
+                write!(f, "`{}` is a keyword and cannot be used as a name", name)
+            }
+            RenameError::Conflict { name, .. } => {
+                write!(
+                    f,
+                    "The name `{}` conflicts with an existing declaration",
+                    name
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenameError {}
+
+// 重命名单个文件中某个位置（字符索引）上的变量、参数、函数、循环标签或导入名称，
+// 返回需要对源码进行的修改
+pub fn rename(
+    source: &str,
+    offset: usize,
+    new_name: &str,
+) -> Result<Vec<TextEdit>, RenameError> {
+    let modules = [Module { path: "", source }];
+    let edits = rename_in_modules(&modules, 0, offset, new_name)?;
+    Ok(edits.into_iter().map(|(_, edit)| edit).collect())
+}
+
+// 在多个模块中重命名，被 `pub` 修饰的顶层声明会同时修改导入它的模块，
+// 返回 (模块下标, 修改) 列表，按模块及位置排序
+pub fn rename_in_modules(
+    modules: &[Module],
+    module: usize,
+    offset: usize,
+    new_name: &str,
+) -> Result<Vec<(usize, TextEdit)>, RenameError> {
+    validate_name(new_name)?;
+    // 只要求重命名所在的模块可以解析，其他无法解析的模块（如: 正在编辑中）会被跳过
+    let mut analyses = vec![];
+    for (index, source) in modules.iter().map(|module| module.source).enumerate() {
+        match Parser::try_parse(source) {
+            Ok(program) => {
+                let table = SymbolTable::new(&program);
+                analyses.push(Some(ModuleAnalysis { program, table }));
+            }
+            Err(error) if index == module => {
+                return Err(RenameError::Parse { module, error })
+            }
+            Err(_) => analyses.push(None),
+        }
+    }
+
+    let renamer = Renamer {
+        modules,
+        analyses: &analyses,
+        new_name,
+    };
+    let symbol = renamer
+        .table(module)
+        .symbol_at(offset)
+        .ok_or(RenameError::NotFound)?;
+    if renamer.table(module).symbols[symbol].name == new_name {
+        return Ok(vec![]);
+    }
+
+    // 未重命名的导入名称即为被导入的声明本身
+    let (module, symbol) = renamer
+        .imported_declaration(module, symbol)
+        .unwrap_or((module, symbol));
+    let mut edits: Vec<Vec<TextEdit>> = vec![vec![]; modules.len()];
+    match renamer.table(module).symbols[symbol].kind {
+        SymbolKind::Import => renamer.rename_import(module, symbol, &mut edits)?,
+        _ => {
+            renamer.rename_symbol(module, symbol, &mut edits)?;
+            if renamer.is_exported(module, symbol) {
+                renamer.rename_importers(module, symbol, &mut edits)?;
+            }
+        }
+    }
+
+    let mut result = vec![];
+    for (index, mut module_edits) in edits.into_iter().enumerate() {
+        if module_edits.is_empty() {
+            continue;
+        }
+        module_edits.sort_by_key(|edit| edit.position);
+        renamer.verify(index, &module_edits)?;
+        result.extend(module_edits.into_iter().map(|edit| (index, edit)));
+    }
+    Ok(result)
+}
+
+// 新名称需为合法的标识符，且不能是关键字
+fn validate_name(name: &str) -> Result<(), RenameError> {
+    let mut chars = name.chars();
+    let is_valid = match chars.next() {
+        Some(ch) => is_identifier_start(ch) && chars.all(is_identifier_char),
+        None => false,
+    };
+    if !is_valid {
+        return Err(RenameError::InvalidName(name.to_string()));
+    }
+    if is_keyword_str(name) {
+        return Err(RenameError::Keyword(name.to_string()));
+    }
+    Ok(())
+}
+
+struct ModuleAnalysis {
+    program: Node,
+    table: SymbolTable,
+}
+
+struct Renamer<'a> {
+    modules: &'a [Module<'a>],
+    analyses: &'a [Option<ModuleAnalysis>], // 无法解析的模块为 None
+    new_name: &'a str,
+}
+
+impl<'a> Renamer<'a> {
+    // 已解析的模块
+    fn analysis(&self, module: usize) -> &'a ModuleAnalysis {
+        self.analyses[module].as_ref().unwrap()
+    }
+
+    fn table(&self, module: usize) -> &'a SymbolTable {
+        &self.analysis(module).table
+    }
+
+    // 重命名符号的声明及所有引用
+    fn rename_symbol(
+        &self,
+        module: usize,
+        symbol: usize,
+        edits: &mut [Vec<TextEdit>],
+    ) -> Result<(), RenameError> {
+        self.check_declared(module, symbol)?;
+        let table = self.table(module);
+        for position in table.references_of(symbol, true) {
+            edits[module].push(TextEdit::replace(position, self.new_name));
+        }
+        Ok(())
+    }
+
+    // 只重命名导入到本模块的名称，如: `import foo.{say}` -> `import foo.{say as speak}`
+    fn rename_import(
+        &self,
+        module: usize,
+        symbol: usize,
+        edits: &mut [Vec<TextEdit>],
+    ) -> Result<(), RenameError> {
+        self.check_declared(module, symbol)?;
+        let table = self.table(module);
+        let (position, imported, local) =
+            match self.find_specifier(module, table.symbols[symbol].declaration) {
+                Some(specifier) => specifier,
+                None => return Err(RenameError::NotFound),
+            };
+        let imported_end = position.0 + imported.chars().count();
+        let edit = match local {
+            // 新名称与导入的名称相同时去掉别名
+            Some(_) if imported == self.new_name => {
+                TextEdit::replace((imported_end, position.1), "")
+            }
+            Some(_) => TextEdit::replace(table.symbols[symbol].position, self.new_name),
+            None => TextEdit::insert(position.1, &format!(" as {}", self.new_name)),
+        };
+        edits[module].push(edit);
+        for position in table.references_of(symbol, false) {
+            edits[module].push(TextEdit::replace(position, self.new_name));
+        }
+        Ok(())
+    }
+
+    // 修改其他模块中对该声明的导入，未重命名的导入名称的引用一并修改
+    fn rename_importers(
+        &self,
+        module: usize,
+        symbol: usize,
+        edits: &mut [Vec<TextEdit>],
+    ) -> Result<(), RenameError> {
+        let name = &self.table(module).symbols[symbol].name;
+        for (index, analysis) in self.analyses.iter().enumerate() {
+            let analysis = match analysis {
+                Some(analysis) if index != module => analysis,
+                _ => continue,
+            };
+            for (source, specifiers) in self.imports(index) {
+                if self.resolve_import(index, source) != Some(module) {
+                    continue;
+                }
+                let specifiers = match specifiers {
+                    Some(specifiers) => specifiers,
+                    // `import mod` 与 `import mod.{*}` 相同
+                    None => {
+                        self.rename_glob_references(index, name, edits);
+                        continue;
+                    }
+                };
+                for specifier in specifiers.iter() {
+                    if let Node::ImportSpecifier {
+                        imported,
+                        local,
+                        position,
+                    } = specifier.deref()
+                    {
+                        if imported == "*" {
+                            self.rename_glob_references(index, name, edits);
+                            continue;
+                        }
+                        if imported != name {
+                            continue;
+                        }
+                        let imported_end = position.0 + imported.chars().count();
+                        match local {
+                            Some(local) if local == self.new_name => {
+                                edits[index].push(TextEdit::replace(
+                                    (position.0, position.1),
+                                    self.new_name,
+                                ));
+                            }
+                            Some(_) => edits[index].push(TextEdit::replace(
+                                (position.0, imported_end),
+                                self.new_name,
+                            )),
+                            None => {
+                                let local_symbol = analysis
+                                    .table
+                                    .symbol_at(position.0)
+                                    .ok_or(RenameError::NotFound)?;
+                                self.rename_symbol(index, local_symbol, edits)?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 通配导入的名称没有声明，修改同名的未解析引用
+    fn rename_glob_references(
+        &self,
+        module: usize,
+        name: &str,
+        edits: &mut [Vec<TextEdit>],
+    ) {
+        for (reference, position) in self.table(module).unresolved.iter() {
+            let edit = TextEdit::replace(*position, self.new_name);
+            if reference == name && !edits[module].contains(&edit) {
+                edits[module].push(edit);
+            }
+        }
+    }
+
+    // 同一作用域内不能存在同名的声明
+    fn check_declared(&self, module: usize, symbol: usize) -> Result<(), RenameError> {
+        let table = self.table(module);
+        let is_label = table.symbols[symbol].kind == SymbolKind::Label;
+        let scope = &table.scopes[table.symbols[symbol].scope];
+        for index in scope.symbols.iter() {
+            let other = &table.symbols[*index];
+            if *index != symbol
+                && other.name == self.new_name
+                && (other.kind == SymbolKind::Label) == is_label
+            {
+                return Err(RenameError::Conflict {
+                    name: self.new_name.to_string(),
+                    module,
+                    position: other.position,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // 应用修改后重新解析，所有引用需仍然指向原来的声明（或仍未解析），
+    // 否则说明新名称遮蔽了其他声明或被其他声明遮蔽
+    fn verify(&self, module: usize, edits: &[TextEdit]) -> Result<(), RenameError> {
+        let source = apply_edits(self.modules[module].source, edits);
+        let program = Parser::try_parse(&source)
+            .map_err(|error| RenameError::Parse { module, error })?;
+        let new_table = SymbolTable::new(&program);
+        let table = self.table(module);
+
+        let new_bindings = bindings(&new_table)
+            .into_iter()
+            .map(|(position, declaration)| (position.0, declaration))
+            .collect::<HashMap<usize, Option<usize>>>();
+        for (position, declaration) in bindings(table) {
+            let start = map_index(edits, position.0);
+            let declaration = declaration.map(|start| map_index(edits, start));
+            if new_bindings.get(&start) != Some(&declaration) {
+                return Err(RenameError::Conflict {
+                    name: self.new_name.to_string(),
+                    module,
+                    position,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // 导入名称对应的其他模块中的声明，有别名或找不到被导入的模块时返回 None
+    fn imported_declaration(
+        &self,
+        module: usize,
+        symbol: usize,
+    ) -> Option<(usize, usize)> {
+        let symbol = &self.table(module).symbols[symbol];
+        if symbol.kind != SymbolKind::Import {
+            return None;
+        }
+        let (_, imported, local) = self.find_specifier(module, symbol.declaration)?;
+        if local.is_some() {
+            return None;
+        }
+        let (source, _) = self.imports(module).into_iter().find(|(_, specifiers)| {
+            specifiers.is_some_and(|specifiers| {
+                specifiers
+                    .iter()
+                    .any(|specifier| specifier.read_position() == symbol.declaration)
+            })
+        })?;
+        // 被导入的模块无法解析时只重命名本模块中的导入名称
+        let target = self.resolve_import(module, source)?;
+        let table = &self.analyses[target].as_ref()?.table;
+        let index = table
+            .scopes
+            .first()?
+            .symbols
+            .iter()
+            .copied()
+            .find(|index| {
+                table.symbols[*index].name == imported && self.is_exported(target, *index)
+            })?;
+        Some((target, index))
+    }
+
+    fn is_exported(&self, module: usize, symbol: usize) -> bool {
+        let analysis = self.analysis(module);
+        is_exported(&analysis.program, &analysis.table.symbols[symbol])
+    }
+
+    // 模块中的所有非标准库导入语句: (导入路径, 导入的名称)
+    fn imports(&self, module: usize) -> Vec<(&'a str, Option<&'a [Box<Node>]>)> {
+        let body = match &self.analysis(module).program {
+            Node::Program { body, .. } => body,
+            _ => return vec![],
+        };
+        body.iter()
+            .filter_map(|stat| match stat.deref() {
+                Node::ImportDeclaration {
+                    source,
+                    is_std_source: false,
+                    specifiers,
+                    ..
+                } => Some((source.as_str(), specifiers.as_deref())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // 某个位置的导入名称: (位置, 导入的名称, 别名)
+    fn find_specifier(
+        &self,
+        module: usize,
+        position: (usize, usize),
+    ) -> Option<((usize, usize), &'a str, Option<&'a str>)> {
+        self.imports(module)
+            .into_iter()
+            .flat_map(|(_, specifiers)| specifiers.into_iter().flatten())
+            .find_map(|specifier| match specifier.deref() {
+                Node::ImportSpecifier {
+                    imported,
+                    local,
+                    position: specifier_position,
+                } if *specifier_position == position => {
+                    Some((position, imported.as_str(), local.as_deref()))
+                }
+                _ => None,
+            })
+    }
+
+    fn resolve_import(&self, module: usize, source: &str) -> Option<usize> {
+        resolve_import(self.modules, module, source)
+    }
+}
+
+// 所有引用的位置及其指向的声明语句的开始位置，未解析的引用没有声明
+fn bindings(table: &SymbolTable) -> Vec<((usize, usize), Option<usize>)> {
+    let mut bindings = vec![];
+    for reference in table.references.iter() {
+        let declaration = table.symbols[reference.symbol].declaration.0;
+        bindings.push((reference.position, Some(declaration)));
+    }
+    for (_, position) in table.unresolved.iter() {
+        bindings.push((*position, None));
+    }
+    bindings
+}
@@ -1,11 +1,14 @@
+use unicode_xid::UnicodeXID;
+
 // 关键字
-const KEYWORDS: [&str; 17] = [
-    "fn", "var", "return", "true", "false", "if", "else", "loop", "break", "continue",
-    "pub", "import", "as", //
-    // reserve
-    "class", "this", "extends", "super",
+pub const KEYWORDS: [&str; 18] = [
+    "fn", "var", "let", "const", "return", "true", "false", "if", "else", "loop",
+    "while", "for", "in", "break", "continue", "pub", "import", "as",
 ];
 
+// 保留字，暂未使用，但不能作为标识符
+pub const RESERVED_KEYWORDS: [&str; 4] = ["class", "this", "extends", "super"];
+
 fn array_index_of_str(arr: &[&str], value: &str) -> isize {
     for (i, v) in arr.iter().enumerate() {
         if *v == value {
@@ -18,11 +21,25 @@ fn array_index_of_str(arr: &[&str], value: &str) -> isize {
 // 判断是否为关键字
 pub fn is_keyword_str(str: &str) -> bool {
     array_index_of_str(&KEYWORDS, str) >= 0
+        || array_index_of_str(&RESERVED_KEYWORDS, str) >= 0
+}
+
+// 是否可以作为标识符的首个字符（Unicode XID_Start，以及 `_`、`$`）
+pub fn is_identifier_start(ch: char) -> bool {
+    ch == '_' || ch == '$' || ch.is_xid_start()
+}
+
+// 是否可以作为标识符的后续字符（Unicode XID_Continue，以及 `$`）
+pub fn is_identifier_char(ch: char) -> bool {
+    ch == '$' || ch.is_xid_continue()
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Copy, Clone)]
 pub enum KindName {
     Number,
+    Int32,
+    Int64,
+    UInt8,
     Boolean,
     String,
     Void,
@@ -39,6 +56,9 @@ impl KindName {
     pub fn from(kind_str: &str, allow_void: bool) -> Option<Self> {
         match kind_str.as_bytes() {
             b"num" => Some(KindName::Number),
+            b"i32" => Some(KindName::Int32),
+            b"i64" => Some(KindName::Int64),
+            b"u8" => Some(KindName::UInt8),
             b"bool" => Some(KindName::Boolean),
             b"str" => Some(KindName::String),
             b"void" => {
@@ -56,11 +76,49 @@ impl KindName {
     pub fn to_string(&self) -> String {
         match self {
             KindName::Number => "num".to_string(),
+            KindName::Int32 => "i32".to_string(),
+            KindName::Int64 => "i64".to_string(),
+            KindName::UInt8 => "u8".to_string(),
             KindName::Boolean => "bool".to_string(),
             KindName::String => "str".to_string(),
             KindName::Void => "void".to_string(),
         }
     }
+
+    // 是否是整数类型
+    pub fn is_integer(&self) -> bool {
+        matches!(self, KindName::Int32 | KindName::Int64 | KindName::UInt8)
+    }
+
+    // 是否是数字类型（包括整数类型）
+    pub fn is_numeric(&self) -> bool {
+        *self == KindName::Number || self.is_integer()
+    }
+
+    // 是否是有符号的整数类型
+    pub fn is_signed(&self) -> bool {
+        matches!(self, KindName::Int32 | KindName::Int64)
+    }
+
+    // 整数类型的位数
+    pub fn int_bits(&self) -> u32 {
+        match self {
+            KindName::Int32 => 32,
+            KindName::Int64 => 64,
+            KindName::UInt8 => 8,
+            _ => panic!("Internal Error: `{}` is not an integer", self.to_string()),
+        }
+    }
+
+    // 整数类型的取值范围
+    pub fn int_range(&self) -> (i128, i128) {
+        match self {
+            KindName::Int32 => (i32::MIN as i128, i32::MAX as i128),
+            KindName::Int64 => (i64::MIN as i128, i64::MAX as i128),
+            KindName::UInt8 => (u8::MIN as i128, u8::MAX as i128),
+            _ => panic!("Internal Error: `{}` is not an integer", self.to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Eq, Serialize, Copy, Clone)]
@@ -126,3 +184,38 @@ impl Kind {
         }
     }
 }
+
+// 内置的类型转换函数: (函数名, 返回类型)，参数类型在调用时校验
+pub const BUILD_IN_FNS: [(&str, KindName); 2] = [
+    ("to_str", KindName::String),
+    ("parse_num", KindName::Number),
+];
+
+// 内置函数的签名，用于编辑器补全
+pub const BUILD_IN_FN_SIGNATURES: [(&str, &str); 3] = [
+    ("print", "fn print(...values)"),
+    (
+        "to_str",
+        "fn to_str(value: num | i32 | i64 | u8 | bool | str) -> str",
+    ),
+    ("parse_num", "fn parse_num(value: str) -> num"),
+];
+
+// 内置类型的方法: (调用者类型, 方法名, 参数类型, 返回类型)
+pub const BUILD_IN_METHODS: [(KindName, &str, &[KindName], KindName); 5] = [
+    (KindName::String, "len", &[], KindName::Number),
+    (
+        KindName::String,
+        "at",
+        &[KindName::Number],
+        KindName::String,
+    ),
+    (
+        KindName::String,
+        "substr",
+        &[KindName::Number, KindName::Number],
+        KindName::String,
+    ),
+    (KindName::Number, "floor", &[], KindName::Number),
+    (KindName::Number, "to_str", &[], KindName::String),
+];
@@ -1,21 +1,35 @@
 use crate::code_frame::print_error_frame;
+use crate::diagnostics::Diagnostics;
 use crate::node::Node;
 use crate::shared::Kind;
-use crate::token::{Token, TokenType};
+use crate::token::{Comment, Token, TokenType};
 use crate::externs;
 
+// 解析错误，由 `Parser::try_parse` 返回
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub pos: usize, // 出错位置的字符索引
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
 #[derive(Debug)]
 pub struct Parser<'a> {
     pub(crate) input: &'a str,
-    pub(crate) chars: Vec<char>,           // 字符 vec
-    pub(crate) index: usize,               // 光标位置
-    pub(crate) is_start: bool,             // 光标是否在开始位置
-    pub(crate) is_seen_newline: bool,      // 读取下一个 token 时是否遇到过换行
-    pub(crate) current_char: char,         // 当前字符
-    pub(crate) current_token: Token,       // 当前 token
-    pub(crate) allow_expr: bool,           // 当前上下文是否允许表达式
-    pub(crate) current_block_level: usize, // 当前进入到第几层块级作用域
-    pub(crate) current_loop_level: usize,  // 当前进入到第几层循环块
+    pub(crate) chars: Vec<char>,            // 字符 vec
+    pub(crate) index: usize,                // 光标位置
+    pub(crate) is_start: bool,              // 光标是否在开始位置
+    pub(crate) is_seen_newline: bool,       // 读取下一个 token 时是否遇到过换行
+    pub(crate) current_char: char,          // 当前字符
+    pub(crate) current_token: Token,        // 当前 token
+    pub(crate) allow_expr: bool,            // 当前上下文是否允许表达式
+    pub(crate) current_block_level: usize,  // 当前进入到第几层块级作用域
+    pub(crate) current_loop_level: usize,   // 当前进入到第几层循环块
+    pub(crate) comments: Vec<Comment>,      // 已读取的所有注释
+    pub(crate) doc_comments: Vec<String>,   // 尚未附加到 token 上的文档注释
+    pub(crate) current_doc: Option<String>, // 当前 token 之前的文档注释
+    pub(crate) diagnostics: Diagnostics,    // 解析过程中产生的警告
 }
 
 impl<'a> Parser<'a> {
@@ -39,15 +53,54 @@ impl<'a> Parser<'a> {
             allow_expr: true,
             current_block_level: 0,
             current_loop_level: 0,
+            comments: vec![],
+            doc_comments: vec![],
+            current_doc: None,
+            diagnostics: Diagnostics::new(),
         }
     }
 
-    // 开始解析
+    // 解析源码，出错时返回错误信息而不打印代码帧（供编辑器等工具使用）
+    pub fn try_parse(input: &'a str) -> ParseResult<Node> {
+        Parser::new(input).parse_program()
+    }
+
+    // 取出解析过程中产生的警告
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    // 取出解析过程中读取到的所有注释（包括文档注释），按出现的顺序排列
+    pub fn take_comments(&mut self) -> Vec<Comment> {
+        std::mem::take(&mut self.comments)
+    }
+
+    // 开始解析，出错时打印错误帧信息并抛出异常
     pub fn parse(&mut self) -> Node {
+        match self.parse_program() {
+            Ok(node) => node,
+            Err(err) => {
+                let mut message = err.message;
+                let position = print_error_frame(self.input, err.pos, &message);
+
+                if let Some((line, column)) = position {
+                    message.push_str(&format!(" ({}:{})", line, column))
+                }
+
+                #[cfg(feature = "wasm")]
+                externs::__throw__(&message);
+
+                panic!("{}", message);
+            }
+        }
+    }
+
+    // 开始解析，出错时返回错误信息
+    pub fn parse_program(&mut self) -> ParseResult<Node> {
         let mut body: Vec<Box<Node>> = vec![];
-        self.next_token();
+        self.next_token()?;
         while self.check_valid_index() {
-            let stat = self.parse_statement();
+            let stat = self.parse_statement()?;
             body.push(Box::new(stat));
         }
 
@@ -56,7 +109,7 @@ impl<'a> Parser<'a> {
             position.0 = body.first().unwrap().read_position().0;
             position.1 = body.last().unwrap().read_position().1;
         }
-        Node::Program { position, body }
+        Ok(Node::Program { position, body })
     }
 
     // 检查光标是否超过最大值
@@ -96,24 +149,34 @@ impl<'a> Parser<'a> {
     }
 
     // 检查下一个有效字符是否是指定的字符
-    pub(crate) fn check_next_char(&mut self, char: char) -> bool {
+    pub(crate) fn check_next_char(&mut self, char: char) -> ParseResult<bool> {
         self.skip_space(true);
-        self.skip_comment();
-        self.current_char == char
+        self.skip_comment()?;
+        Ok(self.current_char == char)
     }
 
-    // 验证是否在函数内部，否则抛错
-    pub(crate) fn validate_inside_fn(&mut self) {
+    // 验证是否在函数内部，否则返回错误
+    pub(crate) fn validate_inside_fn(&self) -> ParseResult<()> {
         if self.current_block_level == 0 {
-            self.unexpected(Some("Cannot be used outside of functions"));
+            return self.unexpected(Some("Cannot be used outside of functions"));
         }
+        Ok(())
     }
 
-    // 检查是否在程序根层级下，否则抛错
-    pub(crate) fn validate_program_root(&mut self, title: &str) {
+    // 检查是否在程序根层级下，否则返回错误
+    pub(crate) fn validate_program_root(&self, title: &str) -> ParseResult<()> {
         if self.current_block_level > 0 {
-            self.unexpected(Some(&format!("{} can only be defined in the root", title)));
+            return self.unexpected(Some(&format!(
+                "{} can only be defined in the root",
+                title
+            )));
         }
+        Ok(())
+    }
+
+    // 读取源码中某一段的文本
+    pub(crate) fn read_source(&self, start: usize, end: usize) -> String {
+        self.chars[start..end].iter().collect()
     }
 
     // 是否是某个关键字
@@ -143,46 +206,44 @@ impl<'a> Parser<'a> {
         }
     }
 
-    // 期望当前 token 类型为指定类型，否则抛错
-    pub(crate) fn expect(&mut self, token_type: TokenType) {
+    // 期望当前 token 类型为指定类型，否则返回错误
+    pub(crate) fn expect(&self, token_type: TokenType) -> ParseResult<()> {
         if !self.is_token(token_type) {
-            self.unexpected(None);
+            return self.unexpected(None);
         }
+        Ok(())
     }
 
     // 消费一个 token 类型，如果消费成功，返回 true 并读取下一个 token，否则返回 false
-    pub(crate) fn consume(&mut self, token_type: TokenType) -> bool {
+    pub(crate) fn consume(&mut self, token_type: TokenType) -> ParseResult<bool> {
         if self.is_token(token_type) {
-            self.next_token();
-            true
+            self.next_token()?;
+            Ok(true)
         } else {
-            false
+            Ok(false)
         }
     }
 
-    // 消费一个 token 类型，如果消费成功，读取下一个 token，否则抛错
-    pub(crate) fn consume_or_panic(&mut self, token_type: TokenType) {
-        self.expect(token_type);
-        self.next_token();
+    // 消费一个 token 类型，如果消费成功，读取下一个 token，否则返回错误
+    pub(crate) fn consume_or_err(&mut self, token_type: TokenType) -> ParseResult<()> {
+        self.expect(token_type)?;
+        self.next_token()
     }
 
-    // 打印错误帧信息并抛出异常
-    pub(crate) fn unexpected_err(&mut self, pos: usize, msg: &str) -> ! {
-        let mut message = msg.to_string();
-        let position = print_error_frame(self.input, pos, &message);
-
-        if let Some((line, column)) = position {
-            message.push_str(&format!(" ({}:{})", line, column))
-        }
-
-        #[cfg(feature = "wasm")]
-        externs::__throw__(&message);
-
-        panic!("{}", message);
+    // 返回一个解析错误
+    pub(crate) fn unexpected_err<T>(&self, pos: usize, msg: &str) -> ParseResult<T> {
+        Err(ParseError {
+            message: msg.to_string(),
+            pos,
+        })
     }
 
-    // 抛出一个 unexpected 错误
-    pub(crate) fn unexpected_pos(&mut self, pos: usize, msg: Option<&str>) -> ! {
+    // 返回一个 unexpected 错误
+    pub(crate) fn unexpected_pos<T>(
+        &self,
+        pos: usize,
+        msg: Option<&str>,
+    ) -> ParseResult<T> {
         let mut message = match self.chars.get(pos) {
             None => String::from("Unexpected end of file"),
             Some(ch) => format!("Unexpected token `{}`", ch),
@@ -194,13 +255,17 @@ impl<'a> Parser<'a> {
         self.unexpected_err(pos, &message)
     }
 
-    // 抛出一个 unexpected token 错误
-    pub(crate) fn unexpected(&mut self, msg: Option<&str>) -> ! {
-        self.unexpected_token(self.current_token.clone(), msg)
+    // 返回一个 unexpected token 错误
+    pub(crate) fn unexpected<T>(&self, msg: Option<&str>) -> ParseResult<T> {
+        self.unexpected_token(&self.current_token, msg)
     }
 
-    // 抛出一个 unexpected token 错误
-    pub(crate) fn unexpected_token(&mut self, token: Token, msg: Option<&str>) -> ! {
+    // 返回一个 unexpected token 错误
+    pub(crate) fn unexpected_token<T>(
+        &self,
+        token: &Token,
+        msg: Option<&str>,
+    ) -> ParseResult<T> {
         let mut message = String::new();
         match token.token_type {
             TokenType::EOF => message.push_str("Unexpected end of file"),
@@ -214,8 +279,8 @@ impl<'a> Parser<'a> {
         self.unexpected_err(token.start, &message)
     }
 
-    // 抛出一个 unexpected kind 错误
-    pub(crate) fn unexpected_kind(&mut self, token: Token) {
+    // 返回一个 unexpected kind 错误
+    pub(crate) fn unexpected_kind<T>(&self, token: &Token) -> ParseResult<T> {
         let mut message = String::new();
         if token.value == "void" {
             message.push_str("Unexpected kind: ")
@@ -1,39 +1,47 @@
 use crate::node::Node;
 use crate::shared::{Kind, KindName};
-use crate::state::Parser;
+use crate::state::{ParseResult, Parser};
 use crate::token::TokenType;
 use std::collections::HashSet;
 
 impl<'a> Parser<'a> {
     // 解析一条语句
-    pub(crate) fn parse_statement(&mut self) -> Node {
+    pub(crate) fn parse_statement(&mut self) -> ParseResult<Node> {
         // 结尾分号是否可以省略
         let mut omit_tailing_semi = false;
 
+        // 声明语句之前的文档注释
+        let doc = self.current_doc.take();
+
         let statement = match self.current_token.token_type {
             TokenType::Keyword => {
                 let value = &self.current_token.value;
                 match value.as_bytes() {
                     b"pub" => {
-                        self.next_token();
+                        self.next_token()?;
                         if self.is_token(TokenType::Keyword) {
                             match self.current_token.value.as_bytes() {
                                 b"fn" => {
                                     omit_tailing_semi = true;
-                                    self.parse_function_declaration(true)
+                                    self.parse_function_declaration(true, doc)
+                                }
+                                b"var" | b"let" | b"const" => {
+                                    self.parse_variable_declaration(true, doc)
                                 }
                                 _ => self.unexpected(None),
                             }
                         } else {
-                            self.unexpected(None);
+                            self.unexpected(None)
                         }
                     }
                     b"import" => self.parse_import_declaration(),
                     b"fn" => {
                         omit_tailing_semi = true;
-                        self.parse_function_declaration(false)
+                        self.parse_function_declaration(false, doc)
+                    }
+                    b"var" | b"let" | b"const" => {
+                        self.parse_variable_declaration(false, doc)
                     }
-                    b"var" => self.parse_variable_declaration(),
                     b"return" => self.parse_return_statement(),
                     b"if" => {
                         omit_tailing_semi = true;
@@ -43,6 +51,14 @@ impl<'a> Parser<'a> {
                         omit_tailing_semi = true;
                         self.parse_loop_statement(None, self.current_token.start)
                     }
+                    b"while" => {
+                        omit_tailing_semi = true;
+                        self.parse_while_statement(None, self.current_token.start)
+                    }
+                    b"for" => {
+                        omit_tailing_semi = true;
+                        self.parse_for_statement(None, self.current_token.start)
+                    }
                     b"break" => self.parse_break_statement(),
                     b"continue" => self.parse_continue_statement(),
                     _ => self.unexpected(None),
@@ -52,15 +68,23 @@ impl<'a> Parser<'a> {
                 // 可能是 label
                 let maybe_label = self.current_token.value.to_string();
                 let start_pos = self.current_token.start;
-                if self.check_next_char(':') {
-                    self.next_token();
-                    self.consume_or_panic(TokenType::Colon);
-                    self.expect(TokenType::Keyword);
+                if self.check_next_char(':')? {
+                    self.next_token()?;
+                    self.consume_or_err(TokenType::Colon)?;
+                    self.expect(TokenType::Keyword)?;
                     match self.current_token.value.as_bytes() {
                         b"loop" => {
                             omit_tailing_semi = true;
                             self.parse_loop_statement(Some(maybe_label), start_pos)
                         }
+                        b"while" => {
+                            omit_tailing_semi = true;
+                            self.parse_while_statement(Some(maybe_label), start_pos)
+                        }
+                        b"for" => {
+                            omit_tailing_semi = true;
+                            self.parse_for_statement(Some(maybe_label), start_pos)
+                        }
                         _ => self.unexpected(None),
                     }
                 } else {
@@ -73,30 +97,42 @@ impl<'a> Parser<'a> {
                 self.parse_block_statement(false)
             }
             _ => self.parse_expression_statement(),
-        };
+        }?;
 
+        // 块、条件、循环语句及函数定义之后的分号，以及其他语句之后多于一个的分号为多余的分号
         let mut tail_semi_count = 0;
-        while self.consume(TokenType::Semi) {
+        let mut redundant_semi: Option<(usize, usize)> = None;
+        loop {
+            let (start, end) = (self.current_token.start, self.current_token.end);
+            if !self.consume(TokenType::Semi)? {
+                break;
+            }
             tail_semi_count += 1;
+            if omit_tailing_semi || tail_semi_count > 1 {
+                redundant_semi = Some((redundant_semi.map_or(start, |v| v.0), end));
+            }
             if self.is_token(TokenType::EOF) {
                 break;
             }
         }
+        if let Some(position) = redundant_semi {
+            self.diagnostics.warn(position, "Redundant semicolon");
+        }
         if !omit_tailing_semi
             && tail_semi_count == 0
             && !self.is_seen_newline
             && !self.is_token(TokenType::EOF)
             && !self.is_token(TokenType::BraceR)
         {
-            self.unexpected(None);
+            return self.unexpected(None);
         }
 
-        statement
+        Ok(statement)
     }
 
     // 解析 import 语句
-    pub(crate) fn parse_import_declaration(&mut self) -> Node {
-        self.validate_program_root("Import declaration");
+    pub(crate) fn parse_import_declaration(&mut self) -> ParseResult<Node> {
+        self.validate_program_root("Import declaration")?;
         let mark_begin_pos = self.current_token.end;
         let start = self.current_token.start;
         let mut end = mark_begin_pos;
@@ -141,16 +177,16 @@ impl<'a> Parser<'a> {
             self.move_index(1);
         }
         if is_std_source && !has_std_ending {
-            self.unexpected_err(mark_pos, "Invalid import source");
+            return self.unexpected_err(mark_pos, "Invalid import source");
         }
         if source.is_empty() {
-            self.unexpected_err(mark_begin_pos, "Missing import source");
+            return self.unexpected_err(mark_begin_pos, "Missing import source");
         }
 
         // parse import specifiers
         if let Some(ref mut specifiers) = specifiers {
-            self.next_token();
-            self.consume_or_panic(TokenType::BraceL);
+            self.next_token()?;
+            self.consume_or_err(TokenType::BraceL)?;
             let mut specifier_set = HashSet::new();
             while self.check_valid_index() && !self.is_token(TokenType::BraceR) {
                 let imported = self.current_token.value.to_string();
@@ -160,18 +196,18 @@ impl<'a> Parser<'a> {
 
                 if self.is_token(TokenType::Star) {
                     mark_pos = self.current_token.start;
-                    self.next_token();
+                    self.next_token()?;
                 } else {
-                    self.expect(TokenType::Identifier);
+                    self.expect(TokenType::Identifier)?;
                     mark_pos = self.current_token.start;
-                    self.next_token();
+                    self.next_token()?;
                     if self.is_keyword("as") {
-                        self.next_token();
-                        self.expect(TokenType::Identifier);
+                        self.next_token()?;
+                        self.expect(TokenType::Identifier)?;
                         local = Some(self.current_token.value.to_string());
                         mark_pos = self.current_token.start;
                         end = self.current_token.end;
-                        self.next_token();
+                        self.next_token()?;
                     }
                 }
 
@@ -182,10 +218,10 @@ impl<'a> Parser<'a> {
                 };
                 let spec = spec.clone();
                 if specifier_set.get(&spec).is_some() {
-                    self.unexpected_err(
+                    return self.unexpected_err(
                         mark_pos,
                         &format!("The import specifier `{}` already exists", spec),
-                    )
+                    );
                 }
                 specifier_set.insert(spec);
 
@@ -197,111 +233,115 @@ impl<'a> Parser<'a> {
                 specifiers.push(Box::new(specifier));
 
                 // maybe has next specifier
-                if !self.consume(TokenType::Comma) {
+                if !self.consume(TokenType::Comma)? {
                     break;
                 }
             }
             end = self.current_token.end;
-            self.consume_or_panic(TokenType::BraceR);
+            self.consume_or_err(TokenType::BraceR)?;
         } else {
             end = self.index;
-            self.next_token();
+            self.next_token()?;
         }
 
-        Node::ImportDeclaration {
+        Ok(Node::ImportDeclaration {
             position: (start, end),
             source,
             is_std_source,
             specifiers,
-        }
+        })
     }
 
     // 解析函数定义语句
-    pub(crate) fn parse_function_declaration(&mut self, is_pub: bool) -> Node {
-        self.validate_program_root("Function declaration");
+    pub(crate) fn parse_function_declaration(
+        &mut self,
+        is_pub: bool,
+        doc: Option<String>,
+    ) -> ParseResult<Node> {
+        self.validate_program_root("Function declaration")?;
 
         let start = self.current_token.start;
-        self.next_token();
+        self.next_token()?;
 
         // id
-        self.expect(TokenType::Identifier);
+        self.expect(TokenType::Identifier)?;
         let id = Box::new(self.gen_identifier(self.current_token.clone(), Kind::None));
-        self.next_token();
+        self.next_token()?;
 
         // arguments
         let mut arguments = vec![];
-        self.consume_or_panic(TokenType::ParenL);
+        self.consume_or_err(TokenType::ParenL)?;
         while self.check_valid_index() && self.is_token(TokenType::Identifier) {
             let name_token = self.current_token.clone();
-            self.next_token();
+            self.next_token()?;
 
             // argument kind
-            self.consume_or_panic(TokenType::Colon);
-            self.expect(TokenType::Identifier);
+            self.consume_or_err(TokenType::Colon)?;
+            self.expect(TokenType::Identifier)?;
             let kind_str = self.current_token.value.to_string();
-            let kind_name = KindName::from(&kind_str, false);
-            if kind_name.is_none() {
-                self.unexpected_kind(self.current_token.clone());
-            }
+            let kind_name = match KindName::from(&kind_str, false) {
+                Some(kind_name) => kind_name,
+                None => return self.unexpected_kind(&self.current_token),
+            };
 
-            arguments.push(Box::new(
-                self.gen_identifier(name_token, kind_name.unwrap().into()),
-            ));
-            self.next_token();
+            arguments.push(Box::new(self.gen_identifier(name_token, kind_name.into())));
+            self.next_token()?;
 
             // maybe has next argument
-            self.consume(TokenType::Comma);
+            self.consume(TokenType::Comma)?;
         }
-        self.consume_or_panic(TokenType::ParenR);
+        self.consume_or_err(TokenType::ParenR)?;
 
         // maybe return kind
         let mut return_kind = Kind::None;
-        if self.consume(TokenType::ReturnSym) {
-            self.expect(TokenType::Identifier);
+        if self.consume(TokenType::ReturnSym)? {
+            self.expect(TokenType::Identifier)?;
             let kind_str = self.current_token.value.to_string();
-            let kind_name = KindName::from(&kind_str, true);
-            if kind_name.is_none() {
-                self.unexpected_kind(self.current_token.clone());
-            }
-            return_kind = kind_name.unwrap().into();
-            self.next_token();
+            return_kind = match KindName::from(&kind_str, true) {
+                Some(kind_name) => kind_name.into(),
+                None => return self.unexpected_kind(&self.current_token),
+            };
+            self.next_token()?;
         }
 
         // body
-        let body = self.parse_block_statement(true);
+        let body = self.parse_block_statement(true)?;
 
-        Node::FunctionDeclaration {
+        Ok(Node::FunctionDeclaration {
             position: (start, body.read_position().1),
             id,
             arguments,
             body: Box::new(body),
             return_kind,
             is_pub,
-        }
+            doc,
+        })
     }
 
     // 解析表达式语句
-    pub(crate) fn parse_expression_statement(&mut self) -> Node {
+    pub(crate) fn parse_expression_statement(&mut self) -> ParseResult<Node> {
         if self.current_block_level == 0 {
-            self.unexpected(Some(&format!("Unexpected token `{}`", self.current_token.value)));
+            return self.unexpected(Some(&format!(
+                "Unexpected token `{}`",
+                self.current_token.value
+            )));
         }
 
-        let expression = self.parse_expression();
-        if expression.is_none() {
-            self.unexpected(Some("Invalid expression"));
-        }
-        let expression = expression.unwrap();
+        let expression = match self.parse_expression()? {
+            Some(expression) => expression,
+            None => return self.unexpected(Some("Invalid expression")),
+        };
         let position = expression.read_position();
-        Node::ExpressionStatement {
+        Ok(Node::ExpressionStatement {
             expression: Box::new(expression),
             position,
-        }
+        })
     }
 
     // 解析块级语句
-    pub(crate) fn parse_block_statement(&mut self, with_fn: bool) -> Node {
+    pub(crate) fn parse_block_statement(&mut self, with_fn: bool) -> ParseResult<Node> {
         if !with_fn {
-            self.validate_inside_fn();
+            self.validate_inside_fn()?;
         }
 
         // 块级作用域层级 +1
@@ -309,72 +349,81 @@ impl<'a> Parser<'a> {
 
         let start = self.current_token.start;
         let mut body = vec![];
-        self.consume_or_panic(TokenType::BraceL);
+        self.consume_or_err(TokenType::BraceL)?;
         while self.check_valid_index() && !self.is_token(TokenType::BraceR) {
-            body.push(Box::new(self.parse_statement()));
+            body.push(Box::new(self.parse_statement()?));
         }
         let end = self.current_token.start;
-        self.consume_or_panic(TokenType::BraceR);
+        self.consume_or_err(TokenType::BraceR)?;
 
         // 块级作用域层级 -1
         self.current_block_level -= 1;
 
-        Node::BlockStatement {
+        Ok(Node::BlockStatement {
             position: (start, end),
             body,
-        }
+        })
     }
 
-    // 解析变量定义语句
-    pub(crate) fn parse_variable_declaration(&mut self) -> Node {
-        self.validate_inside_fn();
+    // 解析变量定义语句，`var` 声明可变变量，`let` 及 `const` 声明不可变变量
+    // 定义在根层级的为全局变量
+    pub(crate) fn parse_variable_declaration(
+        &mut self,
+        is_pub: bool,
+        doc: Option<String>,
+    ) -> ParseResult<Node> {
+        if is_pub {
+            self.validate_program_root("Public variable declaration")?;
+        }
+        let keyword = self.current_token.value.to_string();
         let start = self.current_token.start;
-        self.next_token();
+        self.next_token()?;
 
         // id
-        self.expect(TokenType::Identifier);
+        self.expect(TokenType::Identifier)?;
         let id_token = self.current_token.clone();
-        self.next_token();
+        self.next_token()?;
 
         // maybe variable kind
         let mut kind = Kind::Infer;
-        if self.consume(TokenType::Colon) {
-            self.expect(TokenType::Identifier);
+        if self.consume(TokenType::Colon)? {
+            self.expect(TokenType::Identifier)?;
             let kind_str = self.current_token.value.to_string();
-            let kind_name = KindName::from(&kind_str, false);
-            if kind_name.is_none() {
-                self.unexpected_kind(self.current_token.clone());
-            }
-            kind = kind_name.unwrap().into();
-            self.next_token();
+            kind = match KindName::from(&kind_str, false) {
+                Some(kind_name) => kind_name.into(),
+                None => return self.unexpected_kind(&self.current_token),
+            };
+            self.next_token()?;
         }
 
         let id = Box::new(self.gen_identifier(id_token, kind));
 
         // init
         let mark_pos = self.current_token.start;
-        self.consume_or_panic(TokenType::Assign);
-        let init = self.parse_expression();
-        if init.is_none() {
-            self.unexpected_err(mark_pos, "Missing initial value");
-        }
-        let init = init.unwrap();
+        self.consume_or_err(TokenType::Assign)?;
+        let init = match self.parse_expression()? {
+            Some(init) => init,
+            None => return self.unexpected_err(mark_pos, "Missing initial value"),
+        };
 
-        Node::VariableDeclaration {
+        Ok(Node::VariableDeclaration {
             position: (start, init.read_position().1),
             id,
             init: Box::new(init),
-        }
+            keyword,
+            is_pub,
+            doc,
+        })
     }
 
     // 解析 return 语句
-    pub(crate) fn parse_return_statement(&mut self) -> Node {
-        self.validate_inside_fn();
+    pub(crate) fn parse_return_statement(&mut self) -> ParseResult<Node> {
+        self.validate_inside_fn()?;
         let start = self.current_token.start;
         let mut end = self.current_token.end;
-        self.next_token();
+        self.next_token()?;
 
-        let argument = self.parse_expression();
+        let argument = self.parse_expression()?;
         let argument = match argument {
             Some(v) => {
                 end = v.read_position().1;
@@ -382,15 +431,15 @@ impl<'a> Parser<'a> {
             }
             None => None,
         };
-        Node::ReturnStatement {
+        Ok(Node::ReturnStatement {
             position: (start, end),
             argument,
-        }
+        })
     }
 
     // 解析 if 语句
-    pub(crate) fn parse_if_statement(&mut self) -> Node {
-        self.validate_inside_fn();
+    pub(crate) fn parse_if_statement(&mut self) -> ParseResult<Node> {
+        self.validate_inside_fn()?;
 
         // 递归解析时，如果不是 else-if，只需解析块语句就行了
         if !self.is_keyword("if") {
@@ -399,39 +448,39 @@ impl<'a> Parser<'a> {
 
         let mark_pos = self.current_token.end;
         let start = self.current_token.start;
-        self.next_token();
+        self.next_token()?;
 
         // condition
-        let has_paren = self.consume(TokenType::ParenL);
-        let condition = self.parse_expression();
-        if condition.is_none() {
-            self.unexpected_err(mark_pos, "Missing condition");
-        }
+        let has_paren = self.consume(TokenType::ParenL)?;
+        let condition = match self.parse_expression()? {
+            Some(condition) => condition,
+            None => return self.unexpected_err(mark_pos, "Missing condition"),
+        };
 
         if has_paren {
-            self.consume_or_panic(TokenType::ParenR);
+            self.consume_or_err(TokenType::ParenR)?;
         }
 
         // consequent
-        let consequent = self.parse_block_statement(false);
+        let consequent = self.parse_block_statement(false)?;
         let mut end = consequent.read_position().1;
 
         // alternate
         let alternate = if self.is_keyword("else") {
-            self.next_token();
-            let stat = self.parse_if_statement();
+            self.next_token()?;
+            let stat = self.parse_if_statement()?;
             end = stat.read_position().1;
             Some(Box::new(stat))
         } else {
             None
         };
 
-        Node::IfStatement {
+        Ok(Node::IfStatement {
             position: (start, end),
-            condition: Box::new(condition.unwrap()),
+            condition: Box::new(condition),
             consequent: Box::new(consequent),
             alternate,
-        }
+        })
     }
 
     // 解析 loop 循环语句
@@ -439,68 +488,133 @@ impl<'a> Parser<'a> {
         &mut self,
         label: Option<String>,
         start_pos: usize,
-    ) -> Node {
-        self.validate_inside_fn();
+    ) -> ParseResult<Node> {
+        self.validate_inside_fn()?;
         self.current_loop_level += 1;
 
-        self.next_token();
-        let body = self.parse_block_statement(false);
+        self.next_token()?;
+        let body = self.parse_block_statement(false)?;
+
+        self.current_loop_level -= 1;
+        Ok(Node::LoopStatement {
+            position: (start_pos, body.read_position().1),
+            label,
+            body: Box::new(body),
+        })
+    }
+
+    // 解析 while 循环语句
+    pub(crate) fn parse_while_statement(
+        &mut self,
+        label: Option<String>,
+        start_pos: usize,
+    ) -> ParseResult<Node> {
+        self.validate_inside_fn()?;
+        let mark_pos = self.current_token.end;
+        self.next_token()?;
+
+        // condition
+        let condition = match self.parse_expression()? {
+            Some(condition) => condition,
+            None => return self.unexpected_err(mark_pos, "Missing condition"),
+        };
 
+        self.current_loop_level += 1;
+        let body = self.parse_block_statement(false)?;
         self.current_loop_level -= 1;
-        Node::LoopStatement {
+
+        Ok(Node::WhileStatement {
             position: (start_pos, body.read_position().1),
             label,
+            condition: Box::new(condition),
             body: Box::new(body),
+        })
+    }
+
+    // 解析 for 循环语句，如: `for i in 0..10 {}`
+    pub(crate) fn parse_for_statement(
+        &mut self,
+        label: Option<String>,
+        start_pos: usize,
+    ) -> ParseResult<Node> {
+        self.validate_inside_fn()?;
+        self.next_token()?;
+
+        // id
+        self.expect(TokenType::Identifier)?;
+        let id = self.gen_identifier(self.current_token.clone(), Kind::Infer);
+        self.next_token()?;
+
+        if !self.is_keyword("in") {
+            return self.unexpected(None);
         }
+        self.next_token()?;
+
+        // iterable
+        let iterable = self.parse_range_expression()?;
+
+        self.current_loop_level += 1;
+        let body = self.parse_block_statement(false)?;
+        self.current_loop_level -= 1;
+
+        Ok(Node::ForStatement {
+            position: (start_pos, body.read_position().1),
+            label,
+            id: Box::new(id),
+            iterable: Box::new(iterable),
+            body: Box::new(body),
+        })
     }
 
     // 解析 break 语句
-    pub(crate) fn parse_break_statement(&mut self) -> Node {
-        self.validate_inside_fn();
+    pub(crate) fn parse_break_statement(&mut self) -> ParseResult<Node> {
+        self.validate_inside_fn()?;
         let start = self.current_token.start;
         let mut end = self.current_token.end;
 
         if self.current_loop_level == 0 {
-            self.unexpected(Some("The `break` can only be use in loop statements"));
+            return self
+                .unexpected(Some("The `break` can only be use in loop statements"));
         }
 
-        self.next_token();
+        self.next_token()?;
         let label = if self.is_token(TokenType::Identifier) {
             let label = Some(self.current_token.value.to_string());
             end = self.current_token.end;
-            self.next_token();
+            self.next_token()?;
             label
         } else {
             None
         };
-        Node::BreakStatement {
+        Ok(Node::BreakStatement {
             position: (start, end),
             label,
-        }
+        })
     }
 
     // 解析 continue 语句
-    pub(crate) fn parse_continue_statement(&mut self) -> Node {
-        self.validate_inside_fn();
+    pub(crate) fn parse_continue_statement(&mut self) -> ParseResult<Node> {
+        self.validate_inside_fn()?;
         let start = self.current_token.start;
         let mut end = self.current_token.end;
 
         if self.current_loop_level == 0 {
-            self.unexpected(Some("The `continue` can only be use in loop statements"));
+            return self
+                .unexpected(Some("The `continue` can only be use in loop statements"));
         }
 
-        self.next_token();
+        self.next_token()?;
         let label = if self.is_token(TokenType::Identifier) {
             let label = Some(self.current_token.value.to_string());
             end = self.current_token.end;
-            self.next_token();
+            self.next_token()?;
             label
         } else {
             None
         };
-        Node::ContinueStatement {
+        Ok(Node::ContinueStatement {
             position: (start, end),
             label,
-        }
+        })
     }
 }
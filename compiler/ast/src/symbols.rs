@@ -0,0 +1,424 @@
+use crate::node::Node;
+use crate::shared::Kind;
+use crate::visitor::Visitor;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::ops::Deref;
+
+// 符号的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SymbolKind {
+    Function,
+    Argument,
+    Variable,
+    Label,
+    Import,
+}
+
+// 声明的符号（函数、参数、变量、循环标签、导入）
+#[derive(Debug, Clone, Serialize)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub position: (usize, usize),    // 声明处名称的位置
+    pub declaration: (usize, usize), // 整个声明的位置
+    pub scope: usize,                // 所在作用域的下标
+    pub visible_from: usize,         // 从该位置开始可以引用
+    pub value_kind: Kind,            // 声明的类型（函数为返回值类型），未标注时为 Infer
+    pub detail: String,              // 签名，如: `let a: num`、`fn add(a: num) -> num`
+    pub doc: Option<String>,
+}
+
+// 作用域，程序、函数、块语句及循环语句各为一层作用域
+#[derive(Debug, Clone, Serialize)]
+pub struct Scope {
+    pub parent: Option<usize>,
+    pub position: (usize, usize),
+    pub symbols: Vec<usize>, // 作用域中声明的符号的下标
+}
+
+// 对符号的引用
+#[derive(Debug, Clone, Serialize)]
+pub struct Reference {
+    pub position: (usize, usize),
+    pub symbol: usize,
+}
+
+// 符号表，记录所有的声明及其引用，位置均为字符索引
+#[derive(Debug, Serialize)]
+pub struct SymbolTable {
+    pub symbols: Vec<Symbol>,
+    pub scopes: Vec<Scope>,
+    pub references: Vec<Reference>,
+    pub unresolved: Vec<(String, (usize, usize))>, // 未找到声明的引用，如: 内置函数、通配导入的名称
+}
+
+impl SymbolTable {
+    pub fn new(program: &Node) -> Self {
+        let builder = RefCell::new(SymbolTableBuilder {
+            table: SymbolTable {
+                symbols: vec![],
+                scopes: vec![],
+                references: vec![],
+                unresolved: vec![],
+            },
+            scope_stack: vec![],
+            skipped: HashSet::new(),
+        });
+        Visitor::walk_with_exit(
+            program,
+            &mut |node, _| builder.borrow_mut().enter(node),
+            &mut |node, _| builder.borrow_mut().exit(node),
+        );
+        builder.into_inner().table
+    }
+
+    // 某个位置（位于声明或引用上）对应的符号下标
+    pub fn symbol_at(&self, offset: usize) -> Option<usize> {
+        let contains =
+            |position: &(usize, usize)| position.0 <= offset && offset <= position.1;
+        if let Some(index) = self.symbols.iter().position(|s| contains(&s.position)) {
+            return Some(index);
+        }
+        self.references
+            .iter()
+            .find(|reference| contains(&reference.position))
+            .map(|reference| reference.symbol)
+    }
+
+    // 某个位置上的标识符的定义
+    pub fn definition_at(&self, offset: usize) -> Option<&Symbol> {
+        self.symbol_at(offset).map(|index| &self.symbols[index])
+    }
+
+    // 符号的所有引用位置，`include_declaration` 为 true 时包含声明处
+    pub fn references_of(
+        &self,
+        symbol: usize,
+        include_declaration: bool,
+    ) -> Vec<(usize, usize)> {
+        let mut positions = vec![];
+        if include_declaration {
+            positions.push(self.symbols[symbol].position);
+        }
+        for reference in self.references.iter() {
+            if reference.symbol == symbol {
+                positions.push(reference.position);
+            }
+        }
+        positions
+    }
+
+    // 包含某个位置的最内层作用域
+    pub fn scope_at(&self, offset: usize) -> usize {
+        // 内层作用域总是在外层之后创建，且同层作用域互不重叠
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.position.0 <= offset && offset <= scope.position.1)
+            .unwrap_or(0)
+    }
+
+    // 某个位置可见的所有符号（内层优先，被遮蔽的同名符号不返回）
+    pub fn visible_at(&self, offset: usize) -> Vec<&Symbol> {
+        let mut names = HashSet::new();
+        let mut symbols = vec![];
+        if self.scopes.is_empty() {
+            return symbols;
+        }
+        let mut scope = Some(self.scope_at(offset));
+        while let Some(index) = scope {
+            for symbol_index in self.scopes[index].symbols.iter().rev() {
+                let symbol = &self.symbols[*symbol_index];
+                if symbol.visible_from <= offset
+                    && names.insert((symbol.kind == SymbolKind::Label, &symbol.name))
+                {
+                    symbols.push(symbol);
+                }
+            }
+            scope = self.scopes[index].parent;
+        }
+        symbols
+    }
+}
+
+struct SymbolTableBuilder {
+    table: SymbolTable,
+    scope_stack: Vec<usize>,
+    skipped: HashSet<(usize, usize)>, // 作为声明名称或属性名的标识符，不是引用
+}
+
+impl SymbolTableBuilder {
+    fn enter(&mut self, node: &Node) {
+        match node {
+            Node::Program { body, position } => {
+                self.push_scope(*position);
+                // 函数及全局变量会在编译函数体之前创建，函数中可以引用之后定义的函数及全局变量
+                for stat in body.iter() {
+                    match stat.deref() {
+                        Node::FunctionDeclaration { .. } => self.declare_function(stat),
+                        Node::VariableDeclaration { .. } => {
+                            self.declare_variable(stat, 0)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Node::ImportDeclaration { specifiers, .. } => {
+                for specifier in specifiers.iter().flatten() {
+                    if let Node::ImportSpecifier {
+                        imported,
+                        local,
+                        position,
+                    } = specifier.deref()
+                    {
+                        if imported == "*" {
+                            continue;
+                        }
+                        let name = local.as_ref().unwrap_or(imported);
+                        let detail = match local {
+                            Some(local) => format!("import {} as {}", imported, local),
+                            None => format!("import {}", imported),
+                        };
+                        let name_start = position.1 - name.chars().count();
+                        self.declare(Symbol {
+                            name: name.to_string(),
+                            kind: SymbolKind::Import,
+                            position: (name_start, position.1),
+                            declaration: *position,
+                            scope: 0,
+                            visible_from: 0,
+                            value_kind: Kind::Infer,
+                            detail,
+                            doc: None,
+                        });
+                    }
+                }
+            }
+            Node::FunctionDeclaration {
+                id,
+                arguments,
+                body,
+                position,
+                ..
+            } => {
+                self.skipped.insert(id.read_position());
+                self.push_scope(*position);
+                for arg in arguments.iter() {
+                    let (name, kind, _) = arg.read_identifier();
+                    self.skipped.insert(arg.read_position());
+                    self.declare(Symbol {
+                        name: name.to_string(),
+                        kind: SymbolKind::Argument,
+                        position: arg.read_position(),
+                        declaration: arg.read_position(),
+                        scope: 0,
+                        visible_from: body.read_position().0,
+                        value_kind: *kind,
+                        detail: format!("{}: {}", name, kind.to_string()),
+                        doc: None,
+                    });
+                }
+            }
+            // 局部变量需先定义后使用，初始值中不能引用自身
+            Node::VariableDeclaration { position, .. } if self.scope_stack.len() > 1 => {
+                self.declare_variable(node, position.1)
+            }
+            Node::BlockStatement { position, .. } => self.push_scope(*position),
+            Node::LoopStatement {
+                label, position, ..
+            }
+            | Node::WhileStatement {
+                label, position, ..
+            } => {
+                self.push_scope(*position);
+                self.declare_label(label, *position);
+            }
+            Node::ForStatement {
+                label,
+                id,
+                body,
+                position,
+                ..
+            } => {
+                self.push_scope(*position);
+                self.declare_label(label, *position);
+                let (name, kind, _) = id.read_identifier();
+                self.skipped.insert(id.read_position());
+                let detail = match kind {
+                    Kind::Some(_) => format!("var {}: {}", name, kind.to_string()),
+                    _ => format!("var {}", name),
+                };
+                self.declare(Symbol {
+                    name: name.to_string(),
+                    kind: SymbolKind::Variable,
+                    position: id.read_position(),
+                    declaration: id.read_position(),
+                    scope: 0,
+                    visible_from: body.read_position().0,
+                    value_kind: *kind,
+                    detail,
+                    doc: None,
+                });
+            }
+            Node::BreakStatement {
+                label: Some(label),
+                position,
+            }
+            | Node::ContinueStatement {
+                label: Some(label),
+                position,
+            } => {
+                let start = position.1 - label.chars().count();
+                self.reference(label, true, (start, position.1));
+            }
+            Node::MemberExpression { property, .. } => {
+                // 属性为内置类型的方法名
+                self.skipped.insert(property.read_position());
+            }
+            Node::Identifier { name, position, .. }
+                if !self.skipped.contains(position) =>
+            {
+                self.reference(name, false, *position);
+            }
+            _ => {}
+        }
+    }
+
+    fn exit(&mut self, node: &Node) {
+        match node {
+            Node::Program { .. }
+            | Node::FunctionDeclaration { .. }
+            | Node::BlockStatement { .. }
+            | Node::LoopStatement { .. }
+            | Node::WhileStatement { .. }
+            | Node::ForStatement { .. } => {
+                self.scope_stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn push_scope(&mut self, position: (usize, usize)) {
+        self.table.scopes.push(Scope {
+            parent: self.scope_stack.last().copied(),
+            position,
+            symbols: vec![],
+        });
+        self.scope_stack.push(self.table.scopes.len() - 1);
+    }
+
+    // 在当前作用域中添加符号
+    fn declare(&mut self, mut symbol: Symbol) {
+        let scope = *self.scope_stack.last().unwrap();
+        symbol.scope = scope;
+        self.table.symbols.push(symbol);
+        let index = self.table.symbols.len() - 1;
+        self.table.scopes[scope].symbols.push(index);
+    }
+
+    fn declare_function(&mut self, node: &Node) {
+        if let Node::FunctionDeclaration {
+            id,
+            arguments,
+            return_kind,
+            doc,
+            position,
+            ..
+        } = node
+        {
+            let (name, ..) = id.read_identifier();
+            let arguments = arguments
+                .iter()
+                .map(|arg| {
+                    let (arg_name, arg_kind, _) = arg.read_identifier();
+                    format!("{}: {}", arg_name, arg_kind.to_string())
+                })
+                .collect::<Vec<String>>();
+            let mut detail = format!("fn {}({})", name, arguments.join(", "));
+            if return_kind.is_exact() {
+                detail.push_str(" -> ");
+                detail.push_str(&return_kind.to_string());
+            }
+
+            self.declare(Symbol {
+                name: name.to_string(),
+                kind: SymbolKind::Function,
+                position: id.read_position(),
+                declaration: *position,
+                scope: 0,
+                visible_from: 0,
+                value_kind: *return_kind,
+                detail,
+                doc: doc.clone(),
+            });
+        }
+    }
+
+    fn declare_variable(&mut self, node: &Node, visible_from: usize) {
+        if let Node::VariableDeclaration {
+            id,
+            keyword,
+            doc,
+            position,
+            ..
+        } = node
+        {
+            let (name, kind, _) = id.read_identifier();
+            self.skipped.insert(id.read_position());
+            let detail = match kind {
+                Kind::Some(_) => format!("{} {}: {}", keyword, name, kind.to_string()),
+                _ => format!("{} {}", keyword, name),
+            };
+            self.declare(Symbol {
+                name: name.to_string(),
+                kind: SymbolKind::Variable,
+                position: id.read_position(),
+                declaration: *position,
+                scope: 0,
+                visible_from,
+                value_kind: *kind,
+                detail,
+                doc: doc.clone(),
+            });
+        }
+    }
+
+    // 循环标签，位于循环语句的开头，如: `outer: loop {}`
+    fn declare_label(&mut self, label: &Option<String>, position: (usize, usize)) {
+        if let Some(label) = label {
+            self.declare(Symbol {
+                name: label.to_string(),
+                kind: SymbolKind::Label,
+                position: (position.0, position.0 + label.chars().count()),
+                declaration: position,
+                scope: 0,
+                visible_from: position.0,
+                value_kind: Kind::None,
+                detail: format!("{}:", label),
+                doc: None,
+            });
+        }
+    }
+
+    // 从内到外查找可见的同名符号并记录引用，标签与其他符号的命名互不影响
+    fn reference(&mut self, name: &str, is_label: bool, position: (usize, usize)) {
+        for scope in self.scope_stack.iter().rev() {
+            for index in self.table.scopes[*scope].symbols.iter().rev() {
+                let symbol = &self.table.symbols[*index];
+                if symbol.name == name
+                    && (symbol.kind == SymbolKind::Label) == is_label
+                    && symbol.visible_from <= position.0
+                {
+                    self.table.references.push(Reference {
+                        position,
+                        symbol: *index,
+                    });
+                    return;
+                }
+            }
+        }
+        if !is_label {
+            self.table.unresolved.push((name.to_string(), position));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// 对源码的一处修改，将 `position`（字符索引区间）替换为 `new_text`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    pub position: (usize, usize),
+    pub new_text: String,
+}
+
+impl TextEdit {
+    pub fn replace(position: (usize, usize), new_text: &str) -> Self {
+        TextEdit {
+            position,
+            new_text: new_text.to_string(),
+        }
+    }
+
+    pub fn insert(index: usize, new_text: &str) -> Self {
+        TextEdit::replace((index, index), new_text)
+    }
+
+    // 修改后长度的变化量
+    fn delta(&self) -> isize {
+        self.new_text.chars().count() as isize
+            - (self.position.1 - self.position.0) as isize
+    }
+}
+
+// 应用一组互不重叠的修改，返回修改后的源码
+pub fn apply_edits(source: &str, edits: &[TextEdit]) -> String {
+    let mut edits = edits.iter().collect::<Vec<&TextEdit>>();
+    edits.sort_by_key(|edit| edit.position);
+
+    let chars = source.chars().collect::<Vec<char>>();
+    let mut result = String::with_capacity(source.len());
+    let mut index = 0;
+    for edit in edits {
+        let (start, end) = edit.position;
+        result.extend(&chars[index.min(start)..start]);
+        result.push_str(&edit.new_text);
+        index = index.max(end);
+    }
+    result.extend(&chars[index.min(chars.len())..]);
+    result
+}
+
+// 修改前的字符索引 -> 修改后的字符索引，位于被替换区间内的位置会移动到区间开头
+pub fn map_index(edits: &[TextEdit], index: usize) -> usize {
+    let mut delta = 0;
+    let mut index = index;
+    for edit in edits.iter() {
+        if edit.position.1 <= index {
+            delta += edit.delta();
+        } else if edit.position.0 < index {
+            index = edit.position.0;
+        }
+    }
+    (index as isize + delta) as usize
+}
@@ -1,50 +1,78 @@
-use crate::shared::is_keyword_str;
-use crate::state::Parser;
+use crate::shared::{is_identifier_char, is_identifier_start, is_keyword_str};
+use crate::state::{ParseResult, Parser};
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize)]
 pub enum TokenType {
-    Begin, // 初始Token
-    EOF,   // 结束 Token
-    // Comment, // 注释
+    Begin,      // 初始Token
+    EOF,        // 结束 Token
+    Whitespace, // 空白字符，仅由 `Lexer` 产生
+    Comment,    // 注释，仅由 `Lexer` 产生
+    DocComment, // 文档注释，仅由 `Lexer` 产生
+    Error,      // 无法识别的字符或不完整的字面量，仅由 `Lexer` 产生
     Keyword,
     Identifier,
     Number,
     Boolean,
     String,
-    Assign,    // =
-    Plus,      // +
-    Sub,       // -
-    Mul,       // *
-    Div,       // /
-    REM,       // %
-    LT,        // <
-    LE,        // <=
-    GT,        // >
-    GE,        // >=
-    EQ,        // ==
-    NE,        // !=
-    LogicAnd,  // &&
-    LogicOr,   // ||
-    LogicNot,  // !
-    BitAnd,    // &
-    BitOr,     // |
-    BitNot,    // ~
-    BitXor,    // ^
-    ParenL,    // (
-    ParenR,    // )
-    BracketL,  // [
-    BracketR,  // ]
-    BraceL,    // {
-    BraceR,    // }
-    Comma,     // ,
-    Dot,       // .
-    Semi,      // ;
-    Colon,     // :
-    Star,      // *
-    ReturnSym, // ->
+    Template,     // 模板字符串中 `${` 之前的部分，如: `"a = ${`
+    Assign,       // =
+    PlusAssign,   // +=
+    SubAssign,    // -=
+    MulAssign,    // *=
+    DivAssign,    // /=
+    REMAssign,    // %=
+    BitAndAssign, // &=
+    BitOrAssign,  // |=
+    BitXorAssign, // ^=
+    Plus,         // +
+    Sub,          // -
+    Mul,          // *
+    Div,          // /
+    REM,          // %
+    LT,           // <
+    LE,           // <=
+    GT,           // >
+    GE,           // >=
+    EQ,           // ==
+    NE,           // !=
+    LogicAnd,     // &&
+    LogicOr,      // ||
+    LogicNot,     // !
+    BitAnd,       // &
+    BitOr,        // |
+    BitNot,       // ~
+    BitXor,       // ^
+    ParenL,       // (
+    ParenR,       // )
+    BracketL,     // [
+    BracketR,     // ]
+    BraceL,       // {
+    BraceR,       // }
+    Comma,        // ,
+    Dot,          // .
+    Range,        // ..
+    Semi,         // ;
+    Colon,        // :
+    Star,         // *
+    ReturnSym,    // ->
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CommentKind {
+    Line,  // `// ...`
+    Block, // `/* ... */`，可以嵌套
+    Doc,   // `/// ...`，文档注释，会附加到后面的声明语句上
+}
+
+#[derive(Debug, Clone)]
+pub struct Comment {
+    pub kind: CommentKind,
+    pub value: String, // 注释的内容（不含注释符号）
+    pub start: usize,
+    pub end: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Token {
     pub token_type: TokenType,
     pub value: String,
@@ -74,6 +102,14 @@ impl Token {
     ) -> Self {
         match token_type {
             TokenType::Assign
+            | TokenType::PlusAssign
+            | TokenType::SubAssign
+            | TokenType::MulAssign
+            | TokenType::DivAssign
+            | TokenType::REMAssign
+            | TokenType::BitAndAssign
+            | TokenType::BitOrAssign
+            | TokenType::BitXorAssign
             | TokenType::Plus
             | TokenType::Sub
             | TokenType::Mul
@@ -84,7 +120,9 @@ impl Token {
             | TokenType::BraceR
             | TokenType::Colon
             | TokenType::Comma
-            | TokenType::Semi => p.allow_expr = true,
+            | TokenType::Semi
+            | TokenType::Range
+            | TokenType::Template => p.allow_expr = true,
             _ => p.allow_expr = false,
         }
 
@@ -97,35 +135,40 @@ impl Token {
         }
     }
 
-    // 克隆 Token
-    pub(crate) fn clone(&self) -> Self {
-        Token {
-            token_type: self.token_type.clone(),
-            value: self.value.clone(),
-            precedence: self.precedence,
-            start: self.start,
-            end: self.end,
-        }
+    // 是否是赋值运算符（包括复合赋值，如: `+=`）
+    pub(crate) fn is_assign(&self) -> bool {
+        matches!(
+            self.token_type,
+            TokenType::Assign
+                | TokenType::PlusAssign
+                | TokenType::SubAssign
+                | TokenType::MulAssign
+                | TokenType::DivAssign
+                | TokenType::REMAssign
+                | TokenType::BitAndAssign
+                | TokenType::BitOrAssign
+                | TokenType::BitXorAssign
+        )
     }
 }
 
 impl<'a> Parser<'a> {
     // 读取下一个 token
-    pub(crate) fn next_token(&mut self) {
+    pub(crate) fn next_token(&mut self) -> ParseResult<()> {
         self.is_seen_newline = false;
         self.skip_space(true);
-        self.skip_comment();
+        self.skip_comment()?;
 
         let token = match self.current_char {
-            'A'..='Z' | 'a'..='z' | '_' | '$' => {
+            ch if is_identifier_start(ch) => {
                 if self.current_char == 'r' && self.look_behind(1) == '"' {
-                    self.read_string(true)
+                    self.read_string(true)?
                 } else {
                     self.read_identifier()
                 }
             }
-            '0'..='9' => self.read_number(),
-            '"' => self.read_string(false),
+            '0'..='9' => self.read_number()?,
+            '"' => self.read_string(false)?,
             '=' => {
                 self.move_index(1);
                 if self.current_char == '=' {
@@ -149,13 +192,24 @@ impl<'a> Parser<'a> {
             }
             '+' => {
                 self.move_index(1);
-                Token::create_op(
-                    self,
-                    TokenType::Plus,
-                    "+",
-                    14,
-                    (self.index - 1, self.index),
-                )
+                if self.current_char == '=' {
+                    self.move_index(1);
+                    Token::create_op(
+                        self,
+                        TokenType::PlusAssign,
+                        "+=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
+                } else {
+                    Token::create_op(
+                        self,
+                        TokenType::Plus,
+                        "+",
+                        14,
+                        (self.index - 1, self.index),
+                    )
+                }
             }
             '-' => {
                 let next_char = self.look_behind(1);
@@ -167,8 +221,17 @@ impl<'a> Parser<'a> {
                         "->",
                         (self.index - 2, self.index),
                     )
+                } else if next_char == '=' {
+                    self.move_index(2);
+                    Token::create_op(
+                        self,
+                        TokenType::SubAssign,
+                        "-=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
                 } else if self.allow_expr {
-                    self.read_number()
+                    self.read_number()?
                 } else {
                     self.move_index(1);
                     Token::create_op(
@@ -182,7 +245,16 @@ impl<'a> Parser<'a> {
             }
             '*' => {
                 self.move_index(1);
-                if self.allow_expr {
+                if self.current_char == '=' {
+                    self.move_index(1);
+                    Token::create_op(
+                        self,
+                        TokenType::MulAssign,
+                        "*=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
+                } else if self.allow_expr {
                     Token::new(self, TokenType::Star, "*", (self.index - 1, self.index))
                 } else {
                     Token::create_op(
@@ -196,23 +268,45 @@ impl<'a> Parser<'a> {
             }
             '/' => {
                 self.move_index(1);
-                Token::create_op(
-                    self,
-                    TokenType::Div,
-                    "/",
-                    15,
-                    (self.index - 1, self.index),
-                )
+                if self.current_char == '=' {
+                    self.move_index(1);
+                    Token::create_op(
+                        self,
+                        TokenType::DivAssign,
+                        "/=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
+                } else {
+                    Token::create_op(
+                        self,
+                        TokenType::Div,
+                        "/",
+                        15,
+                        (self.index - 1, self.index),
+                    )
+                }
             }
             '%' => {
                 self.move_index(1);
-                Token::create_op(
-                    self,
-                    TokenType::REM,
-                    "%",
-                    15,
-                    (self.index - 1, self.index),
-                )
+                if self.current_char == '=' {
+                    self.move_index(1);
+                    Token::create_op(
+                        self,
+                        TokenType::REMAssign,
+                        "%=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
+                } else {
+                    Token::create_op(
+                        self,
+                        TokenType::REM,
+                        "%",
+                        15,
+                        (self.index - 1, self.index),
+                    )
+                }
             }
             '<' => {
                 self.move_index(1);
@@ -267,6 +361,15 @@ impl<'a> Parser<'a> {
                         7,
                         (self.index - 2, self.index),
                     )
+                } else if self.current_char == '=' {
+                    self.move_index(1);
+                    Token::create_op(
+                        self,
+                        TokenType::BitAndAssign,
+                        "&=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
                 } else {
                     Token::create_op(
                         self,
@@ -288,6 +391,15 @@ impl<'a> Parser<'a> {
                         6,
                         (self.index - 2, self.index),
                     )
+                } else if self.current_char == '=' {
+                    self.move_index(1);
+                    Token::create_op(
+                        self,
+                        TokenType::BitOrAssign,
+                        "|=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
                 } else {
                     Token::create_op(
                         self,
@@ -331,13 +443,24 @@ impl<'a> Parser<'a> {
             }
             '^' => {
                 self.move_index(1);
-                Token::create_op(
-                    self,
-                    TokenType::BitXor,
-                    "^",
-                    9,
-                    (self.index - 1, self.index),
-                )
+                if self.current_char == '=' {
+                    self.move_index(1);
+                    Token::create_op(
+                        self,
+                        TokenType::BitXorAssign,
+                        "^=",
+                        1,
+                        (self.index - 2, self.index),
+                    )
+                } else {
+                    Token::create_op(
+                        self,
+                        TokenType::BitXor,
+                        "^",
+                        9,
+                        (self.index - 1, self.index),
+                    )
+                }
             }
             '(' => {
                 self.move_index(1);
@@ -368,8 +491,13 @@ impl<'a> Parser<'a> {
                 Token::new(self, TokenType::Comma, ",", (self.index - 1, self.index))
             }
             '.' => {
-                self.move_index(1);
-                Token::new(self, TokenType::Dot, ".", (self.index - 1, self.index))
+                if self.look_behind(1) == '.' {
+                    self.move_index(2);
+                    Token::new(self, TokenType::Range, "..", (self.index - 2, self.index))
+                } else {
+                    self.move_index(1);
+                    Token::new(self, TokenType::Dot, ".", (self.index - 1, self.index))
+                }
             }
             ';' => {
                 self.move_index(1);
@@ -383,23 +511,26 @@ impl<'a> Parser<'a> {
                 if self.index == self.chars.len() {
                     Token::new(self, TokenType::EOF, "EOF", (self.index, self.index))
                 } else {
-                    self.unexpected_pos(self.index, None);
+                    return self.unexpected_pos(self.index, None);
                 }
             }
         };
         self.current_token = token;
+
+        // 紧挨在当前 token 之前的文档注释
+        self.current_doc = if self.doc_comments.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.doc_comments).join("\n"))
+        };
+        Ok(())
     }
 
     // 读取一个标识符 token
     pub(crate) fn read_identifier(&mut self) -> Token {
         let start = self.index;
         let mut value = String::new();
-        while self.check_valid_index()
-            && match self.current_char {
-                'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '$' => true,
-                _ => false,
-            }
-        {
+        while self.check_valid_index() && is_identifier_char(self.current_char) {
             value.push(self.current_char);
             self.move_index(1);
         }
@@ -416,53 +547,203 @@ impl<'a> Parser<'a> {
     }
 
     // 读取一个数字 token
-    pub(crate) fn read_number(&mut self) -> Token {
+    pub(crate) fn read_number(&mut self) -> ParseResult<Token> {
         let start = self.index;
-        let mut value = String::new();
-        if self.current_char == '-' {
-            value.push('-');
+        let is_negative = self.current_char == '-';
+        if is_negative {
             self.move_index(1);
+            if !self.current_char.is_ascii_digit() {
+                return self
+                    .unexpected_err(self.index, "Expected a number literal after `-`");
+            }
         }
 
-        while self.check_valid_index()
-            && match self.current_char {
-                '0'..='9' | '.' => true,
-                _ => false,
+        // 十六进制、八进制、二进制，如: `0xff`、`0o17`、`0b1010`
+        let radix = match (self.current_char, self.look_behind(1)) {
+            ('0', 'x') => 16,
+            ('0', 'o') => 8,
+            ('0', 'b') => 2,
+            _ => 10,
+        };
+        let mut value = if radix == 10 {
+            self.read_decimal_digits()?
+        } else {
+            self.move_index(2);
+            let digits = self.read_radix_digits(radix)?;
+            match u64::from_str_radix(&digits, radix) {
+                Ok(v) => (v as f64).to_string(),
+                Err(_) => {
+                    return self.unexpected_err(start, "Number literal is too large")
+                }
+            }
+        };
+
+        // 数字后面不能紧跟多余的小数点或标识符字符，如: `1.2.3`、`12px`
+        if self.current_char == '.' && self.look_behind(1).is_ascii_digit() {
+            return self.unexpected_err(
+                self.index,
+                "Number literal can only have one decimal point",
+            );
+        }
+        if self.current_char.is_alphanumeric() || self.current_char == '_' {
+            return self.unexpected_err(
+                self.index,
+                &format!(
+                    "Invalid character `{}` in number literal",
+                    self.current_char
+                ),
+            );
+        }
+
+        if is_negative {
+            value.insert(0, '-');
+        }
+        Ok(Token::new(
+            self,
+            TokenType::Number,
+            &value,
+            (start, self.index),
+        ))
+    }
+
+    // 读取十进制数字（可以包含小数及指数部分），返回去除 `_` 分隔符后的文本
+    fn read_decimal_digits(&mut self) -> ParseResult<String> {
+        let mut value = self.read_digits(10)?;
+
+        // 小数点后必须是数字，否则可能是区间 `0..10` 或方法调用 `1.to_str()`
+        if self.current_char == '.' && self.look_behind(1).is_ascii_digit() {
+            value.push('.');
+            self.move_index(1);
+            value.push_str(&self.read_digits(10)?);
+        }
+
+        // 指数部分，如: `1e-9`、`2.5E3`
+        if self.current_char == 'e' || self.current_char == 'E' {
+            value.push('e');
+            self.move_index(1);
+            if self.current_char == '+' || self.current_char == '-' {
+                value.push(self.current_char);
+                self.move_index(1);
+            }
+            if !self.current_char.is_ascii_digit() {
+                return self
+                    .unexpected_err(self.index, "Missing digits in number exponent");
             }
+            value.push_str(&self.read_digits(10)?);
+        }
+        Ok(value)
+    }
+
+    // 读取带进制前缀的数字，返回去除 `_` 分隔符后的文本
+    fn read_radix_digits(&mut self, radix: u32) -> ParseResult<String> {
+        let (prefix, name) = match radix {
+            16 => ("0x", "hexadecimal"),
+            8 => ("0o", "octal"),
+            _ => ("0b", "binary"),
+        };
+        let digits = self.read_digits(radix)?;
+        // 超出进制范围的数字，如: `0b102`、`0o8`
+        if self.current_char.is_ascii_hexdigit() {
+            return self.unexpected_err(
+                self.index,
+                &format!("Invalid digit `{}` in {} literal", self.current_char, name),
+            );
+        }
+        if digits.is_empty() {
+            return self.unexpected_err(
+                self.index,
+                &format!("Missing digits after `{}`", prefix),
+            );
+        }
+        Ok(digits)
+    }
+
+    // 读取连续的数字及 `_` 分隔符，返回去除分隔符后的文本，分隔符只能位于两个数字之间
+    fn read_digits(&mut self, radix: u32) -> ParseResult<String> {
+        let mut digits = String::new();
+        let mut is_after_digit = false;
+        while self.check_valid_index()
+            && (self.current_char.is_digit(radix) || self.current_char == '_')
         {
-            value.push(self.current_char);
+            if self.current_char == '_' {
+                // 开头或连续的分隔符，如: `0x_1`、`1__0`
+                if !is_after_digit {
+                    return self.unexpected_separator(self.index);
+                }
+                is_after_digit = false;
+            } else {
+                digits.push(self.current_char);
+                is_after_digit = true;
+            }
             self.move_index(1);
         }
+        // 结尾的分隔符，如: `10_`
+        if !digits.is_empty() && !is_after_digit {
+            return self.unexpected_separator(self.index - 1);
+        }
+        Ok(digits)
+    }
 
-        Token::new(self, TokenType::Number, &value, (start, self.index))
+    // 返回一个分隔符位置错误
+    fn unexpected_separator<T>(&self, pos: usize) -> ParseResult<T> {
+        self.unexpected_err(pos, "Numeric separator `_` must be between digits")
     }
 
     // 读取一个字符串
-    pub(crate) fn read_string(&mut self, is_raw: bool) -> Token {
+    pub(crate) fn read_string(&mut self, is_raw: bool) -> ParseResult<Token> {
         let start = self.index;
-        let mut value = String::new();
         if is_raw {
             self.move_index(1);
         }
         self.move_index(1);
+        self.read_string_part(start, is_raw)
+    }
 
+    // 从当前位置继续读取字符串内容，遇到 `"` 时返回 String token，
+    // 遇到模板插值 `${` 时返回 Template token（原始字符串不支持插值）
+    pub(crate) fn read_string_part(
+        &mut self,
+        start: usize,
+        is_raw: bool,
+    ) -> ParseResult<Token> {
+        let mut value = String::new();
         while self.check_valid_index() {
             // 字符结束
             if self.current_char == '"' {
                 break;
             }
+            // 模板插值
+            if !is_raw && self.current_char == '$' && self.look_behind(1) == '{' {
+                self.move_index(2);
+                return Ok(Token::new(
+                    self,
+                    TokenType::Template,
+                    &value,
+                    (start, self.index),
+                ));
+            }
             // 换行
             if self.current_char == '\n' && !is_raw {
-                self.unexpected_err(
+                return self.unexpected_err(
                     self.index,
-                    "String literals cannot wrap. Tip: You can use the raw string `r\"...\"`"
+                    "String literals cannot wrap. Tip: You can use the raw string `r\"...\"`",
                 );
             }
             // escape
             if self.current_char == '\\' {
                 self.move_index(1);
-                if self.current_char != '"' {
-                    value.push('\\');
+                // 反斜杠位于文件结尾
+                if !self.check_valid_index() {
+                    break;
+                }
+                if is_raw {
+                    // 原始字符串只转义 `"`，其他的反斜杠原样保留
+                    if self.current_char != '"' {
+                        value.push('\\');
+                    }
+                } else {
+                    value.push(self.read_escape_char()?);
+                    continue;
                 }
             }
             value.push(self.current_char);
@@ -470,14 +751,75 @@ impl<'a> Parser<'a> {
         }
 
         if self.current_char != '"' {
-            self.unexpected_err(
-                self.index,
-                "The string literal is missing the terminator `\"`",
+            return self.unexpected_err(
+                start,
+                "Unterminated string, missing the terminator `\"`",
             );
         }
         self.move_index(1);
 
-        Token::new(self, TokenType::String, &value, (start, self.index))
+        Ok(Token::new(
+            self,
+            TokenType::String,
+            &value,
+            (start, self.index),
+        ))
+    }
+
+    // 读取转义字符（当前字符为 `\` 之后的字符），如: `\n`、`\u{4F60}`
+    pub(crate) fn read_escape_char(&mut self) -> ParseResult<char> {
+        let start = self.index;
+        let char = match self.current_char {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' | '"' | '$' => self.current_char,
+            'u' => {
+                self.move_index(1);
+                if self.current_char != '{' {
+                    return self
+                        .unexpected_err(self.index, "Expected `{` in unicode escape");
+                }
+                self.move_index(1);
+
+                let mut code = String::new();
+                while self.current_char.is_ascii_hexdigit() {
+                    code.push(self.current_char);
+                    self.move_index(1);
+                }
+                if self.current_char != '}' {
+                    return self
+                        .unexpected_err(self.index, "Expected `}` in unicode escape");
+                }
+                if code.is_empty() || code.len() > 6 {
+                    return self.unexpected_err(
+                        start,
+                        "Unicode escape must have 1 to 6 hex digits, such as: `\\u{4F60}`",
+                    );
+                }
+                match char::from_u32(u32::from_str_radix(&code, 16).unwrap()) {
+                    Some(v) => v,
+                    None => {
+                        return self.unexpected_err(
+                            start,
+                            &format!(
+                                "Invalid unicode character escape `\\u{{{}}}`",
+                                code
+                            ),
+                        )
+                    }
+                }
+            }
+            _ => {
+                return self.unexpected_err(
+                    start,
+                    &format!("Unknown character escape `\\{}`", self.current_char),
+                )
+            }
+        };
+        self.move_index(1);
+        Ok(char)
     }
 
     // 跳过空白字符
@@ -504,25 +846,92 @@ impl<'a> Parser<'a> {
     }
 
     // 跳过注释（可能是连续的多段注释）
-    pub(crate) fn skip_comment(&mut self) {
-        while self.current_char == '/' && self.look_behind(1) == '/' {
-            // let value = String::new();
-            // let comment = Token {
-            //     token_type: TokenType::Comment,
-            //     value,
-            //     precedence: -1,
-            //     start: self.index,
-            //     end: 0,
-            // };
-            self.move_index(2);
-            while self.check_valid_index() && self.current_char != '\n' {
-                // value.push(self.current_char);
-                self.move_index(1);
+    pub(crate) fn skip_comment(&mut self) -> ParseResult<()> {
+        while self.current_char == '/'
+            && (self.look_behind(1) == '/' || self.look_behind(1) == '*')
+        {
+            let start = self.index;
+            let comment = if self.look_behind(1) == '/' {
+                self.read_line_comment(start)
+            } else {
+                self.read_block_comment(start)?
+            };
+            if comment.kind == CommentKind::Doc {
+                self.doc_comments.push(comment.value.clone());
             }
-
-            // comment.end = self.index;
-            // self.current_comments.push(comment);
+            self.comments.push(comment);
             self.skip_space(true);
         }
+        Ok(())
+    }
+
+    // 读取单行注释，`///` 开头的为文档注释（`////` 仍为普通注释）
+    pub(crate) fn read_line_comment(&mut self, start: usize) -> Comment {
+        let is_doc = self.look_behind(2) == '/' && self.look_behind(3) != '/';
+        self.move_index(if is_doc { 3 } else { 2 });
+
+        let mut value = String::new();
+        while self.check_valid_index() && self.current_char != '\n' {
+            value.push(self.current_char);
+            self.move_index(1);
+        }
+
+        let (kind, value) = match is_doc {
+            // 去掉文档注释开头的一个空格，如: `/// Returns the sum`
+            true => (
+                CommentKind::Doc,
+                value
+                    .strip_prefix(' ')
+                    .unwrap_or(&value)
+                    .trim_end()
+                    .to_string(),
+            ),
+            false => (CommentKind::Line, value),
+        };
+        Comment {
+            kind,
+            value,
+            start,
+            end: self.index,
+        }
+    }
+
+    // 读取块注释，支持嵌套，如: `/* a /* b */ c */`
+    fn read_block_comment(&mut self, start: usize) -> ParseResult<Comment> {
+        self.move_index(2);
+        let (value, depth) = self.read_block_comment_body(1);
+        if depth > 0 {
+            return self.unexpected_err(start, "Unterminated block comment");
+        }
+        Ok(Comment {
+            kind: CommentKind::Block,
+            value,
+            start,
+            end: self.index,
+        })
+    }
+
+    // 读取块注释的内容，`depth` 为当前未闭合的层数，读取到文件末尾时返回剩余未闭合的层数
+    pub(crate) fn read_block_comment_body(&mut self, depth: usize) -> (String, usize) {
+        let mut depth = depth;
+        let mut value = String::new();
+        while self.index < self.chars.len() {
+            if self.current_char == '/' && self.look_behind(1) == '*' {
+                depth += 1;
+                value.push_str("/*");
+                self.move_index(2);
+            } else if self.current_char == '*' && self.look_behind(1) == '/' {
+                depth -= 1;
+                self.move_index(2);
+                if depth == 0 {
+                    break;
+                }
+                value.push_str("*/");
+            } else {
+                value.push(self.current_char);
+                self.move_index(1);
+            }
+        }
+        (value, depth)
     }
 }
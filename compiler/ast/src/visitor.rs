@@ -7,8 +7,17 @@ pub struct Visitor {
 
 impl Visitor {
     pub fn walk<T: FnMut(&Node, &mut Visitor)>(node: &Node, callback: &mut T) {
+        Visitor::walk_with_exit(node, callback, &mut |_, _| {});
+    }
+
+    // 遍历 node，`exit` 在 node 的子节点都访问完之后调用
+    pub fn walk_with_exit<T, U>(node: &Node, enter: &mut T, exit: &mut U)
+    where
+        T: FnMut(&Node, &mut Visitor),
+        U: FnMut(&Node, &mut Visitor),
+    {
         let mut visitor = Visitor { is_stop: false };
-        visitor.walk_node(node, callback);
+        visitor.walk_node(node, enter, exit);
     }
 
     // 停止遍历
@@ -17,15 +26,15 @@ impl Visitor {
     }
 
     // 递归访问 node
-    fn walk_node<T: FnMut(&Node, &mut Visitor)>(
-        &mut self,
-        node: &Node,
-        callback: &mut T,
-    ) {
+    fn walk_node<T, U>(&mut self, node: &Node, enter: &mut T, exit: &mut U)
+    where
+        T: FnMut(&Node, &mut Visitor),
+        U: FnMut(&Node, &mut Visitor),
+    {
         if self.is_stop {
             return;
         }
-        callback(node, self);
+        enter(node, self);
 
         match node {
             Node::Program { body, .. } => {
@@ -33,7 +42,7 @@ impl Visitor {
                     if self.is_stop {
                         break;
                     }
-                    self.walk_node(stat.deref(), callback);
+                    self.walk_node(stat.deref(), enter, exit);
                 }
             }
             Node::ImportDeclaration { specifiers, .. } => {
@@ -42,7 +51,7 @@ impl Visitor {
                         if self.is_stop {
                             break;
                         }
-                        self.walk_node(specifier.deref(), callback);
+                        self.walk_node(specifier.deref(), enter, exit);
                     }
                 }
             }
@@ -52,34 +61,34 @@ impl Visitor {
                 body,
                 ..
             } => {
-                self.walk_node(id.deref(), callback);
+                self.walk_node(id.deref(), enter, exit);
                 for arg in arguments.iter() {
                     if self.is_stop {
                         break;
                     }
-                    self.walk_node(arg.deref(), callback);
+                    self.walk_node(arg.deref(), enter, exit);
                 }
-                self.walk_node(body.deref(), callback);
+                self.walk_node(body.deref(), enter, exit);
             }
             Node::VariableDeclaration { id, init, .. } => {
-                self.walk_node(id.deref(), callback);
-                self.walk_node(init.deref(), callback);
+                self.walk_node(id.deref(), enter, exit);
+                self.walk_node(init.deref(), enter, exit);
             }
             Node::BlockStatement { body, .. } => {
                 for stat in body.iter() {
                     if self.is_stop {
                         break;
                     }
-                    self.walk_node(stat.deref(), callback);
+                    self.walk_node(stat.deref(), enter, exit);
                 }
             }
             Node::ReturnStatement { argument, .. } => {
                 if let Some(argument) = argument {
-                    self.walk_node(argument.deref(), callback);
+                    self.walk_node(argument.deref(), enter, exit);
                 }
             }
             Node::ExpressionStatement { expression, .. } => {
-                self.walk_node(expression.deref(), callback);
+                self.walk_node(expression.deref(), enter, exit);
             }
             Node::IfStatement {
                 condition,
@@ -87,44 +96,79 @@ impl Visitor {
                 alternate,
                 ..
             } => {
-                self.walk_node(condition.deref(), callback);
-                self.walk_node(consequent.deref(), callback);
+                self.walk_node(condition.deref(), enter, exit);
+                self.walk_node(consequent.deref(), enter, exit);
                 if let Some(alternate) = alternate {
-                    self.walk_node(alternate.deref(), callback);
+                    self.walk_node(alternate.deref(), enter, exit);
                 }
             }
             Node::LoopStatement { body, .. } => {
-                self.walk_node(body.deref(), callback);
+                self.walk_node(body.deref(), enter, exit);
+            }
+            Node::WhileStatement {
+                condition, body, ..
+            } => {
+                self.walk_node(condition.deref(), enter, exit);
+                self.walk_node(body.deref(), enter, exit);
+            }
+            Node::ForStatement {
+                id, iterable, body, ..
+            } => {
+                self.walk_node(id.deref(), enter, exit);
+                self.walk_node(iterable.deref(), enter, exit);
+                self.walk_node(body.deref(), enter, exit);
             }
             Node::BreakStatement { .. } => {}
             Node::ContinueStatement { .. } => {}
             Node::ImportSpecifier { .. } => {}
+            Node::CastExpression { expression, .. } => {
+                self.walk_node(expression.deref(), enter, exit);
+            }
+            Node::RangeExpression { start, end, .. } => {
+                self.walk_node(start.deref(), enter, exit);
+                self.walk_node(end.deref(), enter, exit);
+            }
             Node::CallExpression {
                 callee, arguments, ..
             } => {
-                self.walk_node(callee.deref(), callback);
+                self.walk_node(callee.deref(), enter, exit);
                 for arg in arguments.iter() {
                     if self.is_stop {
                         break;
                     }
-                    self.walk_node(arg.deref(), callback);
+                    self.walk_node(arg.deref(), enter, exit);
                 }
             }
+            Node::MemberExpression {
+                object, property, ..
+            } => {
+                self.walk_node(object.deref(), enter, exit);
+                self.walk_node(property.deref(), enter, exit);
+            }
             Node::BinaryExpression { left, right, .. } => {
-                self.walk_node(left.deref(), callback);
-                self.walk_node(right.deref(), callback);
+                self.walk_node(left.deref(), enter, exit);
+                self.walk_node(right.deref(), enter, exit);
             }
             Node::UnaryExpression { argument, .. } => {
-                self.walk_node(argument.deref(), callback);
+                self.walk_node(argument.deref(), enter, exit);
             }
             Node::AssignmentExpression { left, right, .. } => {
-                self.walk_node(left.deref(), callback);
-                self.walk_node(right.deref(), callback);
+                self.walk_node(left.deref(), enter, exit);
+                self.walk_node(right.deref(), enter, exit);
             }
             Node::Identifier { .. } => {}
             Node::NumberLiteral { .. } => {}
             Node::BooleanLiteral { .. } => {}
             Node::StringLiteral { .. } => {}
+            Node::TemplateLiteral { expressions, .. } => {
+                for expression in expressions.iter() {
+                    self.walk_node(expression.deref(), enter, exit);
+                }
+            }
+        }
+
+        if !self.is_stop {
+            exit(node, self);
         }
     }
 }
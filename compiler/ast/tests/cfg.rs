@@ -0,0 +1,114 @@
+use x_lang_ast::cfg::{build_cfg, to_dot, CfgEdgeKind, FunctionCfg};
+
+fn build(source: &str) -> FunctionCfg {
+    build_cfg(source).unwrap().remove(0)
+}
+
+// 节点的标签：入口、出口或节点中的第一条语句
+fn label(cfg: &FunctionCfg, index: usize) -> String {
+    match index {
+        0 => String::from("entry"),
+        1 => String::from("exit"),
+        _ => cfg.nodes[index].statements[0].clone(),
+    }
+}
+
+// 所有边的 (起点标签, 终点标签, 类型)
+fn edges(cfg: &FunctionCfg) -> Vec<(String, String, CfgEdgeKind)> {
+    cfg.edges
+        .iter()
+        .map(|edge| (label(cfg, edge.from), label(cfg, edge.to), edge.kind))
+        .collect()
+}
+
+fn edge(from: &str, to: &str, kind: CfgEdgeKind) -> (String, String, CfgEdgeKind) {
+    (from.to_string(), to.to_string(), kind)
+}
+
+#[test]
+fn labelled_jumps() {
+    // 带标签的 break / continue 跳到外层循环之后及外层循环头
+    let cfg = build(
+        "fn main() {\n    outer: loop {\n        inner: loop {\n            if a {\n                \
+         break outer;\n            }\n            continue outer;\n        }\n    }\n    \
+         print(1);\n}\n",
+    );
+    assert_eq!(
+        edges(&cfg),
+        vec![
+            edge("entry", "outer: loop", CfgEdgeKind::Normal),
+            edge("outer: loop", "inner: loop", CfgEdgeKind::Normal),
+            edge("inner: loop", "if a", CfgEdgeKind::Normal),
+            edge("if a", "break outer", CfgEdgeKind::True),
+            edge("break outer", "print(1)", CfgEdgeKind::Break),
+            edge("if a", "continue outer", CfgEdgeKind::False),
+            edge("continue outer", "outer: loop", CfgEdgeKind::Continue),
+            edge("print(1)", "exit", CfgEdgeKind::Normal),
+        ]
+    );
+    assert!(cfg.reachable().into_iter().all(|reachable| reachable));
+}
+
+#[test]
+fn loop_false_edges() {
+    // while / for 的循环头有跳出循环的 false 分支
+    let cfg = build(
+        "fn main() {\n    while a {\n        print(1);\n    }\n    for i in 0..3 {\n        \
+         print(i);\n    }\n}\n",
+    );
+    assert_eq!(
+        edges(&cfg),
+        vec![
+            edge("entry", "while a", CfgEdgeKind::Normal),
+            edge("while a", "print(1)", CfgEdgeKind::True),
+            edge("print(1)", "while a", CfgEdgeKind::Back),
+            edge("while a", "for i in 0..3", CfgEdgeKind::False),
+            edge("for i in 0..3", "print(i)", CfgEdgeKind::True),
+            edge("print(i)", "for i in 0..3", CfgEdgeKind::Back),
+            edge("for i in 0..3", "exit", CfgEdgeKind::False),
+        ]
+    );
+}
+
+#[test]
+fn empty_loop() {
+    // 空的 `loop {}` 指向自身，之后的语句及出口不可达
+    let cfg = build("fn main() {\n    loop {}\n    print(1);\n}\n");
+    assert_eq!(
+        edges(&cfg),
+        vec![
+            edge("entry", "loop", CfgEdgeKind::Normal),
+            edge("loop", "loop", CfgEdgeKind::Back),
+            edge("print(1)", "exit", CfgEdgeKind::Normal),
+        ]
+    );
+    assert_eq!(cfg.reachable(), vec![true, false, true, false]);
+}
+
+#[test]
+fn unreachable_tail_in_dot() {
+    let source = "fn main() {\n    print(1);\n    return;\n    print(2);\n}\n";
+    let cfg = build(source);
+    let reachable = cfg.reachable();
+    let tail = (0..cfg.nodes.len())
+        .find(|index| cfg.nodes[*index].statements == vec!["print(2)"])
+        .unwrap();
+    assert!(!reachable[tail]);
+
+    let dot = to_dot(&build_cfg(source).unwrap());
+    assert!(dot.contains(&format!(
+        "f0_{} [label=\"print(2)\\l\", shape=box, style=dashed];",
+        tail
+    )));
+    assert!(dot.contains("[label=\"print(1)\\lreturn\\l\", shape=box];"));
+    assert!(dot.contains("-> f0_1 [label=\"return\"];"));
+}
+
+#[test]
+fn dot_escaping() {
+    let dot =
+        to_dot(&build_cfg("fn main() {\n    print(\"a\\\"b\\\\c\");\n}\n").unwrap());
+    assert!(dot.contains("[label=\"print(\\\"a\\\\\\\"b\\\\\\\\c\\\")\\l\", shape=box];"));
+    assert!(dot.starts_with("digraph cfg {\n"));
+    assert!(dot.ends_with("    }\n}\n"));
+}
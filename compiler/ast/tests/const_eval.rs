@@ -0,0 +1,38 @@
+use x_lang_ast::const_eval::{eval_const_bool, eval_int_literal};
+use x_lang_ast::node::Node;
+use x_lang_ast::state::Parser;
+
+// 解析 `let a = <expr>;`，返回初始值表达式
+fn parse_expr(expr: &str) -> Box<Node> {
+    let program = Parser::try_parse(&format!("let a = {};", expr)).unwrap();
+    match program {
+        Node::Program { mut body, .. } => match *body.remove(0) {
+            Node::VariableDeclaration { init, .. } => init,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn int_literals() {
+    let eval = |expr: &str| eval_int_literal(&parse_expr(expr));
+    // 超过 2^53 的整数不会丢失精度
+    assert_eq!(eval("9007199254740993"), Some(9007199254740993));
+    assert_eq!(eval("9_223_372_036_854_775_807"), Some(i64::MAX as i128));
+    assert_eq!(eval("0xffff_ffff_ffff_ffff"), Some(u64::MAX as i128));
+    assert_eq!(eval("0o17 + 0b1010"), Some(25));
+    assert_eq!(eval("2 * 8 - 1"), Some(15));
+    assert_eq!(eval("1e3"), Some(1000));
+    assert_eq!(eval("1.5"), None);
+    assert_eq!(eval("18446744073709551616"), None);
+    assert_eq!(eval("1 / 0"), None);
+}
+
+#[test]
+fn const_bools() {
+    let eval = |expr: &str| eval_const_bool(&parse_expr(expr));
+    assert_eq!(eval("!false"), Some(true));
+    assert_eq!(eval("1 > 2 || true && false"), Some(false));
+    assert_eq!(eval("a == 1"), None);
+}
@@ -0,0 +1,118 @@
+use x_lang_ast::infer::InferredKinds;
+use x_lang_ast::shared::KindName;
+use x_lang_ast::state::Parser;
+use x_lang_ast::symbols::SymbolTable;
+
+fn infer(source: &str) -> InferredKinds {
+    let program = Parser::try_parse(source).unwrap();
+    InferredKinds::new(&program, &SymbolTable::new(&program))
+}
+
+// 变量名 -> 推断出的类型
+fn variable_kinds(kinds: &InferredKinds) -> Vec<(&str, KindName)> {
+    kinds
+        .variables
+        .iter()
+        .map(|variable| (variable.name.as_str(), variable.kind))
+        .collect()
+}
+
+#[test]
+fn variables() {
+    let kinds = infer(
+        "fn main() {\n    let a = 1;\n    let b: i64 = 2;\n    let c = b * 3;\n    \
+         let d = to_str(a);\n    let e = a > 1 && true;\n    for i in 0..10 {}\n}\n",
+    );
+    assert_eq!(
+        variable_kinds(&kinds),
+        vec![
+            ("a", KindName::Number),
+            ("c", KindName::Int64),
+            ("d", KindName::String),
+            ("e", KindName::Boolean),
+            ("i", KindName::Number),
+        ]
+    );
+}
+
+#[test]
+fn function_calls() {
+    let kinds = infer(
+        "fn add(a: i32, b: i32) -> i32 {\n    return a + b;\n}\n\
+         fn main() {\n    let sum = add(1, 2);\n    let len = \"abc\".len();\n}\n",
+    );
+    assert_eq!(
+        variable_kinds(&kinds),
+        vec![("sum", KindName::Int32), ("len", KindName::Number)]
+    );
+}
+
+#[test]
+fn expressions() {
+    let source = "fn main() {\n    let b: u8 = 2;\n    print(b + 1 as u8);\n}\n";
+    let kinds = infer(source);
+    let offset = source.find("b + 1").unwrap();
+    let expr = kinds.expression_at(offset + 2).unwrap();
+    assert_eq!(expr.kind, KindName::UInt8);
+    assert_eq!(expr.position, (offset, offset + 11));
+
+    let offset = source.find("1 as").unwrap();
+    assert_eq!(kinds.expression_at(offset).unwrap().kind, KindName::Number);
+}
+
+#[test]
+fn symbol_kind() {
+    let source = "fn main() {\n    let a = \"x\";\n    let b: num = 1;\n}\n";
+    let program = Parser::try_parse(source).unwrap();
+    let table = SymbolTable::new(&program);
+    let kinds = InferredKinds::new(&program, &table);
+    let symbol = |name: &str| {
+        table
+            .symbols
+            .iter()
+            .position(|symbol| symbol.name == name)
+            .unwrap()
+    };
+    assert_eq!(kinds.symbol_kind(symbol("a")), Some(KindName::String));
+    // 已标注类型的变量不需要推断
+    assert_eq!(kinds.symbol_kind(symbol("b")), None);
+}
+
+#[test]
+fn syntax_error() {
+    // 语法错误时返回错误，不会抛出异常
+    assert!(Parser::try_parse("fn main() {\n    let a = ;\n}\n").is_err());
+    assert!(Parser::try_parse("fn main() {\n    let s = \"abc").is_err());
+}
+
+#[test]
+fn mismatches() {
+    let source = "fn get() -> i64 {\n    return \"x\";\n}\n\
+                  fn main() {\n    let a: str = 1;\n    let b: u8 = 255;\n    let c: i32 = a;\n}\n";
+    let kinds = infer(source);
+    let mismatches: Vec<(usize, KindName, KindName)> = kinds
+        .mismatches
+        .iter()
+        .map(|mismatch| (mismatch.position.0, mismatch.expected, mismatch.found))
+        .collect();
+    assert_eq!(
+        mismatches,
+        vec![
+            (
+                source.find("\"x\"").unwrap(),
+                KindName::Int64,
+                KindName::String
+            ),
+            (
+                source.find("1;").unwrap(),
+                KindName::String,
+                KindName::Number
+            ),
+            (
+                source.find("a;").unwrap(),
+                KindName::Int32,
+                KindName::String
+            ),
+        ]
+    );
+}
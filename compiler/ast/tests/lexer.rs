@@ -0,0 +1,87 @@
+use x_lang_ast::lexer::{Lexer, LexerState};
+use x_lang_ast::token::TokenType;
+
+fn tokens(source: &str) -> Vec<(TokenType, String)> {
+    Lexer::new(source, false)
+        .map(|token| (token.token_type, token.value))
+        .collect()
+}
+
+#[test]
+fn trivia_tokens() {
+    assert_eq!(
+        tokens("let a = 1; // one"),
+        vec![
+            (TokenType::Keyword, "let".to_string()),
+            (TokenType::Identifier, "a".to_string()),
+            (TokenType::Assign, "=".to_string()),
+            (TokenType::Number, "1".to_string()),
+            (TokenType::Semi, ";".to_string()),
+        ]
+    );
+    let trivia: Vec<TokenType> = Lexer::new("a /* b */ /// c", true)
+        .map(|token| token.token_type)
+        .collect();
+    assert_eq!(
+        trivia,
+        vec![
+            TokenType::Identifier,
+            TokenType::Whitespace,
+            TokenType::Comment,
+            TokenType::Whitespace,
+            TokenType::DocComment,
+        ]
+    );
+}
+
+#[test]
+fn resume_unterminated_comment() {
+    let source = "a /* b\nc */ d";
+    let mut lexer = Lexer::new(&source[..6], false);
+    assert_eq!(lexer.next().unwrap().value, "a");
+    assert!(lexer.next().is_none());
+    let state = lexer.state();
+    assert_eq!(state.comment_depth, 1);
+
+    let rest: Vec<String> = Lexer::resume(source, 7, state, false)
+        .map(|token| token.value)
+        .collect();
+    assert_eq!(rest, vec!["d".to_string()]);
+    assert_eq!(LexerState::new().comment_depth, 0);
+}
+
+#[test]
+fn error_tokens() {
+    assert_eq!(
+        tokens("a = \"abc\nb = 1.2.3 @ c"),
+        vec![
+            (TokenType::Identifier, "a".to_string()),
+            (TokenType::Assign, "=".to_string()),
+            (TokenType::Error, "\"abc".to_string()),
+            (TokenType::Identifier, "b".to_string()),
+            (TokenType::Assign, "=".to_string()),
+            (TokenType::Error, "1.2.3".to_string()),
+            (TokenType::Error, "@".to_string()),
+            (TokenType::Identifier, "c".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn error_at_end() {
+    // 未闭合的字符串延续到文件结尾
+    let source = "let s = \"abc";
+    let last = Lexer::new(source, false).last().unwrap();
+    assert_eq!(last.token_type, TokenType::Error);
+    assert_eq!((last.start, last.end), (8, 12));
+
+    // 错误的数字字面量包含之后紧跟的字符
+    assert_eq!(
+        tokens("12px + 1"),
+        vec![
+            (TokenType::Error, "12px".to_string()),
+            (TokenType::Plus, "+".to_string()),
+            (TokenType::Number, "1".to_string()),
+        ]
+    );
+}
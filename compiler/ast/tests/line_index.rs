@@ -0,0 +1,44 @@
+use x_lang_ast::code_frame::get_source_position;
+use x_lang_ast::line_index::{LineCol, LineIndex};
+
+const SOURCE: &str = "ab\n你好😀x\n\nz";
+
+#[test]
+fn offsets() {
+    let line_index = LineIndex::new(SOURCE);
+    let chars = SOURCE.chars().collect::<Vec<char>>();
+    let (mut byte, mut utf16) = (0, 0);
+    for index in 0..=chars.len() {
+        assert_eq!(line_index.char_to_byte(index), byte);
+        assert_eq!(line_index.byte_to_char(byte), index);
+        assert_eq!(line_index.char_to_utf16(index), utf16);
+        assert_eq!(line_index.utf16_to_char(utf16), index);
+        assert_eq!(line_index.index_of(line_index.line_col(index)), Some(index));
+        assert_eq!(
+            line_index.index_of_utf16(line_index.line_col_utf16(index)),
+            Some(index)
+        );
+        if index < chars.len() {
+            byte += chars[index].len_utf8();
+            utf16 += chars[index].len_utf16();
+        }
+    }
+}
+
+#[test]
+fn line_col() {
+    let line_index = LineIndex::new(SOURCE);
+    assert_eq!(line_index.line_count(), 4);
+    // `x` 之前的 `😀` 在 UTF-16 中占两个单位
+    assert_eq!(line_index.line_col(6), LineCol { line: 1, column: 3 });
+    assert_eq!(line_index.line_col_utf16(6), LineCol { line: 1, column: 4 });
+    assert_eq!(line_index.index_of(LineCol { line: 0, column: 3 }), None);
+}
+
+#[test]
+fn source_position() {
+    let line_index = LineIndex::new(SOURCE);
+    assert_eq!(get_source_position(&line_index, 4), Some((2, 2)));
+    assert_eq!(get_source_position(&line_index, 9), Some((4, 1)));
+    assert_eq!(get_source_position(&line_index, 10), None);
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use x_lang_ast::lint::{fix, lint, LintConfig, LintLevel, RULES};
+
+// 检查源码，返回 (规则名称, 信息) 列表
+fn lint_messages(source: &str) -> Vec<(&'static str, String)> {
+    lint(source, &LintConfig::default())
+        .unwrap()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.rule, diagnostic.message))
+        .collect()
+}
+
+// 只启用一条规则时检查源码，返回 (信息, 位置) 列表
+fn rule_messages(rule: &str, source: &str) -> Vec<(String, (usize, usize))> {
+    let rules = RULES
+        .iter()
+        .map(|item| {
+            let level = match item.name == rule {
+                true => LintLevel::Warn,
+                false => LintLevel::Allow,
+            };
+            (item.name.to_string(), level)
+        })
+        .collect::<HashMap<_, _>>();
+    lint(source, &LintConfig { rules })
+        .unwrap()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.message, diagnostic.position))
+        .collect()
+}
+
+// `target` 在源码中的位置
+fn position_of(source: &str, target: &str) -> (usize, usize) {
+    let start = source.find(target).unwrap();
+    (start, start + target.chars().count())
+}
+
+#[test]
+fn unused_variable() {
+    let source = "fn main() {\n    let a = 1;\n    let b = 2;\n    let _c = 3;\n    print(b);\n}\n";
+    assert_eq!(
+        rule_messages("unused-variable", source),
+        vec![("Unused variable `a`".to_string(), (20, 21))]
+    );
+    let (fixed, count) = fix(source, &LintConfig::default()).unwrap();
+    assert_eq!(count, 1);
+    assert!(!fixed.contains("let a"));
+}
+
+#[test]
+fn unused_argument() {
+    let source = "fn add(a: num, b: num, _c: num) -> num {\n    return a;\n}\n\
+                  fn main() {\n    print(add(1, 2, 3));\n}\n";
+    assert_eq!(
+        rule_messages("unused-argument", source),
+        vec![("Unused argument `b`".to_string(), (15, 16))]
+    );
+}
+
+#[test]
+fn unused_function() {
+    // 导出的函数及 `main` 不需要被调用
+    let source = "fn helper() {}\npub fn api() {}\nfn main() {}\n";
+    assert_eq!(
+        rule_messages("unused-function", source),
+        vec![(
+            "Function `helper` is never called".to_string(),
+            position_of(source, "helper")
+        )]
+    );
+}
+
+#[test]
+fn unused_import() {
+    let source = "import lib.{say, talk as speak};\nfn main() {\n    say();\n}\n";
+    assert_eq!(
+        rule_messages("unused-import", source),
+        vec![(
+            "Unused import `speak`".to_string(),
+            position_of(source, "speak")
+        )]
+    );
+}
+
+#[test]
+fn unreachable_code() {
+    let source = "fn main() {\n    loop {\n        break;\n        print(1);\n        print(2);\n    }\n}\n";
+    let start = source.find("print(1)").unwrap();
+    let end = source.find("print(2)").unwrap() + "print(2)".len();
+    assert_eq!(
+        rule_messages("unreachable-code", source),
+        vec![("Unreachable code after `break`".to_string(), (start, end))]
+    );
+    let (fixed, _) = fix(source, &LintConfig::default()).unwrap();
+    assert_eq!(fixed, "fn main() {\n    loop {\n        break;\n    }\n}\n");
+}
+
+#[test]
+fn shadowed_variable() {
+    let source = "fn a(b: num) {\n    let b = 1;\n    let a = b;\n    print(a);\n}\n";
+    assert_eq!(
+        rule_messages("shadowed-variable", source),
+        vec![
+            (
+                "`b` shadows an argument with the same name".to_string(),
+                (23, 24)
+            ),
+            (
+                "`a` shadows a function with the same name".to_string(),
+                (38, 39)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn constant_condition() {
+    let source = "fn main() {\n    if !false {}\n    if 1 > 2 {}\n    if a() {}\n}\nfn a() -> bool {\n    return true;\n}\n";
+    assert_eq!(
+        rule_messages("constant-condition", source),
+        vec![
+            (
+                "Condition is always `true`".to_string(),
+                position_of(source, "!false")
+            ),
+            (
+                "Condition is always `false`".to_string(),
+                position_of(source, "1 > 2")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn empty_block() {
+    // 函数体及包含注释的块不会报告
+    let source = "fn main() {\n    loop {}\n    if true {\n        // TODO\n    }\n}\nfn noop() {}\n";
+    assert_eq!(
+        rule_messages("empty-block", source),
+        vec![("Empty block".to_string(), position_of(source, "{}"))]
+    );
+}
+
+#[test]
+fn unused_label() {
+    let source = "fn main() {\n    outer: loop {\n        inner: loop {\n            break outer;\n        }\n    }\n}\n";
+    let start = source.find("inner").unwrap();
+    assert_eq!(
+        rule_messages("unused-label", source),
+        vec![("Unused label `inner`".to_string(), (start, start + 5))]
+    );
+}
+
+#[test]
+fn redundant_parens() {
+    // `if` 条件及表达式内部的括号不会报告
+    let source = "fn main() {\n    var a = (1);\n    a = (a + 1) * 2;\n    if (a > 1) {\n        a = 0;\n    }\n    while (a < 3) {\n        a = a + 1;\n    }\n}\n";
+    assert_eq!(
+        rule_messages("redundant-parens", source),
+        vec![
+            (
+                "Redundant parentheses".to_string(),
+                position_of(source, "(1)")
+            ),
+            (
+                "Redundant parentheses".to_string(),
+                position_of(source, "(a < 3)")
+            ),
+        ]
+    );
+    let (fixed, count) = fix(source, &LintConfig::default()).unwrap();
+    assert_eq!(count, 2);
+    assert!(fixed.contains("var a = 1;") && fixed.contains("while a < 3 {"));
+}
+
+#[test]
+fn global_used_before_declaration() {
+    // 全局变量在编译函数体之前创建，可以在之前定义的函数中引用
+    let source = "pub fn show() {\n    print(limit);\n}\nconst limit = 10;\n";
+    assert_eq!(lint_messages(source), vec![]);
+    assert_eq!(
+        fix(source, &LintConfig::default()).unwrap(),
+        (source.to_string(), 0)
+    );
+}
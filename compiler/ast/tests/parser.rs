@@ -0,0 +1,79 @@
+use x_lang_ast::state::{ParseError, Parser};
+
+// 解析源码，返回错误信息及位置
+fn source_error(source: &str) -> (String, usize) {
+    match Parser::try_parse(source) {
+        Ok(_) => panic!("expected a syntax error: {}", source),
+        Err(ParseError { message, pos }) => (message, pos),
+    }
+}
+
+// 解析 `fn main() { <code> }`，返回错误信息及相对 `<code>` 的位置
+fn parse_error(code: &str) -> (String, usize) {
+    let prefix = "fn main() { ";
+    let (message, pos) = source_error(&format!("{}{} }}", prefix, code));
+    (message, pos - prefix.len())
+}
+
+#[test]
+fn unterminated_string() {
+    let message = "Unterminated string, missing the terminator `\"`".to_string();
+    assert_eq!(
+        source_error("fn main() { let s = \"abc\\"),
+        (message.clone(), 20)
+    );
+    assert_eq!(
+        source_error("fn main() { let s = r\"abc\\"),
+        (message.clone(), 20)
+    );
+    assert_eq!(parse_error("let s = \"abc"), (message, 8));
+}
+
+#[test]
+fn number_literals() {
+    let separator = "Numeric separator `_` must be between digits".to_string();
+    assert_eq!(parse_error("let a = 1__0;"), (separator.clone(), 10));
+    assert_eq!(parse_error("let a = 10_;"), (separator.clone(), 10));
+    assert_eq!(parse_error("let a = 1_0.5_;"), (separator.clone(), 13));
+    assert_eq!(parse_error("let a = 0x_ff;"), (separator, 10));
+    assert_eq!(
+        parse_error("let a = 0o9;"),
+        ("Invalid digit `9` in octal literal".to_string(), 10)
+    );
+    assert_eq!(
+        parse_error("let a = 0b;"),
+        ("Missing digits after `0b`".to_string(), 10)
+    );
+    assert!(
+        Parser::try_parse("fn main() { let a = 1_000_000 + 0xff_ff + 1_0.0_1; }").is_ok()
+    );
+}
+
+#[test]
+fn redundant_semicolons() {
+    let source = "fn main() {\n    let a = 1;;;\n    if a > 0 {};\n};\n";
+    let mut parser = Parser::new(source);
+    assert!(parser.parse_program().is_ok());
+    let warnings: Vec<(String, (usize, usize))> = parser
+        .take_diagnostics()
+        .items
+        .into_iter()
+        .map(|item| (item.message, item.position))
+        .collect();
+    let start = source.find(";;").unwrap() + 1;
+    let message = "Redundant semicolon".to_string();
+    assert_eq!(
+        warnings,
+        vec![
+            (message.clone(), (start, start + 2)),
+            (
+                message.clone(),
+                (
+                    source.find("};").unwrap() + 1,
+                    source.find("};").unwrap() + 2
+                )
+            ),
+            (message, (source.len() - 2, source.len() - 1)),
+        ]
+    );
+}
@@ -0,0 +1,174 @@
+use x_lang_ast::module::Module;
+use x_lang_ast::rename::{rename, rename_in_modules, RenameError};
+use x_lang_ast::text_edit::{apply_edits, TextEdit};
+
+// 重命名源码中第 `nth` 个（从 0 开始）`target` 处的符号，返回修改后的源码
+fn rename_nth(source: &str, target: &str, nth: usize, new_name: &str) -> String {
+    let offset = source.match_indices(target).nth(nth).unwrap().0;
+    let offset = source[..offset].chars().count();
+    let edits = rename(source, offset, new_name).unwrap();
+    apply_edits(source, &edits)
+}
+
+#[test]
+fn global_used_before_declaration() {
+    // 全局变量在编译函数体之前创建，可以在之前定义的函数中引用
+    let source = "fn show() {\n    print(limit);\n}\nconst limit = 10;\n";
+    let expected = "fn show() {\n    print(max);\n}\nconst max = 10;\n";
+    assert_eq!(rename_nth(source, "limit", 0, "max"), expected);
+    assert_eq!(rename_nth(source, "limit", 1, "max"), expected);
+}
+
+#[test]
+fn not_found() {
+    assert_eq!(rename("fn main() {}", 9, "a"), Err(RenameError::NotFound));
+}
+
+// 重命名第 `nth` 个 `target` 处的符号，返回错误
+fn rename_err(source: &str, target: &str, nth: usize, new_name: &str) -> RenameError {
+    let offset = source.match_indices(target).nth(nth).unwrap().0;
+    let offset = source[..offset].chars().count();
+    rename(source, offset, new_name).unwrap_err()
+}
+
+#[test]
+fn conflict() {
+    // 同一作用域内已存在同名的声明
+    let source = "fn main() {\n    let x = 1;\n    let y = 2;\n    print(x + y);\n}\n";
+    assert_eq!(
+        rename_err(source, "x", 0, "y"),
+        RenameError::Conflict {
+            name: "y".to_string(),
+            module: 0,
+            position: (35, 36),
+        }
+    );
+}
+
+#[test]
+fn shadowing() {
+    // 新名称会遮蔽外层的声明，导致引用指向改变
+    let source = "fn main() {\n    let x = 1;\n    {\n        let y = 2;\n        \
+                  print(x + y);\n    }\n}\n";
+    assert!(matches!(
+        rename_err(source, "y", 0, "x"),
+        RenameError::Conflict { .. }
+    ));
+    // 内层声明遮蔽外层同名声明时，只修改内层的引用
+    let source = "fn main() {\n    let x = 1;\n    {\n        let x = 2;\n        \
+                  print(x);\n    }\n    print(x);\n}\n";
+    assert_eq!(
+        rename_nth(source, "x", 1, "y"),
+        "fn main() {\n    let x = 1;\n    {\n        let y = 2;\n        \
+         print(y);\n    }\n    print(x);\n}\n"
+    );
+}
+
+#[test]
+fn labels() {
+    // 标签与其他符号的命名互不影响
+    let source = "fn main() {\n    let inner = 1;\n    outer: loop {\n        \
+                  loop {\n            break outer;\n        }\n    }\n}\n";
+    assert_eq!(
+        rename_nth(source, "outer", 1, "inner"),
+        "fn main() {\n    let inner = 1;\n    inner: loop {\n        \
+         loop {\n            break inner;\n        }\n    }\n}\n"
+    );
+    let source = "fn main() {\n    outer: loop {\n        inner: loop {\n            \
+                  break outer;\n        }\n    }\n}\n";
+    assert!(matches!(
+        rename_err(source, "outer", 0, "inner"),
+        RenameError::Conflict { .. }
+    ));
+}
+
+#[test]
+fn import_alias() {
+    let lib = "pub fn say() {}\n";
+    let main = "import lib.{say};\nfn main() {\n    say();\n}\n";
+    let alias = "import lib.{say as talk};\nfn main() {\n    talk();\n}\n";
+    let modules = [
+        Module {
+            path: "lib.x",
+            source: lib,
+        },
+        Module {
+            path: "main.x",
+            source: main,
+        },
+        Module {
+            path: "alias.x",
+            source: alias,
+        },
+    ];
+    let apply = |edits: Vec<(usize, TextEdit)>, module: usize| {
+        let edits = edits
+            .into_iter()
+            .filter(|(index, _)| *index == module)
+            .map(|(_, edit)| edit)
+            .collect::<Vec<TextEdit>>();
+        apply_edits(modules[module].source, &edits)
+    };
+
+    // 重命名声明时修改导入的名称，别名保持不变
+    let edits = rename_in_modules(&modules, 0, 8, "speak").unwrap();
+    assert_eq!(apply(edits.clone(), 0), "pub fn speak() {}\n");
+    assert_eq!(
+        apply(edits.clone(), 1),
+        "import lib.{speak};\nfn main() {\n    speak();\n}\n"
+    );
+    assert_eq!(
+        apply(edits, 2),
+        "import lib.{speak as talk};\nfn main() {\n    talk();\n}\n"
+    );
+
+    // 重命名别名只修改本模块
+    let edits = rename_in_modules(&modules, 2, 19, "speak").unwrap();
+    assert!(edits.iter().all(|(index, _)| *index == 2));
+    assert_eq!(
+        apply(edits, 2),
+        "import lib.{say as speak};\nfn main() {\n    speak();\n}\n"
+    );
+
+    // 新名称与导入的名称相同时去掉别名
+    let edits = rename_in_modules(&modules, 2, 19, "say").unwrap();
+    assert_eq!(
+        apply(edits, 2),
+        "import lib.{say};\nfn main() {\n    say();\n}\n"
+    );
+}
+
+#[test]
+fn unparsable_importer() {
+    let lib = "pub fn say() {}\n";
+    let main = "import lib.{say};\nfn main() {\n    say();\n}\n";
+    let broken = "import lib.{say};\nfn main() {\n    say(\n}\n";
+    let modules = [
+        Module {
+            path: "lib.x",
+            source: lib,
+        },
+        Module {
+            path: "main.x",
+            source: main,
+        },
+        Module {
+            path: "broken.x",
+            source: broken,
+        },
+    ];
+    // 无法解析的其他模块被跳过
+    let edits = rename_in_modules(&modules, 0, 8, "speak").unwrap();
+    assert_eq!(
+        edits
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<usize>>(),
+        vec![0, 1, 1]
+    );
+    // 重命名所在的模块需可以解析
+    assert!(matches!(
+        rename_in_modules(&modules, 2, 36, "speak"),
+        Err(RenameError::Parse { module: 2, .. })
+    ));
+}
@@ -0,0 +1,22 @@
+use x_lang_ast::state::Parser;
+use x_lang_ast::symbols::{SymbolKind, SymbolTable};
+
+#[test]
+fn global_used_before_declaration() {
+    // 全局变量在编译函数体之前创建，可以在之前定义的函数中引用
+    let source = "fn show() {\n    print(limit);\n}\nconst limit = 10;\n";
+    let table = SymbolTable::new(&Parser::new(source).parse());
+    let offset = source.find("limit").unwrap();
+
+    let symbol = table.definition_at(offset).unwrap();
+    assert_eq!(symbol.name, "limit");
+    assert_eq!(symbol.kind, SymbolKind::Variable);
+    assert!(table.visible_at(offset).iter().any(|s| s.name == "limit"));
+
+    let index = table.symbol_at(offset).unwrap();
+    let declaration = source.rfind("limit").unwrap();
+    assert_eq!(
+        table.references_of(index, true),
+        vec![(declaration, declaration + 5), (offset, offset + 5)]
+    );
+}
@@ -3,11 +3,12 @@ use crate::helper::never;
 use crate::scope::{FunctionScope, ScopeType};
 use crate::Compiler;
 use inkwell::types::BasicMetadataTypeEnum;
-use inkwell::values::{BasicValue, BasicValueEnum};
+use inkwell::values::{BasicMetadataValueEnum, BasicValue, BasicValueEnum};
 use inkwell::AddressSpace;
+use std::cell::RefCell;
 use std::ops::{Deref, Index};
 use x_lang_ast::node::Node;
-use x_lang_ast::shared::{Kind, KindName};
+use x_lang_ast::shared::{Kind, KindName, BUILD_IN_FNS, BUILD_IN_METHODS};
 
 pub extern "C" fn system_print_newline() {
     print!("\n");
@@ -41,6 +42,10 @@ pub extern "C" fn system_print_u64(value: u64) {
     print!("{}", value);
 }
 
+pub extern "C" fn system_print_i64(value: i64) {
+    print!("{}", value);
+}
+
 pub extern "C" fn system_print_bool(value: u8) {
     print!(
         "{}",
@@ -52,6 +57,114 @@ pub extern "C" fn system_print_bool(value: u8) {
     );
 }
 
+// 读取内存中的字符串（utf-16 编码），address 为字符串结构体 `{ size i32, array i16* }` 的地址
+unsafe fn read_system_str(address: i64) -> Vec<u16> {
+    let size = *(address as *const i32) as usize;
+    let chars = (address as *const u8).add(4) as *const u16;
+    std::slice::from_raw_parts(chars, size).to_vec()
+}
+
+thread_local! {
+    // 运行时内置函数分配的字符串。这是一个只增不减的内存池：运行期间不会释放任何临时字符串
+    // （如循环中反复调用 `s.substr(...)` 会持续占用内存），只在 `main` 函数返回后整体清空
+    static SYSTEM_STRS: RefCell<Vec<Vec<u32>>> = RefCell::new(vec![]);
+}
+
+// 在堆上分配一个字符串结构体（内存布局与 `build_string_value` 一致），返回内存地址
+fn alloc_system_str(chars: &[u16]) -> i64 {
+    // 4 字节的 size，加上以 0 结尾的字符数组；按 u32 分配以保证 size 的内存对齐
+    let bytes = 4 + (chars.len() + 1) * 2;
+    let mut memory = vec![0u32; (bytes + 3) / 4];
+    let ptr = memory.as_mut_ptr();
+    unsafe {
+        *(ptr as *mut i32) = chars.len() as i32;
+        let array_ptr = (ptr as *mut u8).add(4) as *mut u16;
+        std::ptr::copy_nonoverlapping(chars.as_ptr(), array_ptr, chars.len());
+    }
+    // 生成的代码没有所有权信息，无法确定字符串何时不再使用，由内存池持有直到程序运行结束
+    SYSTEM_STRS.with(|strs| strs.borrow_mut().push(memory));
+    ptr as i64
+}
+
+// 清空内存池，释放运行时分配的所有字符串，在 main 函数返回后调用（运行期间不会释放）
+pub fn free_system_strs() {
+    SYSTEM_STRS.with(|strs| strs.borrow_mut().clear());
+}
+
+// 将数字索引转换为字符数组的下标（负数视为 0，超出长度视为长度）
+fn to_str_index(index: f64, size: usize) -> usize {
+    if index <= 0.0 {
+        0
+    } else if index >= size as f64 {
+        size
+    } else {
+        index as usize
+    }
+}
+
+pub extern "C" fn system_str_len(address: i64) -> f64 {
+    let chars = unsafe { read_system_str(address) };
+    chars.len() as f64
+}
+
+pub extern "C" fn system_str_at(address: i64, index: f64) -> i64 {
+    let chars = unsafe { read_system_str(address) };
+    let index = to_str_index(index, chars.len());
+    match chars.get(index) {
+        Some(ch) => alloc_system_str(&[*ch]),
+        None => alloc_system_str(&[]),
+    }
+}
+
+pub extern "C" fn system_str_substr(address: i64, start: f64, end: f64) -> i64 {
+    let chars = unsafe { read_system_str(address) };
+    let start = to_str_index(start, chars.len());
+    let end = to_str_index(end, chars.len());
+    if start >= end {
+        alloc_system_str(&[])
+    } else {
+        alloc_system_str(&chars[start..end])
+    }
+}
+
+pub extern "C" fn system_num_floor(value: f64) -> f64 {
+    value.floor()
+}
+
+pub extern "C" fn system_num_to_str(value: f64) -> i64 {
+    let chars = value.to_string().encode_utf16().collect::<Vec<u16>>();
+    alloc_system_str(&chars)
+}
+
+pub extern "C" fn system_i64_to_str(value: i64) -> i64 {
+    let chars = value.to_string().encode_utf16().collect::<Vec<u16>>();
+    alloc_system_str(&chars)
+}
+
+pub extern "C" fn system_bool_to_str(value: u8) -> i64 {
+    let chars = match value {
+        1 => "true",
+        0 => "false",
+        _ => never(),
+    };
+    alloc_system_str(&chars.encode_utf16().collect::<Vec<u16>>())
+}
+
+pub extern "C" fn system_str_concat(left: i64, right: i64) -> i64 {
+    let mut chars = unsafe { read_system_str(left) };
+    chars.extend(unsafe { read_system_str(right) });
+    alloc_system_str(&chars)
+}
+
+// 解析失败时返回 NaN
+pub extern "C" fn system_str_parse_num(address: i64) -> f64 {
+    let chars = unsafe { read_system_str(address) };
+    String::from_utf16(&chars)
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .unwrap_or(f64::NAN)
+}
+
 impl<'ctx> Compiler<'ctx> {
     // built-in
     pub fn inject_build_in(&mut self) {
@@ -75,6 +188,12 @@ impl<'ctx> Compiler<'ctx> {
                 &[self.context.i64_type().into()],
                 system_print_u64 as usize,
             );
+            // print signed i64 number
+            self.bind_system_print_fn(
+                "i64",
+                &[self.context.i64_type().into()],
+                system_print_i64 as usize,
+            );
             // print bool
             self.bind_system_print_fn(
                 "bool",
@@ -84,6 +203,196 @@ impl<'ctx> Compiler<'ctx> {
             // print newline
             self.bind_system_print_fn("newline", &[], system_print_newline as usize);
         }
+
+        // 内置类型的方法
+        let method_addresses = [
+            system_str_len as usize,
+            system_str_at as usize,
+            system_str_substr as usize,
+            system_num_floor as usize,
+            system_num_to_str as usize,
+        ];
+        for (i, address) in method_addresses.iter().enumerate() {
+            let (kind_name, method, arg_kind_names, return_kind_name) =
+                &BUILD_IN_METHODS[i];
+            self.bind_system_method_fn(
+                kind_name,
+                method,
+                arg_kind_names,
+                return_kind_name,
+                *address,
+            );
+        }
+
+        // 内置类型转换函数的实现，整数统一扩展为 i64 后转换
+        let conversions = [
+            (
+                KindName::Int64,
+                "to_str",
+                KindName::String,
+                system_i64_to_str as usize,
+            ),
+            (
+                KindName::Boolean,
+                "to_str",
+                KindName::String,
+                system_bool_to_str as usize,
+            ),
+            (
+                KindName::String,
+                "concat",
+                KindName::String,
+                system_str_concat as usize,
+            ),
+            (
+                KindName::String,
+                "parse_num",
+                KindName::Number,
+                system_str_parse_num as usize,
+            ),
+        ];
+        for (kind_name, name, return_kind_name, address) in conversions.iter() {
+            // 字符串拼接额外接收一个字符串参数
+            let arg_kind_names: &[KindName] = match *name {
+                "concat" => &[KindName::String],
+                _ => &[],
+            };
+            self.bind_system_method_fn(
+                kind_name,
+                name,
+                arg_kind_names,
+                return_kind_name,
+                *address,
+            );
+        }
+    }
+
+    fn bind_system_method_fn(
+        &mut self,
+        kind_name: &KindName,
+        method: &str,
+        arg_kind_names: &[KindName],
+        return_kind_name: &KindName,
+        address: usize,
+    ) {
+        // 第一个参数为调用者自身
+        let mut arg_types = vec![self.build_arg_type(kind_name)];
+        for arg_kind_name in arg_kind_names.iter() {
+            arg_types.push(self.build_arg_type(arg_kind_name));
+        }
+
+        let name = format!("{}_{}", kind_name.to_string(), method);
+        let method_fn_value = self.build_fn_value(
+            &name,
+            &Kind::Some(*return_kind_name),
+            arg_types.as_slice(),
+        );
+        self.execution_engine
+            .add_global_mapping(&method_fn_value, address);
+        self.method_fns.insert(name, method_fn_value);
+    }
+
+    // 查找内置类型的方法，返回参数类型及返回类型
+    pub fn get_build_in_method(
+        &self,
+        kind_name: &KindName,
+        method: &str,
+        pos: usize,
+    ) -> (&'static [KindName], KindName) {
+        for (target_kind_name, name, arg_kind_names, return_kind_name) in
+            BUILD_IN_METHODS.iter()
+        {
+            if target_kind_name == kind_name && *name == method {
+                return (*arg_kind_names, *return_kind_name);
+            }
+        }
+        self.unexpected_err(
+            pos,
+            &format!(
+                "Method `{}` is not found on type `{}`",
+                method,
+                kind_name.to_string()
+            ),
+        )
+    }
+
+    // 查找内置函数（未被用户定义的函数覆盖），返回函数的返回类型
+    pub fn get_build_in_fn(&self, name: &str) -> Option<KindName> {
+        if self.scope.search_by_name(name, true).is_some() {
+            return None;
+        }
+        BUILD_IN_FNS
+            .iter()
+            .find(|(fn_name, ..)| *fn_name == name)
+            .map(|(_, return_kind_name)| *return_kind_name)
+    }
+
+    // 构建内置函数调用，如: `to_str(n)`、`parse_num(s)`
+    pub fn build_call_system_fn(
+        &self,
+        name: &str,
+        arguments: &Vec<Box<Node>>,
+        pos: usize,
+    ) -> BasicValueEnum<'ctx> {
+        if arguments.len() != 1 {
+            self.unexpected_err(
+                pos,
+                &format!("Expected 1 arguments, found {} arguments", arguments.len()),
+            );
+        }
+        let arg = arguments[0].deref();
+        let kind = self.infer_expression_kind(arg);
+        let kind_name = *kind.read_kind_name().unwrap();
+        let value = self.compile_expression(arg);
+
+        match name {
+            "to_str" => self.build_to_str_value(&kind_name, value),
+            _ => {
+                if kind_name != KindName::String {
+                    self.unexpected_err(
+                        arg.read_position().0,
+                        &format!("Expected `str`, found `{}`", kind.to_string()),
+                    );
+                }
+                let fn_value = self.method_fns.get("str_parse_num").unwrap();
+                self.build_call_fn(fn_value, &[value.into()], name)
+            }
+        }
+    }
+
+    // 将任意类型的值转换为字符串
+    pub fn build_to_str_value(
+        &self,
+        kind_name: &KindName,
+        value: BasicValueEnum<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        let (fn_name, value) = match kind_name {
+            KindName::String => return value,
+            KindName::Number => ("num_to_str", value),
+            KindName::Boolean => ("bool_to_str", value),
+            KindName::Int32 | KindName::Int64 | KindName::UInt8 => (
+                "i64_to_str",
+                self.build_cast_value(kind_name, &KindName::Int64, &value),
+            ),
+            KindName::Void => never(),
+        };
+        let fn_value = self.method_fns.get(fn_name).unwrap();
+        self.build_call_fn(fn_value, &[value.into()], "to_str")
+    }
+
+    // 构建内置类型的方法调用，`object` 为调用者的值
+    pub fn build_call_system_method(
+        &self,
+        kind_name: &KindName,
+        method: &str,
+        object: BasicValueEnum<'ctx>,
+        args: &[BasicMetadataValueEnum<'ctx>],
+    ) -> BasicValueEnum<'ctx> {
+        let name = format!("{}_{}", kind_name.to_string(), method);
+        let fn_value = self.method_fns.get(&name).unwrap();
+        let mut call_args = vec![BasicMetadataValueEnum::from(object)];
+        call_args.extend_from_slice(args);
+        self.build_call_fn(fn_value, call_args.as_slice(), &name)
     }
 
     fn bind_system_print_fn(
@@ -131,6 +440,31 @@ impl<'ctx> Compiler<'ctx> {
                         .unwrap();
                     self.build_call_fn(fn_value, &[arg_value.into()], "sys_print");
                 }
+                // 整数统一扩展为 64 位后打印
+                KindName::Int32 | KindName::Int64 | KindName::UInt8 => {
+                    let i64_type = self.context.i64_type();
+                    let value = arg_value.into_int_value();
+                    let (fn_name, value) = match infer_kind_name.is_signed() {
+                        true => (
+                            "i64",
+                            self.builder.build_int_s_extend_or_bit_cast(
+                                value,
+                                i64_type,
+                                "print_int",
+                            ),
+                        ),
+                        false => (
+                            "u64",
+                            self.builder.build_int_z_extend_or_bit_cast(
+                                value,
+                                i64_type,
+                                "print_int",
+                            ),
+                        ),
+                    };
+                    let fn_value = self.print_fns.get(fn_name).unwrap();
+                    self.build_call_fn(fn_value, &[value.into()], "sys_print");
+                }
                 KindName::String => {
                     let fn_value = self.print_fns.get("str").unwrap();
                     let address = arg_value.into_int_value();
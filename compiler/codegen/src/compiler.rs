@@ -1,5 +1,7 @@
-use crate::helper::{never, Terminator};
+use crate::build_in::free_system_strs;
+use crate::helper::{has_loop_break, has_return, never, Terminator};
 use crate::scope::{BlockScope, FunctionScope, Label, Labels, ScopeType};
+use crate::CompileOptions;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
 use inkwell::execution_engine::*;
@@ -10,6 +12,8 @@ use inkwell::values::*;
 use inkwell::OptimizationLevel;
 use std::collections::HashMap;
 use std::ops::Deref;
+use x_lang_ast::const_eval::{eval_const_bool, eval_int_literal};
+use x_lang_ast::diagnostics::{Diagnostics, Severity};
 use x_lang_ast::node::Node;
 use x_lang_ast::shared::{Kind, KindName};
 use x_lang_ast::state::Parser;
@@ -23,13 +27,18 @@ pub struct Compiler<'ctx> {
     pub labels: Labels<'ctx>,
     pub execution_engine: ExecutionEngine<'ctx>,
     pub print_fns: HashMap<&'static str, FunctionValue<'ctx>>,
+    pub method_fns: HashMap<String, FunctionValue<'ctx>>,
     pub current_fn: Option<FunctionValue<'ctx>>,
     pub current_return_kind_name: Option<KindName>,
+    pub global_init_fn: Option<FunctionValue<'ctx>>, // 全局变量初始化函数，在 main 函数开始时调用
     pub is_debug: bool,
+    pub diagnostics: Diagnostics, // 解析及编译过程中产生的警告
 }
 
 impl<'ctx> Compiler<'ctx> {
-    pub fn compile(source: &str, is_debug: bool) {
+    // 编译并运行，返回收集到的诊断信息，存在错误（被视为错误的警告）时不运行
+    pub fn compile(source: &str, options: CompileOptions) -> Diagnostics {
+        let is_debug = options.is_debug;
         let context = &Context::create();
         let module = context.create_module("main");
         let builder = context.create_builder();
@@ -49,8 +58,11 @@ impl<'ctx> Compiler<'ctx> {
             execution_engine,
             current_fn: None,
             current_return_kind_name: None,
+            global_init_fn: None,
             print_fns: HashMap::new(),
+            method_fns: HashMap::new(),
             is_debug,
+            diagnostics: Diagnostics::new(),
         };
 
         // TODO TEST
@@ -61,9 +73,23 @@ impl<'ctx> Compiler<'ctx> {
 
         // 开始编译
         let mut parser = Parser::new(source);
-        let node = parser.parse();
+        let node = parser.parse_program();
+        compiler.diagnostics.extend(parser.take_diagnostics());
+        let node = match node {
+            Ok(node) => node,
+            Err(err) => compiler.unexpected_err(err.pos, &err.message),
+        };
         compiler.compile_program(&node);
 
+        // 编译完成后打印警告，警告被视为错误时不再运行
+        if options.deny_warnings {
+            compiler.diagnostics.deny_warnings();
+        }
+        compiler.diagnostics.print(source);
+        if compiler.diagnostics.count(Severity::Error) > 0 {
+            return compiler.diagnostics;
+        }
+
         #[cfg(not(test))]
         if is_debug {
             compiler.module.print_to_file(".debug.ll");
@@ -79,6 +105,7 @@ impl<'ctx> Compiler<'ctx> {
                 main_fn.call();
             }
         }
+        free_system_strs();
 
         // Target::initialize_all(&InitializationConfig::default());
         // let target = Target::from_name("x86-64").unwrap();
@@ -97,6 +124,8 @@ impl<'ctx> Compiler<'ctx> {
         //     FileType::Object,
         //     Path::new("abc"),
         // );
+
+        compiler.diagnostics
     }
 
     pub fn compile_program(&mut self, node: &Node) {
@@ -104,6 +133,18 @@ impl<'ctx> Compiler<'ctx> {
 
         match node {
             Node::Program { body, .. } => {
+                // 根层级的常量按定义顺序生成全局变量，以便函数中可以引用
+                for stat in body.iter() {
+                    if let Node::VariableDeclaration {
+                        id, init, keyword, ..
+                    } = stat.deref()
+                    {
+                        if keyword == "const" {
+                            self.compile_global_constant(id.deref(), init.deref());
+                        }
+                    }
+                }
+
                 // 预编译
                 for stat in body.iter() {
                     if let Node::FunctionDeclaration {
@@ -117,13 +158,7 @@ impl<'ctx> Compiler<'ctx> {
                         let args = arguments.iter();
                         let args = args.map(|arg| {
                             let (_, kind, ..) = arg.deref().read_identifier();
-                            let kind_name = kind.read_kind_name().unwrap();
-                            (match kind_name {
-                                KindName::Number => self.build_number_type().into(),
-                                KindName::Boolean => self.build_bool_type().into(),
-                                KindName::String => self.build_store_ptr_type().into(),
-                                KindName::Void => never(),
-                            })
+                            self.build_arg_type(kind.read_kind_name().unwrap())
                         });
                         let args = args.collect();
                         self.pre_compile_function(
@@ -136,6 +171,9 @@ impl<'ctx> Compiler<'ctx> {
                     }
                 }
 
+                // 全局变量的初始化可能调用函数，需要在函数预编译之后
+                self.compile_global_variables(body);
+
                 // 逐条编译语句
                 for stat in body.iter() {
                     self.compile_statement(stat.deref());
@@ -148,14 +186,12 @@ impl<'ctx> Compiler<'ctx> {
     // 编译一条语句，返回语句中是否被终结了
     pub fn compile_statement(&mut self, node: &Node) -> Terminator {
         match node {
-            Node::ImportDeclaration {
-                source,
-                is_std_source,
-                specifiers,
-                ..
-            } => {
-                // TODO
-                panic!("TODO: No implement");
+            Node::ImportDeclaration { position, .. } => {
+                // 编译器尚未支持多模块，导入（包括 `pub` 修饰的全局变量）目前只在语言服务中可用
+                self.unexpected_err(
+                    position.0,
+                    "Importing modules is not supported by the compiler yet",
+                );
             }
             Node::FunctionDeclaration { id, body, .. } => {
                 let (name, ..) = id.deref().read_identifier();
@@ -167,8 +203,17 @@ impl<'ctx> Compiler<'ctx> {
                 );
                 Terminator::None
             }
-            Node::VariableDeclaration { id, init, .. } => {
-                self.compile_variable_statement(id.deref(), init.deref());
+            Node::VariableDeclaration {
+                id, init, keyword, ..
+            } => {
+                // 根层级的常量已在预编译时生成
+                if !self.scope.is_root() {
+                    self.compile_variable_statement(
+                        id.deref(),
+                        init.deref(),
+                        keyword == "var",
+                    );
+                }
                 Terminator::None
             }
             Node::BlockStatement { body, .. } => self.compile_block_statement(body, true),
@@ -176,8 +221,12 @@ impl<'ctx> Compiler<'ctx> {
                 self.compile_return_statement(argument, position.1);
                 Terminator::Return
             }
-            Node::ExpressionStatement { expression, .. } => {
+            Node::ExpressionStatement {
+                expression,
+                position,
+            } => {
                 self.compile_expression(expression.deref());
+                self.check_unused_value(expression.deref(), *position);
                 Terminator::None
             }
             Node::IfStatement {
@@ -190,14 +239,38 @@ impl<'ctx> Compiler<'ctx> {
                 label,
                 body,
                 position,
-            } => self.compile_loop_statement(label, body.deref(), position.0),
+            } => self.compile_loop_statement(label, body.deref(), *position),
+            Node::WhileStatement {
+                label,
+                condition,
+                body,
+                position,
+            } => self.compile_while_statement(
+                label,
+                condition.deref(),
+                body.deref(),
+                position.0,
+            ),
+            Node::ForStatement {
+                label,
+                id,
+                iterable,
+                body,
+                position,
+            } => self.compile_for_statement(
+                label,
+                id.deref(),
+                iterable.deref(),
+                body.deref(),
+                position.0,
+            ),
             Node::BreakStatement { label, .. } => {
                 self.compile_break_statement(label);
                 Terminator::Break
             }
-            Node::ContinueStatement { label, .. } => {
-                // TODO
-                panic!("TODO: No implement");
+            Node::ContinueStatement { label, position } => {
+                self.compile_continue_statement(label, position.0);
+                Terminator::Continue
             }
             _ => never(),
         }
@@ -238,6 +311,10 @@ impl<'ctx> Compiler<'ctx> {
                     let fv = arg.into_float_value();
                     fv.set_name(arg_name);
                 }
+                KindName::Int32 | KindName::Int64 | KindName::UInt8 => {
+                    let fv = arg.into_int_value();
+                    fv.set_name(arg_name);
+                }
                 KindName::Boolean => {
                     let fv = arg.into_int_value();
                     fv.set_name(arg_name);
@@ -278,7 +355,14 @@ impl<'ctx> Compiler<'ctx> {
 
         // 形参设置到作用域
         for (arg_name, kind, arg_value, pos) in arg_variables.iter() {
-            self.put_variable(arg_name, *kind, Some(*arg_value), true, *pos);
+            self.put_variable(arg_name, *kind, Some(*arg_value), true, true, *pos);
+        }
+
+        // 程序开始时先初始化全局变量
+        if name == "main" {
+            if let Some(init_fn) = self.global_init_fn {
+                self.builder.build_call(init_fn, &[], "");
+            }
         }
 
         // 更新当前正在解析的函数及返回值
@@ -333,9 +417,13 @@ impl<'ctx> Compiler<'ctx> {
         }
 
         let mut terminator = Terminator::None;
-        for stat in statements.iter() {
+        for (index, stat) in statements.iter().enumerate() {
             terminator = self.compile_statement(stat.deref());
             if terminator.is_terminated() {
+                // 之后的语句不可达，不再编译
+                if index + 1 < statements.len() {
+                    self.warn_unreachable(stat.deref(), &statements[index + 1..]);
+                }
                 break;
             }
         }
@@ -371,6 +459,12 @@ impl<'ctx> Compiler<'ctx> {
                 "If condition expression must be a boolean type",
             );
         }
+        if let Some(value) = eval_const_bool(condition) {
+            self.diagnostics.warn(
+                condition.read_position(),
+                &format!("Condition is always `{}`", value),
+            );
+        }
         let condition_value = self.compile_expression(condition).into_int_value();
 
         let fn_value = self.current_fn.unwrap();
@@ -386,7 +480,7 @@ impl<'ctx> Compiler<'ctx> {
         self.push_block_scope(then_block);
         let mut then_terminator =
             self.compile_block_statement(consequent.read_block_body(), false);
-        if !then_terminator.is_return() {
+        if !then_terminator.is_return() && !then_terminator.is_continue() {
             if then_terminator.is_break() {
                 if let Some(v) = &self.labels.last_break_label {
                     self.builder.build_unconditional_branch(v.after_block);
@@ -424,18 +518,26 @@ impl<'ctx> Compiler<'ctx> {
         }
         if !else_terminator.is_terminated() {
             self.builder.build_unconditional_branch(if_after_block);
+        } else if else_terminator.is_break() {
+            if let Some(v) = &self.labels.last_break_label {
+                self.builder.build_unconditional_branch(v.after_block);
+            }
         }
         self.pop_block_scope();
 
         // 继续构建 if 语句之后的逻辑
         self.builder.position_at_end(if_after_block);
 
-        // 如果 if / else 都return了，下方的代码直接不用执行了
+        // 如果 if / else 都终结了，下方的代码直接不用执行了
         if then_terminator.is_terminated() && else_terminator.is_terminated() {
-            unsafe {
-                if_after_block.delete().unwrap();
+            let terminator = then_terminator.merge(else_terminator);
+            // break 时外层循环还会在此处生成跳转，保留该块
+            if !terminator.is_break() {
+                unsafe {
+                    if_after_block.delete().unwrap();
+                }
             }
-            return then_terminator.merge(else_terminator);
+            return terminator;
         }
         Terminator::None
     }
@@ -444,16 +546,152 @@ impl<'ctx> Compiler<'ctx> {
         &mut self,
         label: &Option<String>,
         body: &Node,
+        position: (usize, usize),
+    ) -> Terminator {
+        // 有返回值的函数中，既不 break 也不 return 的循环永远不会结束
+        let return_kind_name = self.current_return_kind_name.unwrap();
+        if return_kind_name != KindName::Void
+            && !has_loop_break(body)
+            && !has_return(body)
+        {
+            self.diagnostics.warn(
+                position,
+                &format!(
+                    "Infinite loop without `break` or `return` in a function returning `{}`",
+                    return_kind_name.to_string()
+                ),
+            );
+        }
+        self.compile_loop(label, None, None, body, position.0)
+    }
+
+    pub fn compile_while_statement(
+        &mut self,
+        label: &Option<String>,
+        condition: &Node,
+        body: &Node,
+        pos: usize,
+    ) -> Terminator {
+        let infer_condition_kind = self.infer_expression_kind(condition);
+        if *infer_condition_kind.read_kind_name().unwrap() != KindName::Boolean {
+            self.unexpected_err(
+                condition.read_position().0,
+                "While condition expression must be a boolean type",
+            );
+        }
+
+        let condition: &dyn Fn(&Self) -> IntValue<'ctx> =
+            &|c: &Self| c.compile_expression(condition).into_int_value();
+        self.compile_loop(label, Some(condition), None, body, pos)
+    }
+
+    pub fn compile_for_statement(
+        &mut self,
+        label: &Option<String>,
+        id: &Node,
+        iterable: &Node,
+        body: &Node,
         pos: usize,
     ) -> Terminator {
-        let label_name = match label {
-            None => "",
-            Some(v) => v,
+        let (start, end) = match iterable {
+            Node::RangeExpression { start, end, .. } => (start.deref(), end.deref()),
+            _ => never(),
+        };
+        let (start_kind, end_kind) = self.infer_binary_kinds(start, end);
+        for (expr, kind) in [(start, start_kind), (end, end_kind)] {
+            if !kind.read_kind_name().unwrap().is_numeric() {
+                self.unexpected_err(
+                    expr.read_position().0,
+                    "Range bound expression must be a number type",
+                );
+            }
+        }
+        if start_kind != end_kind {
+            self.unexpected_err(
+                end.read_position().0,
+                &format!(
+                    "Expected `{}`, found `{}`",
+                    start_kind.to_string(),
+                    end_kind.to_string()
+                ),
+            );
+        }
+        let kind_name = *start_kind.read_kind_name().unwrap();
+
+        // 循环变量放在单独的块作用域中，循环结束后即销毁
+        let fn_value = self.current_fn.unwrap();
+        let for_block = self.context.append_basic_block(fn_value, "for");
+        self.builder.build_unconditional_branch(for_block);
+        self.push_block_scope(for_block);
+
+        // 区间只在进入循环前求值一次
+        let (name, _, id_pos) = id.read_identifier();
+        let start_value = self.compile_expression_as(start, &start_kind);
+        let end_value = self.compile_expression_as(end, &end_kind);
+        let end_ptr = self
+            .builder
+            .build_alloca(self.build_basic_type(&kind_name), "for_end");
+        self.builder.build_store(end_ptr, end_value);
+        self.put_variable(name, start_kind, Some(start_value), false, false, id_pos);
+        let ptr = *self.get_declare_var_ptr(name, id_pos);
+
+        let condition: &dyn Fn(&Self) -> IntValue<'ctx> = &|c: &Self| {
+            let value = c.builder.build_load(ptr, name);
+            let end = c.builder.build_load(end_ptr, "for_end");
+            c.compile_binary_operation(&kind_name, &value, &end, "<", id_pos)
+                .into_int_value()
         };
+        // 循环变量小于结束值时才会递增，整数类型不会溢出
+        let update: &dyn Fn(&Self) = &|c: &Self| {
+            let value = c.builder.build_load(ptr, name);
+            let value: BasicValueEnum = match kind_name {
+                KindName::Number => c
+                    .builder
+                    .build_float_add(
+                        value.into_float_value(),
+                        c.build_number_value(1.0),
+                        "for_next",
+                    )
+                    .into(),
+                _ => c
+                    .builder
+                    .build_int_add(
+                        value.into_int_value(),
+                        c.build_int_type(&kind_name).const_int(1, false),
+                        "for_next",
+                    )
+                    .into(),
+            };
+            c.builder.build_store(ptr, value);
+        };
+        let terminator =
+            self.compile_loop(label, Some(condition), Some(update), body, pos);
+
+        let loop_after_block = self.builder.get_insert_block().unwrap();
+        self.pop_block_scope();
+        self.builder.position_at_end(loop_after_block);
+        terminator
+    }
+
+    // 编译循环结构，loop / while / for 都基于此实现
+    // condition 为每轮开始前的附加条件，update 为每轮结束（包括 continue）后执行的逻辑
+    pub fn compile_loop(
+        &mut self,
+        label: &Option<String>,
+        condition: Option<&dyn Fn(&Self) -> IntValue<'ctx>>,
+        update: Option<&dyn Fn(&Self)>,
+        body: &Node,
+        pos: usize,
+    ) -> Terminator {
+        // 没有条件也没有跳出该循环的 break 时，循环永远不会正常结束
+        let is_endless = condition.is_none() && !has_loop_break(body);
         let fn_value = self.current_fn.unwrap();
         let loop_block = self.context.append_basic_block(fn_value, "loop");
         let loop_then_block = self.context.append_basic_block(fn_value, "loop_then");
+        let loop_update_block =
+            update.map(|_| self.context.append_basic_block(fn_value, "loop_update"));
         let loop_after_block = self.context.append_basic_block(fn_value, "loop_after");
+        let continue_block = loop_update_block.unwrap_or(loop_block);
 
         // 切换到循环块判断条件
         let condition_ptr = self
@@ -464,13 +702,15 @@ impl<'ctx> Compiler<'ctx> {
         self.builder.build_unconditional_branch(loop_block);
 
         self.builder.position_at_end(loop_block);
-        self.builder.build_conditional_branch(
-            self.builder
-                .build_load(condition_ptr, "do_loop")
-                .into_int_value(),
-            loop_then_block,
-            loop_after_block,
-        );
+        let mut do_loop = self
+            .builder
+            .build_load(condition_ptr, "do_loop")
+            .into_int_value();
+        if let Some(condition) = condition {
+            do_loop = self.builder.build_and(do_loop, condition(self), "do_loop");
+        }
+        self.builder
+            .build_conditional_branch(do_loop, loop_then_block, loop_after_block);
 
         // 块作用域入栈
         self.push_block_scope(loop_then_block);
@@ -479,15 +719,19 @@ impl<'ctx> Compiler<'ctx> {
                 self.unexpected_err(pos, &format!("The label `{}` is exists", v))
             }
         }
-        self.labels
-            .push(label.clone(), condition_ptr, loop_block, loop_after_block);
+        self.labels.push(
+            label.clone(),
+            condition_ptr,
+            continue_block,
+            loop_after_block,
+        );
 
         // 编译循环块
         let terminator = self.compile_block_statement(body.read_block_body(), false);
 
         if !terminator.is_terminated() {
             // 循环块结束后重新开始循环
-            self.builder.build_unconditional_branch(loop_block);
+            self.builder.build_unconditional_branch(continue_block);
         } else if terminator.is_break() {
             // 这里应该要跳转到 break label 的那个 after_block，不一定是当前的
             match &self.labels.last_break_label {
@@ -498,21 +742,28 @@ impl<'ctx> Compiler<'ctx> {
                     self.builder.build_unconditional_branch(loop_after_block);
                 }
             }
-        } else {
-            // 循环中间被 return
-            // TODO: 这里强制给 loop_after 块加了跳转到自身，只是为了避免报错，后续再解决这个问题，这里永远不会执行
+        }
+        if is_endless {
+            // 循环之后的块不可达
             self.builder.position_at_end(loop_after_block);
-            self.builder.build_unconditional_branch(loop_after_block);
+            self.builder.build_unreachable();
         }
         self.pop_block_scope();
         self.labels.pop();
 
+        // 每轮结束后的更新逻辑，执行完重新判断条件
+        if let (Some(update), Some(block)) = (update, loop_update_block) {
+            self.builder.position_at_end(block);
+            update(self);
+            self.builder.build_unconditional_branch(loop_block);
+        }
+
         // 继续编译循环块下面的代码
         self.builder.position_at_end(loop_after_block);
 
-        // 循环中只有 return 才透传结束者
-        if terminator.is_return() {
-            terminator
+        // 永远不会正常结束的循环视为 return，之后的语句不可达
+        if is_endless {
+            Terminator::Return
         } else {
             Terminator::None
         }
@@ -524,10 +775,14 @@ impl<'ctx> Compiler<'ctx> {
         let actual_kind_name = match argument {
             Some(v) => {
                 let node = v.deref();
+                let expected = Kind::Some(fn_kind_name);
                 self.builder
-                    .build_return(Some(&self.compile_expression(node)));
+                    .build_return(Some(&self.compile_expression_as(node, &expected)));
                 pos = node.read_position().0;
-                *self.infer_expression_kind(node).read_kind_name().unwrap()
+                *self
+                    .infer_expression_kind_as(node, &expected)
+                    .read_kind_name()
+                    .unwrap()
             }
             None => {
                 self.builder.build_return(None);
@@ -570,22 +825,191 @@ impl<'ctx> Compiler<'ctx> {
         };
     }
 
-    pub fn compile_variable_statement(&mut self, id: &Node, init: &Node) {
+    pub fn compile_continue_statement(&mut self, label: &Option<String>, pos: usize) {
+        let target = match label {
+            Some(label_name) => self.labels.get(label_name).cloned(),
+            None => self.labels.current().cloned(),
+        };
+        match target {
+            // 直接跳转到目标循环的下一轮，内层循环再次进入时会重置条件
+            Some(v) => {
+                self.builder.build_unconditional_branch(v.continue_block);
+            }
+            None => self.unexpected_err(
+                pos,
+                &format!("Label `{}` is not found", label.as_ref().unwrap()),
+            ),
+        }
+    }
+
+    pub fn compile_variable_statement(
+        &mut self,
+        id: &Node,
+        init: &Node,
+        is_mutable: bool,
+    ) {
         let (id, mut kind, pos) = id.read_identifier();
 
         // Note: 避免下面的临时变量生命周期不够长，临时借用变量
         let temp;
-        if !kind.is_exact() {
+        if kind.is_exact() {
+            let init_kind = self.infer_expression_kind_as(init, kind);
+            if *kind != init_kind {
+                self.unexpected_err(
+                    init.read_position().0,
+                    &format!(
+                        "Expected `{}`, found `{}`",
+                        kind.to_string(),
+                        init_kind.to_string()
+                    ),
+                );
+            }
+        } else {
             temp = self.infer_expression_kind(init);
             kind = &temp;
         }
-        let init_value = self.compile_expression(init);
+        let init_value = self.compile_expression_as(init, kind);
         self.put_variable(
             id,
             *kind,
             Some(init_value.as_basic_value_enum()),
             false,
+            is_mutable,
             pos,
         );
     }
+
+    // 编译根层级的常量，生成为全局常量（目前只支持字面量初始化）
+    pub fn compile_global_constant(&mut self, id: &Node, init: &Node) {
+        let (name, kind, pos) = id.read_identifier();
+        let init_kind = self.infer_global_kind(name, kind, init, pos);
+
+        let init_kind_name = init_kind.read_kind_name().unwrap();
+        let value = match init {
+            _ if init_kind_name.is_integer() => match eval_int_literal(init) {
+                Some(v) => self
+                    .build_int_literal_value(init_kind_name, v, init.read_position().0)
+                    .as_basic_value_enum(),
+                None => self.unexpected_err(
+                    init.read_position().0,
+                    "Constants in the root can only be initialized with literals",
+                ),
+            },
+            Node::NumberLiteral { value, .. } => {
+                self.build_number_value(*value).as_basic_value_enum()
+            }
+            Node::BooleanLiteral { value, .. } => {
+                self.build_bool_value(*value).as_basic_value_enum()
+            }
+            Node::StringLiteral { value, .. } => {
+                self.build_const_string_value(value).as_basic_value_enum()
+            }
+            _ => self.unexpected_err(
+                init.read_position().0,
+                "Constants in the root can only be initialized with literals",
+            ),
+        };
+        let global = self.module.add_global(value.get_type(), None, name);
+        global.set_initializer(&value);
+        global.set_constant(true);
+
+        let scope_type = ScopeType::Variable {
+            kind: init_kind,
+            ptr: global.as_pointer_value(),
+            is_mutable: false,
+        };
+        self.scope.globals.add(name, scope_type);
+    }
+
+    // 编译根层级的变量，生成为全局变量，并将初始化逻辑按定义顺序生成到初始化函数中
+    pub fn compile_global_variables(&mut self, body: &Vec<Box<Node>>) {
+        let mut declarations = vec![];
+        for stat in body.iter() {
+            if let Node::VariableDeclaration { keyword, .. } = stat.deref() {
+                if keyword != "const" {
+                    declarations.push(stat.deref());
+                }
+            }
+        }
+        if declarations.is_empty() {
+            return;
+        }
+
+        let fn_type = self.build_void_type().fn_type(&[], false);
+        let fn_value = self.module.add_function("global.init", fn_type, None);
+        let entry_block = self.context.append_basic_block(fn_value, "entry");
+        self.global_init_fn = Some(fn_value);
+        self.current_fn = Some(fn_value);
+        self.current_return_kind_name = Some(KindName::Void);
+        self.push_block_scope(entry_block);
+
+        for stat in declarations.into_iter() {
+            if let Node::VariableDeclaration {
+                id, init, keyword, ..
+            } = stat
+            {
+                let (name, kind, pos) = id.read_identifier();
+                let init_kind = self.infer_global_kind(name, kind, init.deref(), pos);
+                let zero_value: BasicValueEnum = match init_kind.read_kind_name().unwrap()
+                {
+                    KindName::Number => self.build_number_type().const_zero().into(),
+                    KindName::Int32 | KindName::Int64 | KindName::UInt8 => self
+                        .build_int_type(init_kind.read_kind_name().unwrap())
+                        .const_zero()
+                        .into(),
+                    KindName::Boolean => self.build_bool_type().const_zero().into(),
+                    KindName::String => self.build_store_ptr_type().const_zero().into(),
+                    KindName::Void => never(),
+                };
+                let global = self.module.add_global(zero_value.get_type(), None, name);
+                global.set_initializer(&zero_value);
+
+                let init_value = self.compile_expression_as(init.deref(), &init_kind);
+                self.builder
+                    .build_store(global.as_pointer_value(), init_value);
+
+                // 初始化表达式中不能引用自身，编译完成后再放入作用域
+                let scope_type = ScopeType::Variable {
+                    kind: init_kind,
+                    ptr: global.as_pointer_value(),
+                    is_mutable: keyword == "var",
+                };
+                self.scope.globals.add(name, scope_type);
+            }
+        }
+
+        self.builder.build_return(None);
+        self.pop_block_scope();
+        self.current_fn = None;
+        self.current_return_kind_name = None;
+    }
+
+    // 推断全局常量或变量的类型，并校验是否重复定义
+    fn infer_global_kind(
+        &self,
+        name: &str,
+        kind: &Kind,
+        init: &Node,
+        pos: usize,
+    ) -> Kind {
+        if self.scope.globals.has(name) {
+            self.unexpected_err(pos, &format!("Scope name `{}` is exist", name));
+        }
+
+        let init_kind = match kind.is_exact() {
+            true => self.infer_expression_kind_as(init, kind),
+            false => self.infer_expression_kind(init),
+        };
+        if kind.is_exact() && *kind != init_kind {
+            self.unexpected_err(
+                init.read_position().0,
+                &format!(
+                    "Expected `{}`, found `{}`",
+                    kind.to_string(),
+                    init_kind.to_string()
+                ),
+            );
+        }
+        init_kind
+    }
 }
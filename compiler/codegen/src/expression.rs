@@ -2,8 +2,9 @@ use crate::compiler::Compiler;
 use crate::helper::never;
 use crate::scope::FunctionScope;
 use inkwell::values::*;
-use inkwell::{AtomicRMWBinOp, FloatPredicate, IntPredicate};
+use inkwell::{FloatPredicate, IntPredicate};
 use std::ops::Deref;
+use x_lang_ast::const_eval::eval_int_literal;
 use x_lang_ast::node::Node;
 use x_lang_ast::shared::{Kind, KindName};
 
@@ -19,8 +20,8 @@ impl<'ctx> Compiler<'ctx> {
                 operator,
                 position,
             } => {
-                let left_kind = self.infer_expression_kind(left.deref());
-                let right_kind = self.infer_expression_kind(right.deref());
+                let (left_kind, right_kind) =
+                    self.infer_binary_kinds(left.deref(), right.deref());
                 if left_kind != right_kind {
                     self.unexpected_err(
                         position.0,
@@ -29,19 +30,11 @@ impl<'ctx> Compiler<'ctx> {
                 }
 
                 let kind_name = *left_kind.read_kind_name().unwrap();
-                let left = self.compile_expression(left.deref());
-                let right = self.compile_expression(right.deref());
-                if kind_name == KindName::Number {
-                    self.compile_num_binary_expression(
-                        &left, &right, operator, position.0,
-                    )
-                } else if kind_name == KindName::Boolean {
-                    self.compile_bool_binary_expression(
-                        &left, &right, operator, position.0,
-                    )
-                } else {
-                    self.unexpected_err(position.0, "Invalid binary expression");
-                }
+                let left = self.compile_expression_as(left.deref(), &left_kind);
+                let right = self.compile_expression_as(right.deref(), &right_kind);
+                self.compile_binary_operation(
+                    &kind_name, &left, &right, operator, position.0,
+                )
             }
             Node::UnaryExpression {
                 argument,
@@ -51,7 +44,9 @@ impl<'ctx> Compiler<'ctx> {
                 let kind = self.infer_expression_kind(argument.deref());
                 let kind_name = *kind.read_kind_name().unwrap();
                 let argument = self.compile_expression(argument.deref());
-                if kind_name == KindName::Number {
+                if kind_name.is_integer() {
+                    self.compile_int_unary_expression(&argument, operator, position.0)
+                } else if kind_name == KindName::Number {
                     self.compile_num_unary_expression(&argument, operator, position.0)
                 } else if kind_name == KindName::Boolean {
                     self.compile_bool_unary_expression(&argument, operator, position.0)
@@ -65,11 +60,51 @@ impl<'ctx> Compiler<'ctx> {
                 operator,
                 position,
             } => {
-                let (left_var, .., pos) = left.deref().read_identifier();
-                let ptr = self.get_declare_var_ptr(left_var, pos);
-                let right = self.compile_expression(right.deref());
-                self.builder.build_store(*ptr, right);
-                right
+                let left_kind = self.infer_expression_kind(left.deref());
+                let right_kind = self.infer_expression_kind_as(right.deref(), &left_kind);
+                if left_kind != right_kind {
+                    self.unexpected_err(
+                        right.read_position().0,
+                        &format!(
+                            "Expected `{}`, found `{}`",
+                            left_kind.to_string(),
+                            right_kind.to_string()
+                        ),
+                    );
+                }
+
+                let ptr = self.compile_assignment_target(left.deref());
+                let right = self.compile_expression_as(right.deref(), &left_kind);
+                let value = match operator.strip_suffix('=').unwrap() {
+                    "" => right,
+                    // 复合赋值，如: `a += 1` 等价于 `a = a + 1`，左值只读取一次
+                    binary_operator => {
+                        let left = self.builder.build_load(ptr, "assign_left");
+                        self.compile_binary_operation(
+                            left_kind.read_kind_name().unwrap(),
+                            &left,
+                            &right,
+                            binary_operator,
+                            position.0,
+                        )
+                    }
+                };
+                self.builder.build_store(ptr, value);
+                value
+            }
+            Node::CastExpression {
+                expression, kind, ..
+            } => {
+                let from_kind = self.infer_expression_kind(expression.deref());
+                let value = self.compile_expression(expression.deref());
+                self.build_cast_value(
+                    from_kind.read_kind_name().unwrap(),
+                    kind.read_kind_name().unwrap(),
+                    &value,
+                )
+            }
+            Node::MemberExpression { position, .. } => {
+                self.unexpected_err(position.0, "Property access is not supported")
             }
             Node::Identifier { name, position, .. } => {
                 let ptr = self.get_declare_var_ptr(name, position.0);
@@ -82,17 +117,109 @@ impl<'ctx> Compiler<'ctx> {
                 self.build_bool_value(*value).as_basic_value_enum()
             }
             Node::StringLiteral { value, .. } => {
-                self.build_string_value(value).as_basic_value_enum()
+                // 初始化函数返回后其栈内存即失效，全局变量引用的字符串需生成为全局常量
+                if self.current_fn.is_some() && self.current_fn == self.global_init_fn {
+                    self.build_const_string_value(value).as_basic_value_enum()
+                } else {
+                    self.build_string_value(value).as_basic_value_enum()
+                }
             }
+            Node::TemplateLiteral {
+                quasis,
+                expressions,
+                ..
+            } => self.compile_template_literal(quasis, expressions),
             _ => never(),
         }
     }
 
+    // 编译模板字符串，各插值表达式按类型转换为字符串后，依次拼接为堆上的新字符串
+    pub fn compile_template_literal(
+        &self,
+        quasis: &Vec<String>,
+        expressions: &Vec<Box<Node>>,
+    ) -> BasicValueEnum<'ctx> {
+        let concat_fn = self.method_fns.get("str_concat").unwrap();
+        let concat = |left: BasicValueEnum<'ctx>, right: BasicValueEnum<'ctx>| {
+            self.build_call_fn(concat_fn, &[left.into(), right.into()], "template")
+        };
+
+        let mut value = self.build_string_value(&quasis[0]).as_basic_value_enum();
+        for (i, expression) in expressions.iter().enumerate() {
+            let expression = expression.deref();
+            let kind = self.infer_expression_kind(expression);
+            let expression_value = self.compile_expression(expression);
+            let expression_value =
+                self.build_to_str_value(kind.read_kind_name().unwrap(), expression_value);
+            value = concat(value, expression_value);
+
+            let quasi = &quasis[i + 1];
+            if !quasi.is_empty() {
+                value =
+                    concat(value, self.build_string_value(quasi).as_basic_value_enum());
+            }
+        }
+        value
+    }
+
+    // 按照期望的类型编译表达式，整数字面量会直接生成为期望的整数类型
+    pub fn compile_expression_as(
+        &self,
+        node: &Node,
+        expected: &Kind,
+    ) -> BasicValueEnum<'ctx> {
+        if let Kind::Some(kind_name) = expected {
+            if kind_name.is_integer() {
+                if let Some(v) = eval_int_literal(node) {
+                    return self
+                        .build_int_literal_value(kind_name, v, node.read_position().0)
+                        .as_basic_value_enum();
+                }
+            }
+        }
+        self.compile_expression(node)
+    }
+
+    // 编译赋值目标，返回需要写入的地址
+    pub fn compile_assignment_target(&self, target: &Node) -> PointerValue<'ctx> {
+        match target {
+            Node::Identifier { name, position, .. } => {
+                if let Some(v) = self.scope.search_by_name(name, false) {
+                    if v.is_var() && !v.is_mutable() {
+                        self.unexpected_err(
+                            position.0,
+                            &format!("Cannot assign to immutable variable `{}`", name),
+                        );
+                    }
+                }
+                *self.get_declare_var_ptr(name, position.0)
+            }
+            Node::MemberExpression { position, .. } => {
+                self.unexpected_err(position.0, "Property access is not supported")
+            }
+            _ => {
+                self.unexpected_err(target.read_position().0, "Invalid assignment target")
+            }
+        }
+    }
+
     pub fn compile_call_expression(
         &self,
         callee: &Node,
         arguments: &Vec<Box<Node>>,
     ) -> BasicValueEnum<'ctx> {
+        // 方法调用，如: `s.len()`
+        if let Node::MemberExpression {
+            object, property, ..
+        } = callee
+        {
+            return self.compile_method_call_expression(
+                object.deref(),
+                property.deref(),
+                arguments,
+            );
+        }
+
         let (name, .., pos) = callee.read_identifier();
 
         // print 方法调用特殊处理
@@ -100,16 +227,61 @@ impl<'ctx> Compiler<'ctx> {
             return self.build_call_system_print(arguments);
         }
 
+        // 内置的类型转换函数
+        if self.get_build_in_fn(name).is_some() {
+            return self.build_call_system_fn(
+                name,
+                arguments,
+                pos + name.chars().count(),
+            );
+        }
+
         let FunctionScope {
             fn_value,
             arg_kind_names,
             ..
         } = self.get_declare_fn(name, pos);
 
+        let args = self.compile_call_arguments(
+            arguments,
+            arg_kind_names,
+            pos + name.chars().count(),
+        );
+        self.build_call_fn(fn_value, args.as_slice(), name)
+    }
+
+    // 编译内置类型的方法调用
+    pub fn compile_method_call_expression(
+        &self,
+        object: &Node,
+        property: &Node,
+        arguments: &Vec<Box<Node>>,
+    ) -> BasicValueEnum<'ctx> {
+        let (method, .., pos) = property.read_identifier();
+        let object_kind = self.infer_expression_kind(object);
+        let kind_name = *object_kind.read_kind_name().unwrap();
+        let (arg_kind_names, ..) = self.get_build_in_method(&kind_name, method, pos);
+
+        let object_value = self.compile_expression(object);
+        let args = self.compile_call_arguments(
+            arguments,
+            arg_kind_names,
+            pos + method.chars().count(),
+        );
+        self.build_call_system_method(&kind_name, method, object_value, args.as_slice())
+    }
+
+    // 编译并校验调用参数，`pos` 为参数个数不匹配时的提示位置
+    pub fn compile_call_arguments(
+        &self,
+        arguments: &Vec<Box<Node>>,
+        arg_kind_names: &[KindName],
+        pos: usize,
+    ) -> Vec<BasicMetadataValueEnum<'ctx>> {
         // 校验参数
         if arg_kind_names.len() != arguments.len() {
             self.unexpected_err(
-                pos + name.len(),
+                pos,
                 &format!(
                     "Expected {} arguments, found {} arguments",
                     arg_kind_names.len(),
@@ -122,8 +294,9 @@ impl<'ctx> Compiler<'ctx> {
         let mut i = 0;
         let args = args.map(|arg| {
             let arg = arg.deref();
-            let infer_arg_kind = self.infer_expression_kind(arg);
-            let value = self.compile_expression(arg);
+            let expected = Kind::Some(arg_kind_names[i]);
+            let infer_arg_kind = self.infer_expression_kind_as(arg, &expected);
+            let value = self.compile_expression_as(arg, &expected);
 
             // 校验参数
             if !match infer_arg_kind {
@@ -142,10 +315,30 @@ impl<'ctx> Compiler<'ctx> {
             i += 1;
             BasicMetadataValueEnum::from(value)
         });
-        let args = args.collect::<Vec<BasicMetadataValueEnum>>();
-        let args = args.as_slice();
+        args.collect::<Vec<BasicMetadataValueEnum>>()
+    }
 
-        self.build_call_fn(fn_value, args, name)
+    // 根据两端的类型编译二元运算符
+    pub fn compile_binary_operation(
+        &self,
+        kind_name: &KindName,
+        left: &BasicValueEnum<'ctx>,
+        right: &BasicValueEnum<'ctx>,
+        operator: &str,
+        pos: usize,
+    ) -> BasicValueEnum<'ctx> {
+        match kind_name {
+            KindName::Int32 | KindName::Int64 | KindName::UInt8 => {
+                self.compile_int_binary_expression(kind_name, left, right, operator, pos)
+            }
+            KindName::Number => {
+                self.compile_num_binary_expression(left, right, operator, pos)
+            }
+            KindName::Boolean => {
+                self.compile_bool_binary_expression(left, right, operator, pos)
+            }
+            _ => self.unexpected_err(pos, "Invalid binary expression"),
+        }
     }
 
     // 编译两端为数字类型的二元运算符
@@ -177,23 +370,28 @@ impl<'ctx> Compiler<'ctx> {
                 .builder
                 .build_float_rem(left.into_float_value(), right.into_float_value(), "REM")
                 .as_basic_value_enum(),
-            /*b"&" => self
-                .builder
-                .build_atomicrmw(
-                    AtomicRMWBinOp::And,
-                    left.into_int_value(),
-                    right.into_int_value(),
-                    "BIT_AND",
-                )
-                .as_basic_value_enum(),
-            b"|" => self
-                .builder
-                .build_or(left.into_int_value(), right.into_int_value(), "BIT_OR")
-                .as_basic_value_enum(),
-            b"^" => self
-                .builder
-                .build_xor(left.into_int_value(), right.into_int_value(), "BIT_XOR")
-                .as_basic_value_enum(),*/
+            b"&" | b"|" | b"^" => {
+                // 位运算先将数字转换为整数，运算后再转换回数字
+                let int_type = self.context.i64_type();
+                let left = self.builder.build_float_to_signed_int(
+                    left.into_float_value(),
+                    int_type,
+                    "LEFT_INT",
+                );
+                let right = self.builder.build_float_to_signed_int(
+                    right.into_float_value(),
+                    int_type,
+                    "RIGHT_INT",
+                );
+                let value = match operator.as_bytes() {
+                    b"&" => self.builder.build_and(left, right, "BIT_AND"),
+                    b"|" => self.builder.build_or(left, right, "BIT_OR"),
+                    _ => self.builder.build_xor(left, right, "BIT_XOR"),
+                };
+                self.builder
+                    .build_signed_int_to_float(value, self.build_number_type(), "BIT")
+                    .as_basic_value_enum()
+            }
             b"<" => self
                 .builder
                 .build_float_compare(
@@ -255,6 +453,250 @@ impl<'ctx> Compiler<'ctx> {
         }
     }
 
+    // 编译两端为整数类型的二元运算符，除法向零取整，除数为 0 时中止程序
+    pub fn compile_int_binary_expression(
+        &self,
+        kind_name: &KindName,
+        left: &BasicValueEnum<'ctx>,
+        right: &BasicValueEnum<'ctx>,
+        operator: &str,
+        pos: usize,
+    ) -> BasicValueEnum<'ctx> {
+        let left = left.into_int_value();
+        let right = right.into_int_value();
+        let is_signed = kind_name.is_signed();
+        let predicate = |signed: IntPredicate, unsigned: IntPredicate| match is_signed {
+            true => signed,
+            false => unsigned,
+        };
+        let value = match operator.as_bytes() {
+            b"+" | b"-" | b"*" => {
+                self.build_int_arithmetic(kind_name, left, right, operator)
+            }
+            b"/" | b"%" => {
+                self.build_int_division_check(kind_name, left, right);
+                match (operator, is_signed) {
+                    ("/", true) => self.builder.build_int_signed_div(left, right, "DIV"),
+                    ("/", false) => {
+                        self.builder.build_int_unsigned_div(left, right, "DIV")
+                    }
+                    (_, true) => self.builder.build_int_signed_rem(left, right, "REM"),
+                    (_, false) => self.builder.build_int_unsigned_rem(left, right, "REM"),
+                }
+            }
+            b"&" => self.builder.build_and(left, right, "BIT_AND"),
+            b"|" => self.builder.build_or(left, right, "BIT_OR"),
+            b"^" => self.builder.build_xor(left, right, "BIT_XOR"),
+            b"<" => self.builder.build_int_compare(
+                predicate(IntPredicate::SLT, IntPredicate::ULT),
+                left,
+                right,
+                "LT",
+            ),
+            b"<=" => self.builder.build_int_compare(
+                predicate(IntPredicate::SLE, IntPredicate::ULE),
+                left,
+                right,
+                "LE",
+            ),
+            b">" => self.builder.build_int_compare(
+                predicate(IntPredicate::SGT, IntPredicate::UGT),
+                left,
+                right,
+                "GT",
+            ),
+            b">=" => self.builder.build_int_compare(
+                predicate(IntPredicate::SGE, IntPredicate::UGE),
+                left,
+                right,
+                "GE",
+            ),
+            b"==" => self
+                .builder
+                .build_int_compare(IntPredicate::EQ, left, right, "EQ"),
+            b"!=" => self
+                .builder
+                .build_int_compare(IntPredicate::NE, left, right, "NE"),
+            _ => self.unexpected_err(
+                pos,
+                &format!("Invalid binary operator between integers: `{}`", operator),
+            ),
+        };
+        value.as_basic_value_enum()
+    }
+
+    // 编译整数的加减乘运算，调试模式下溢出时中止程序，否则按补码回绕
+    fn build_int_arithmetic(
+        &self,
+        kind_name: &KindName,
+        left: IntValue<'ctx>,
+        right: IntValue<'ctx>,
+        operator: &str,
+    ) -> IntValue<'ctx> {
+        let op_name = match operator {
+            "+" => "add",
+            "-" => "sub",
+            _ => "mul",
+        };
+        if !self.is_debug {
+            return match op_name {
+                "add" => self.builder.build_int_add(left, right, "ADD"),
+                "sub" => self.builder.build_int_sub(left, right, "SUB"),
+                _ => self.builder.build_int_mul(left, right, "MUL"),
+            };
+        }
+
+        // 使用 `llvm.{s|u}{add|sub|mul}.with.overflow.iN` 同时得到结果及是否溢出
+        let int_type = self.build_int_type(kind_name);
+        let name = format!(
+            "llvm.{}{}.with.overflow.i{}",
+            if kind_name.is_signed() { "s" } else { "u" },
+            op_name,
+            kind_name.int_bits()
+        );
+        let ret_type = self
+            .context
+            .struct_type(&[int_type.into(), self.build_bool_type().into()], false);
+        let fn_value = self.get_intrinsic_fn(
+            &name,
+            ret_type.fn_type(&[int_type.into(), int_type.into()], false),
+        );
+        let result = self
+            .build_call_fn(&fn_value, &[left.into(), right.into()], op_name)
+            .into_struct_value();
+        let value = self
+            .builder
+            .build_extract_value(result, 0, "value")
+            .unwrap()
+            .into_int_value();
+        let overflow = self
+            .builder
+            .build_extract_value(result, 1, "overflow")
+            .unwrap()
+            .into_int_value();
+        self.build_trap_if(overflow);
+        value
+    }
+
+    // 整数除法的除数为 0，或有符号整数的最小值除以 -1 时中止程序
+    fn build_int_division_check(
+        &self,
+        kind_name: &KindName,
+        left: IntValue<'ctx>,
+        right: IntValue<'ctx>,
+    ) {
+        let int_type = self.build_int_type(kind_name);
+        let mut invalid = self.builder.build_int_compare(
+            IntPredicate::EQ,
+            right,
+            int_type.const_zero(),
+            "div_zero",
+        );
+        if kind_name.is_signed() {
+            let (min, _) = kind_name.int_range();
+            let is_min = self.builder.build_int_compare(
+                IntPredicate::EQ,
+                left,
+                int_type.const_int(min as i64 as u64, true),
+                "div_min",
+            );
+            let is_neg_one = self.builder.build_int_compare(
+                IntPredicate::EQ,
+                right,
+                int_type.const_all_ones(),
+                "div_neg_one",
+            );
+            let overflow = self.builder.build_and(is_min, is_neg_one, "div_overflow");
+            invalid = self.builder.build_or(invalid, overflow, "div_invalid");
+        }
+        self.build_trap_if(invalid);
+    }
+
+    // 编译数字及布尔类型之间的 `as` 转换，数字转整数时向零取整并限制在整数的取值范围内
+    pub fn build_cast_value(
+        &self,
+        from: &KindName,
+        to: &KindName,
+        value: &BasicValueEnum<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        if from == to {
+            return *value;
+        }
+
+        // 转换为布尔类型时，非 0 即为 true（NaN 为 false）
+        if *to == KindName::Boolean {
+            let value = match from {
+                KindName::Number => self.builder.build_float_compare(
+                    FloatPredicate::ONE,
+                    value.into_float_value(),
+                    self.build_number_type().const_zero(),
+                    "CAST",
+                ),
+                _ => self.builder.build_int_compare(
+                    IntPredicate::NE,
+                    value.into_int_value(),
+                    value.into_int_value().get_type().const_zero(),
+                    "CAST",
+                ),
+            };
+            return value.as_basic_value_enum();
+        }
+
+        // 布尔类型的 true 转换为 1，false 转换为 0
+        if *from == KindName::Boolean {
+            let value = value.into_int_value();
+            return match to {
+                KindName::Number => self
+                    .builder
+                    .build_unsigned_int_to_float(value, self.build_number_type(), "CAST")
+                    .as_basic_value_enum(),
+                _ => self
+                    .builder
+                    .build_int_z_extend(value, self.build_int_type(to), "CAST")
+                    .as_basic_value_enum(),
+            };
+        }
+
+        if *from == KindName::Number {
+            // `llvm.fpto{s|u}i.sat` 在超出范围时取边界值，NaN 转换为 0
+            let int_type = self.build_int_type(to);
+            let name = format!(
+                "llvm.fpto{}i.sat.i{}.f64",
+                if to.is_signed() { "s" } else { "u" },
+                to.int_bits()
+            );
+            let fn_value = self.get_intrinsic_fn(
+                &name,
+                int_type.fn_type(&[self.build_number_type().into()], false),
+            );
+            return self.build_call_fn(&fn_value, &[(*value).into()], "CAST");
+        }
+
+        let value = value.into_int_value();
+        if *to == KindName::Number {
+            let float_type = self.build_number_type();
+            return match from.is_signed() {
+                true => self
+                    .builder
+                    .build_signed_int_to_float(value, float_type, "CAST"),
+                false => self
+                    .builder
+                    .build_unsigned_int_to_float(value, float_type, "CAST"),
+            }
+            .as_basic_value_enum();
+        }
+
+        let int_type = self.build_int_type(to);
+        let value = if to.int_bits() < from.int_bits() {
+            self.builder.build_int_truncate(value, int_type, "CAST")
+        } else if from.is_signed() {
+            self.builder.build_int_s_extend(value, int_type, "CAST")
+        } else {
+            self.builder.build_int_z_extend(value, int_type, "CAST")
+        };
+        value.as_basic_value_enum()
+    }
+
     // 编译两端为布尔类型的二元运算符
     pub fn compile_bool_binary_expression(
         &self,
@@ -316,6 +758,25 @@ impl<'ctx> Compiler<'ctx> {
         }
     }
 
+    // 编译整数类型的一元运算符
+    pub fn compile_int_unary_expression(
+        &self,
+        argument: &BasicValueEnum<'ctx>,
+        operator: &str,
+        pos: usize,
+    ) -> BasicValueEnum<'ctx> {
+        match operator.as_bytes() {
+            b"~" => self
+                .builder
+                .build_not(argument.into_int_value(), "BIT_NOT")
+                .as_basic_value_enum(),
+            _ => self.unexpected_err(
+                pos,
+                &format!("Invalid unary operator in integers: `{}`", operator),
+            ),
+        }
+    }
+
     // 编译布尔类型的一元运算符
     pub fn compile_bool_unary_expression(
         &self,
@@ -7,8 +7,10 @@ use inkwell::context::Context;
 use inkwell::types::*;
 use inkwell::values::*;
 use inkwell::AddressSpace;
+use std::cell::RefCell;
 use std::ops::Deref;
 use x_lang_ast::code_frame::print_error_frame;
+use x_lang_ast::const_eval::eval_int_literal;
 use x_lang_ast::node::Node;
 use x_lang_ast::shared::{Kind, KindName};
 use x_lang_ast::visitor::Visitor;
@@ -18,11 +20,61 @@ pub fn never() -> ! {
     panic!("Internal Error: never")
 }
 
+// 循环体中是否有跳出该循环的 break 语句（包括跳出外层循环的，不包括只跳出内层循环的）
+pub fn has_loop_break(body: &Node) -> bool {
+    let inner_labels = RefCell::new(vec![]); // 内层循环的标签
+    let mut has_break = false;
+    Visitor::walk_with_exit(
+        body,
+        &mut |node, visitor| match node {
+            Node::LoopStatement { label, .. }
+            | Node::WhileStatement { label, .. }
+            | Node::ForStatement { label, .. } => {
+                inner_labels.borrow_mut().push(label.clone())
+            }
+            Node::BreakStatement { label, .. } => {
+                let inner_labels = inner_labels.borrow();
+                let is_exit = match label {
+                    Some(_) => !inner_labels.contains(label),
+                    None => inner_labels.is_empty(),
+                };
+                if is_exit {
+                    has_break = true;
+                    visitor.stop();
+                }
+            }
+            _ => {}
+        },
+        &mut |node, _| {
+            if let Node::LoopStatement { .. }
+            | Node::WhileStatement { .. }
+            | Node::ForStatement { .. } = node
+            {
+                inner_labels.borrow_mut().pop();
+            }
+        },
+    );
+    has_break
+}
+
+// 语句中是否包含 return 语句
+pub fn has_return(node: &Node) -> bool {
+    let mut has_return = false;
+    Visitor::walk(node, &mut |node, visitor| {
+        if let Node::ReturnStatement { .. } = node {
+            has_return = true;
+            visitor.stop();
+        }
+    });
+    has_return
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum Terminator {
     None,
     Return,
     Break,
+    Continue,
 }
 
 impl Terminator {
@@ -31,6 +83,7 @@ impl Terminator {
             Terminator::None => false,
             Terminator::Return => true,
             Terminator::Break => true,
+            Terminator::Continue => true,
         }
     }
 
@@ -48,6 +101,13 @@ impl Terminator {
         }
     }
 
+    pub fn is_continue(&self) -> bool {
+        match self {
+            Terminator::Continue => true,
+            _ => false,
+        }
+    }
+
     pub fn merge(&self, other: Terminator) -> Terminator {
         if self.is_terminated() {
             if self.is_return() {
@@ -84,16 +144,37 @@ impl<'ctx> Compiler<'ctx> {
         )
     }
 
+    // 返回整数类型对应的 LLVM 整数类型
+    pub fn build_int_type(&self, kind_name: &KindName) -> IntType<'ctx> {
+        self.context.custom_width_int_type(kind_name.int_bits())
+    }
+
+    // 返回类型对应的 LLVM 基础类型
+    pub fn build_basic_type(&self, kind_name: &KindName) -> BasicTypeEnum<'ctx> {
+        match kind_name {
+            KindName::Number => self.build_number_type().into(),
+            KindName::Int32 | KindName::Int64 | KindName::UInt8 => {
+                self.build_int_type(kind_name).into()
+            }
+            KindName::Boolean => self.build_bool_type().into(),
+            KindName::String => self.build_store_ptr_type().into(),
+            KindName::Void => never(),
+        }
+    }
+
+    // 返回类型对应的函数形参类型
+    pub fn build_arg_type(&self, kind_name: &KindName) -> BasicMetadataTypeEnum<'ctx> {
+        self.build_basic_type(kind_name).into()
+    }
+
     pub fn build_fn_type(
         &self,
         return_kind: &Kind,
         args: &[BasicMetadataTypeEnum<'ctx>],
     ) -> FunctionType<'ctx> {
         match return_kind.read_return_kind_name() {
-            KindName::Number => self.build_number_type().fn_type(args, false),
-            KindName::Boolean => self.build_bool_type().fn_type(args, false),
-            KindName::String => self.build_store_ptr_type().fn_type(args, false),
             KindName::Void => self.build_void_type().fn_type(args, false),
+            kind_name => self.build_basic_type(kind_name).fn_type(args, false),
         }
     }
 
@@ -137,6 +218,35 @@ impl<'ctx> Compiler<'ctx> {
         }
     }
 
+    // 获取 LLVM 内建函数，如: `llvm.trap`，首次使用时在模块中声明
+    pub fn get_intrinsic_fn(
+        &self,
+        name: &str,
+        fn_type: FunctionType<'ctx>,
+    ) -> FunctionValue<'ctx> {
+        match self.module.get_function(name) {
+            Some(v) => v,
+            None => self.module.add_function(name, fn_type, None),
+        }
+    }
+
+    // 条件成立时中止程序，并将后续代码编译到新的块中
+    pub fn build_trap_if(&self, condition: IntValue<'ctx>) {
+        let fn_value = self.current_fn.unwrap();
+        let trap_block = self.context.append_basic_block(fn_value, "trap");
+        let trap_after_block = self.context.append_basic_block(fn_value, "trap_after");
+        self.builder
+            .build_conditional_branch(condition, trap_block, trap_after_block);
+
+        self.builder.position_at_end(trap_block);
+        let trap_fn = self
+            .get_intrinsic_fn("llvm.trap", self.build_void_type().fn_type(&[], false));
+        self.builder.build_call(trap_fn, &[], "");
+        self.builder.build_unreachable();
+
+        self.builder.position_at_end(trap_after_block);
+    }
+
     pub fn get_declare_var(
         &self,
         name: &str,
@@ -168,6 +278,7 @@ impl<'ctx> Compiler<'ctx> {
         kind: Kind,
         value: Option<BasicValueEnum<'ctx>>, // 如果是函数参数，为空
         is_arg: bool,                        // 是否是函数参数
+        is_mutable: bool,                    // 是否可以重新赋值
         pos: usize,
     ) {
         let current = self.scope.current().unwrap();
@@ -180,35 +291,17 @@ impl<'ctx> Compiler<'ctx> {
             true => format!("ARGUMENT.{}", name),
             false => name.to_string(),
         };
-        let ptr = match kind.read_kind_name().expect("Never") {
-            KindName::Number => {
-                let ty = self.build_number_type();
-                let ptr = self.builder.build_alloca(ty, &mem_name);
-                if let Some(v) = value {
-                    self.builder.build_store(ptr, v.into_float_value());
-                }
-                ptr
-            }
-            KindName::Boolean => {
-                let ty = self.build_bool_type();
-                let ptr = self.builder.build_alloca(ty, &mem_name);
-                if let Some(v) = value {
-                    self.builder.build_store(ptr, v.into_int_value());
-                }
-                ptr
-            }
-            KindName::String => {
-                let ty = self.build_store_ptr_type();
-                let ptr = self.builder.build_alloca(ty, &mem_name);
-                if let Some(v) = value {
-                    self.builder.build_store(ptr, v.into_int_value());
-                }
-                ptr
-            }
-            KindName::Void => never(),
-        };
+        let ty = self.build_basic_type(kind.read_kind_name().expect("Never"));
+        let ptr = self.builder.build_alloca(ty, &mem_name);
+        if let Some(v) = value {
+            self.builder.build_store(ptr, v);
+        }
 
-        let scope_type = ScopeType::Variable { kind, ptr };
+        let scope_type = ScopeType::Variable {
+            kind,
+            ptr,
+            is_mutable,
+        };
         self.scope.put_variable(name, scope_type);
     }
 
@@ -234,7 +327,27 @@ impl<'ctx> Compiler<'ctx> {
             Node::CallExpression {
                 callee, position, ..
             } => {
+                // 内置类型的方法调用
+                if let Node::MemberExpression {
+                    object, property, ..
+                } = callee.deref()
+                {
+                    let (method, .., pos) = property.deref().read_identifier();
+                    let object_kind = self.infer_expression_kind(object.deref());
+                    let kind_name = object_kind.read_kind_name().unwrap();
+                    let (.., return_kind_name) =
+                        self.get_build_in_method(kind_name, method, pos);
+                    ret_kind = Kind::Some(return_kind_name);
+                    visitor.stop();
+                    return;
+                }
+
                 let (name, ..) = callee.deref().read_identifier();
+                if let Some(return_kind_name) = self.get_build_in_fn(name) {
+                    ret_kind = Kind::Some(return_kind_name);
+                    visitor.stop();
+                    return;
+                }
                 match self.scope.search_by_name(name, false) {
                     Some(v) => {
                         if v.is_fn() {
@@ -264,8 +377,8 @@ impl<'ctx> Compiler<'ctx> {
                 operator,
                 position,
             } => {
-                let left_kind = self.infer_expression_kind(left.deref());
-                let right_kind = self.infer_expression_kind(right.deref());
+                let (left_kind, right_kind) =
+                    self.infer_binary_kinds(left.deref(), right.deref());
                 if left_kind != right_kind {
                     self.unexpected_err(
                         position.0,
@@ -274,13 +387,13 @@ impl<'ctx> Compiler<'ctx> {
                 }
 
                 let kind_name = *left_kind.read_kind_name().unwrap();
-                if kind_name == KindName::Number {
+                if kind_name.is_numeric() {
                     match operator.as_bytes() {
                         b"<" | b"<=" | b">" | b">=" | b"==" | b"!=" => {
                             ret_kind = Kind::create("bool")
                         }
                         b"+" | b"-" | b"*" | b"/" | b"%" | b"&" | b"|" | b"^" => {
-                            ret_kind = Kind::create("num")
+                            ret_kind = left_kind
                         }
                         _ => self.unexpected_err(position.0, "Invalid binary expression"),
                     }
@@ -308,9 +421,9 @@ impl<'ctx> Compiler<'ctx> {
             } => {
                 let kind = self.infer_expression_kind(argument.deref());
                 let kind_name = *kind.read_kind_name().unwrap();
-                if kind_name == KindName::Number {
+                if kind_name.is_numeric() {
                     match operator.as_bytes() {
-                        b"~" => ret_kind = Kind::create("num"),
+                        b"~" => ret_kind = kind,
                         _ => self.unexpected_err(position.0, "Invalid unary expression"),
                     }
                 } else if kind_name == KindName::Boolean {
@@ -324,10 +437,37 @@ impl<'ctx> Compiler<'ctx> {
 
                 visitor.stop();
             }
+            Node::CastExpression {
+                expression,
+                kind,
+                position,
+            } => {
+                let from_kind = self.infer_expression_kind(expression.deref());
+                let from_kind_name = from_kind.read_kind_name().unwrap();
+                let to_kind_name = kind.read_kind_name().unwrap();
+                // 只支持数字及布尔类型之间的转换，字符串需使用 `to_str`、`parse_num`
+                let is_castable =
+                    |k: &KindName| k.is_numeric() || *k == KindName::Boolean;
+                if !is_castable(from_kind_name) || !is_castable(to_kind_name) {
+                    self.unexpected_err(
+                        position.0,
+                        &format!(
+                            "Cannot cast `{}` as `{}`",
+                            from_kind.to_string(),
+                            kind.to_string()
+                        ),
+                    )
+                }
+                ret_kind = *kind;
+                visitor.stop();
+            }
             Node::AssignmentExpression { left, .. } => {
                 ret_kind = self.infer_expression_kind(left.deref());
                 visitor.stop();
             }
+            Node::MemberExpression { position, .. } => {
+                self.unexpected_err(position.0, "Property access is not supported")
+            }
             Node::Identifier { name, kind, .. } => match kind {
                 Kind::Some(_) => {
                     ret_kind = *kind;
@@ -355,7 +495,7 @@ impl<'ctx> Compiler<'ctx> {
                 ret_kind = Kind::create("bool");
                 visitor.stop();
             }
-            Node::StringLiteral { .. } => {
+            Node::StringLiteral { .. } | Node::TemplateLiteral { .. } => {
                 ret_kind = Kind::create("str");
                 visitor.stop();
             }
@@ -364,8 +504,105 @@ impl<'ctx> Compiler<'ctx> {
         ret_kind
     }
 
-    // 打印错误帧信息并抛出异常
+    // 按照期望的类型推断表达式类型，整数字面量（及其常量运算）可以推断为期望的整数类型
+    pub fn infer_expression_kind_as(&self, expr: &Node, expected: &Kind) -> Kind {
+        if let Kind::Some(kind_name) = expected {
+            if kind_name.is_integer() && eval_int_literal(expr).is_some() {
+                return *expected;
+            }
+        }
+        self.infer_expression_kind(expr)
+    }
+
+    // 推断二元表达式两端的类型，字面量一端跟随另一端的整数类型，如: `a + 1`
+    pub fn infer_binary_kinds(&self, left: &Node, right: &Node) -> (Kind, Kind) {
+        let left_kind = self.infer_expression_kind(left);
+        let right_kind = self.infer_expression_kind_as(right, &left_kind);
+        let left_kind = self.infer_expression_kind_as(left, &right_kind);
+        (left_kind, right_kind)
+    }
+
+    // 构建整数字面量，超出类型的取值范围时报错
+    pub fn build_int_literal_value(
+        &self,
+        kind_name: &KindName,
+        value: i128,
+        pos: usize,
+    ) -> IntValue<'ctx> {
+        let (min, max) = kind_name.int_range();
+        if value < min || value > max {
+            self.unexpected_err(
+                pos,
+                &format!("Literal out of range for `{}`", kind_name.to_string()),
+            )
+        }
+        self.build_int_type(kind_name)
+            .const_int(value as i64 as u64, kind_name.is_signed())
+    }
+
+    // 获取调用表达式的返回值类型，无返回值时返回 None
+    pub fn call_return_kind_name(&self, callee: &Node) -> Option<KindName> {
+        // 内置类型的方法调用均有返回值
+        if let Node::MemberExpression {
+            object, property, ..
+        } = callee
+        {
+            let (method, .., pos) = property.deref().read_identifier();
+            let object_kind = self.infer_expression_kind(object.deref());
+            let kind_name = object_kind.read_kind_name()?;
+            let (.., return_kind_name) = self.get_build_in_method(kind_name, method, pos);
+            return Some(return_kind_name);
+        }
+
+        let (name, .., pos) = callee.read_identifier();
+        if name == "print" && self.scope.search_by_name(name, true).is_none() {
+            return None;
+        }
+        if let Some(return_kind_name) = self.get_build_in_fn(name) {
+            return Some(return_kind_name);
+        }
+        let FunctionScope { return_kind, .. } = self.get_declare_fn(name, pos);
+        match return_kind.read_kind_name() {
+            Some(kind_name) if *kind_name != KindName::Void => Some(*kind_name),
+            _ => None,
+        }
+    }
+
+    // 记录终止语句（或所有分支均终止的语句）之后不可达的语句
+    pub fn warn_unreachable(&mut self, terminated: &Node, rest: &[Box<Node>]) {
+        let start = rest[0].read_position().0;
+        let end = rest.last().unwrap().read_position().1;
+        let message = match terminated {
+            Node::ReturnStatement { .. } => "Unreachable code after `return`",
+            Node::BreakStatement { .. } => "Unreachable code after `break`",
+            Node::ContinueStatement { .. } => "Unreachable code after `continue`",
+            _ => "Unreachable code",
+        };
+        self.diagnostics.warn((start, end), message);
+    }
+
+    // 检查表达式语句的值是否被使用，未使用时记录警告
+    pub fn check_unused_value(&mut self, expr: &Node, position: (usize, usize)) {
+        match expr {
+            Node::CallExpression { callee, .. } => {
+                if let Some(kind_name) = self.call_return_kind_name(callee.deref()) {
+                    self.diagnostics.warn(
+                        position,
+                        &format!(
+                            "Unused return value of type `{}`",
+                            kind_name.to_string()
+                        ),
+                    );
+                }
+            }
+            Node::AssignmentExpression { .. } => {}
+            _ => self.diagnostics.warn(position, "Unused expression value"),
+        }
+    }
+
+    // 打印之前收集的警告及错误帧信息并抛出异常
     pub fn unexpected_err(&self, pos: usize, msg: &str) -> ! {
+        self.diagnostics.print(self.source);
         let mut message = msg.to_string();
         let position = print_error_frame(self.source, pos, &message);
 
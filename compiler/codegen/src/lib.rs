@@ -9,8 +9,27 @@ mod utils;
 mod tests;
 
 use crate::compiler::Compiler;
+use x_lang_ast::diagnostics::Diagnostics;
 use x_lang_ast::node::Node;
 
+// 编译选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompileOptions {
+    pub is_debug: bool,
+    pub deny_warnings: bool, // 将警告视为错误，存在警告时不运行
+}
+
 pub fn compile(source: &str, is_debug: bool) {
-    Compiler::compile(source, is_debug);
+    compile_with_options(
+        source,
+        CompileOptions {
+            is_debug,
+            ..CompileOptions::default()
+        },
+    );
+}
+
+// 编译并运行，返回收集到的警告及被视为错误的警告
+pub fn compile_with_options(source: &str, options: CompileOptions) -> Diagnostics {
+    Compiler::compile(source, options)
 }
@@ -15,7 +15,11 @@ pub struct FunctionScope<'ctx> {
 #[derive(Debug)]
 pub enum ScopeType<'ctx> {
     Function(FunctionScope<'ctx>),
-    Variable { kind: Kind, ptr: PointerValue<'ctx> },
+    Variable {
+        kind: Kind,
+        ptr: PointerValue<'ctx>,
+        is_mutable: bool, // 是否可以重新赋值
+    },
 }
 
 impl<'ctx> ScopeType<'ctx> {
@@ -40,7 +44,14 @@ impl<'ctx> ScopeType<'ctx> {
     pub fn get_var(&self) -> (&Kind, &PointerValue<'ctx>) {
         match self {
             ScopeType::Function { .. } => panic!("Internal Error"),
-            ScopeType::Variable { kind, ptr } => (kind, ptr),
+            ScopeType::Variable { kind, ptr, .. } => (kind, ptr),
+        }
+    }
+
+    pub fn is_mutable(&self) -> bool {
+        match self {
+            ScopeType::Function { .. } => false,
+            ScopeType::Variable { is_mutable, .. } => *is_mutable,
         }
     }
 }
@@ -91,6 +102,7 @@ impl<'ctx> Scope<'ctx> {
 pub struct BlockScope<'ctx> {
     pub external: Scope<'ctx>,      // 外部绑定的作用域
     pub fns: Scope<'ctx>,           // 全局定义的方法（只能在全局定义）
+    pub globals: Scope<'ctx>,       // 全局定义的常量
     scope_chains: Vec<Scope<'ctx>>, // 变量作用域链
 }
 
@@ -99,6 +111,7 @@ impl<'ctx> BlockScope<'ctx> {
         BlockScope {
             external: Scope::new(None),
             fns: Scope::new(None),
+            globals: Scope::new(None),
             scope_chains: vec![],
         }
     }
@@ -119,6 +132,11 @@ impl<'ctx> BlockScope<'ctx> {
         self.scope_chains.last_mut()
     }
 
+    // 当前是否处于根层级（不在任何块级作用域中）
+    pub fn is_root(&self) -> bool {
+        self.scope_chains.is_empty()
+    }
+
     // 将一个变量放置到当前块作用域中
     pub fn put_variable(&mut self, name: &str, scope_type: ScopeType<'ctx>) {
         let mut scope = self.current().unwrap();
@@ -143,6 +161,11 @@ impl<'ctx> BlockScope<'ctx> {
             }
         }
 
+        // 搜索全局常量
+        if self.globals.has(name) {
+            return self.globals.get(name);
+        }
+
         // 搜索全局方法
         if self.fns.has(name) {
             return self.fns.get(name);
@@ -161,6 +184,7 @@ impl<'ctx> BlockScope<'ctx> {
 pub struct Label<'ctx> {
     pub name: Option<String>,
     pub condition_ptr: PointerValue<'ctx>,
+    pub continue_block: BasicBlock<'ctx>,
     pub after_block: BasicBlock<'ctx>,
 }
 
@@ -191,12 +215,13 @@ impl<'ctx> Labels<'ctx> {
         &mut self,
         name: Option<String>,
         condition_ptr: PointerValue<'ctx>,
-        loop_block: BasicBlock<'ctx>,
+        continue_block: BasicBlock<'ctx>,
         after_block: BasicBlock<'ctx>,
     ) {
         self.label_chains.push(Label {
             name,
             condition_ptr,
+            continue_block,
             after_block,
         });
     }
@@ -11,6 +11,34 @@ impl<'ctx> Compiler<'ctx> {
     // 构建存储 string 到内存中, 返回内存地址的值（i64）
     // 使用 utf-16 编码
     pub fn build_string_value(&self, value: &str) -> IntValue<'ctx> {
+        let str_struct_value = self.build_string_struct_value(value);
+
+        // 分配内存
+        let ptr = self
+            .builder
+            .build_alloca(str_struct_value.get_type(), "string");
+        self.builder.build_store(ptr, str_struct_value);
+
+        self.builder
+            .build_ptr_to_int(ptr, self.build_store_ptr_type(), "")
+    }
+
+    // 构建全局常量 string，返回内存地址的常量值（i64）
+    pub fn build_const_string_value(&self, value: &str) -> IntValue<'ctx> {
+        let str_struct_value = self.build_string_struct_value(value);
+        let global = self
+            .module
+            .add_global(str_struct_value.get_type(), None, "string");
+        global.set_initializer(&str_struct_value);
+        global.set_constant(true);
+
+        global
+            .as_pointer_value()
+            .const_to_int(self.build_store_ptr_type())
+    }
+
+    // 构建 string 结构体的常量值
+    fn build_string_struct_value(&self, value: &str) -> StructValue<'ctx> {
         let chars = get_string_utf16_chars(value);
         let size = chars.len() as u32;
 
@@ -30,20 +58,13 @@ impl<'ctx> Compiler<'ctx> {
             .struct_type(&[i32_type.into(), array_type.into()], false);
 
         // Note: chars 包含了结尾的结束符 0，真实 size 比 chars 长度少 1
-        let str_struct_value = str_struct.const_named_struct(&[
+        str_struct.const_named_struct(&[
             self.context
                 .i32_type()
                 .const_int((size - 1) as u64, false)
                 .into(),
             array_value.into(),
-        ]);
-
-        // 分配内存
-        let ptr = self.builder.build_alloca(str_struct, "string");
-        self.builder.build_store(ptr, str_struct_value);
-
-        self.builder
-            .build_ptr_to_int(ptr, self.build_store_ptr_type(), "")
+        ])
     }
 
     // 构建读取字符串 size (i32)
@@ -8,8 +8,22 @@ fn run_test(code: &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::compile;
+    use crate::{compile, compile_with_options, CompileOptions};
     use std::path::PathBuf;
+    use x_lang_ast::diagnostics::Severity;
+
+    const WARNINGS_SOURCE: &str = "fn add(a: num, b: num) -> num {
+    return a + b;;
+}
+fn main() {
+    add(1, 2);
+    1 + 2;
+    if true {
+        return;
+        print(1);
+    };
+}
+";
 
     #[test]
     fn test1() {
@@ -22,4 +36,38 @@ mod tests {
             run_test(&code);
         }
     }
+
+    #[test]
+    fn warnings() {
+        let diagnostics =
+            compile_with_options(WARNINGS_SOURCE, CompileOptions::default());
+        let mut items = diagnostics.items.clone();
+        items.sort_by_key(|item| item.position);
+        let messages: Vec<&str> =
+            items.iter().map(|item| item.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Redundant semicolon",
+                "Unused return value of type `num`",
+                "Unused expression value",
+                "Condition is always `true`",
+                "Unreachable code after `return`",
+                "Redundant semicolon",
+            ]
+        );
+        assert_eq!(diagnostics.count(Severity::Warning), 6);
+        assert_eq!(diagnostics.count(Severity::Error), 0);
+    }
+
+    #[test]
+    fn deny_warnings() {
+        let options = CompileOptions {
+            deny_warnings: true,
+            ..CompileOptions::default()
+        };
+        let diagnostics = compile_with_options(WARNINGS_SOURCE, options);
+        assert_eq!(diagnostics.count(Severity::Warning), 0);
+        assert_eq!(diagnostics.count(Severity::Error), 6);
+    }
 }
@@ -2,23 +2,41 @@ use std::io::repeat;
 use std::ops::Deref;
 use x_lang_ast::node::Node;
 use x_lang_ast::state::Parser;
+use x_lang_ast::token::{Comment, CommentKind};
 
+// 格式化源码，保留普通注释：独占一行的注释放在下一条语句之前，与语句结尾同一行的注释保留在行尾
 pub fn format(code: &str) -> String {
     let mut parser = Parser::new(code);
     let node = parser.parse();
-    Formatter::format(&node, 4)
+    let comments = parser.take_comments();
+    Formatter::format(&node, 4, code, comments)
 }
 
 struct Formatter {
     current_indent: usize,
     config_indent: usize,
+    chars: Vec<char>,       // 源码字符 vec
+    comments: Vec<Comment>, // 源码中的普通注释（文档注释随声明语句输出）
+    next_comment: usize,    // 下一条尚未输出的注释
 }
 
 impl Formatter {
-    pub fn format(node: &Node, config_indent: usize) -> String {
+    pub fn format(
+        node: &Node,
+        config_indent: usize,
+        code: &str,
+        comments: Vec<Comment>,
+    ) -> String {
+        let comments = comments
+            .into_iter()
+            .filter(|comment| comment.kind != CommentKind::Doc)
+            .collect();
         let mut formatter = Formatter {
             current_indent: 0,
             config_indent,
+            chars: code.chars().collect(),
+            comments,
+            next_comment: 0,
         };
         formatter.format_node(node)
     }
@@ -39,13 +57,90 @@ impl Formatter {
         str
     }
 
+    // 注释在源码中的原文
+    fn comment_text(&self, comment: &Comment) -> String {
+        let text: String = self.chars[comment.start..comment.end].iter().collect();
+        text.trim_end().to_string()
+    }
+
+    // 格式化 `end` 之前尚未输出的注释，每条注释单独一行。
+    // 表达式中间的注释无法保留原位置，会放到下一条语句（或块结尾）之前
+    fn format_leading_comments(&mut self, end: usize) -> String {
+        let mut code = String::new();
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.start >= end {
+                break;
+            }
+            code.push_str(&self.get_indent_str());
+            code.push_str(&self.comment_text(comment));
+            code.push('\n');
+            self.next_comment += 1;
+        }
+        code
+    }
+
+    // 格式化一条语句，与语句结尾在同一行的注释保留在行尾，如: `a = 1; // 注释`
+    fn format_statement(&mut self, node: &Node) -> String {
+        let mut code = self.format_node(node);
+        let end = node.read_position().1;
+        if let Some(comment) = self.comments.get(self.next_comment) {
+            let is_same_line =
+                comment.start >= end && !self.chars[end..comment.start].contains(&'\n');
+            if is_same_line {
+                // 注释插入到语句结尾的换行之前（函数声明之后有空行）
+                let content_len = code.trim_end_matches('\n').len();
+                let newlines = code.split_off(content_len);
+                code.push(' ');
+                code.push_str(&self.comment_text(comment));
+                code.push_str(&newlines);
+                self.next_comment += 1;
+            }
+        }
+        code
+    }
+
+    // 格式化二元运算的操作数，赋值表达式优先级最低，需要保留括号；
+    // 优先级低于父级运算符的二元运算（右侧操作数为不高于）同样需要保留括号，如: `(a + b) * c`
+    fn format_operand(&mut self, node: &Node, operator: &str, is_right: bool) -> String {
+        let is_wrapped = match node {
+            Node::AssignmentExpression { .. } => true,
+            _ if !is_right && ends_with_unary(node) => true,
+            _ => needs_parens(node, operator, is_right),
+        };
+        if is_wrapped {
+            format!("({})", self.format_node(node))
+        } else {
+            self.format_node(node)
+        }
+    }
+
+    // 格式化文档注释，每行一个 `///`，最后一行之后缩进到声明语句的位置
+    fn format_doc(&self, doc: &Option<String>) -> String {
+        let mut code = String::new();
+        if let Some(doc) = doc {
+            for line in doc.lines() {
+                code.push_str("///");
+                if !line.is_empty() {
+                    code.push_str(" ");
+                    code.push_str(line);
+                }
+                code.push_str("\n");
+                code.push_str(&self.get_indent_str());
+            }
+        }
+        code
+    }
+
     fn format_node(&mut self, node: &Node) -> String {
         let mut code = String::new();
         match node {
             Node::Program { body, .. } => {
                 for i in body.iter() {
-                    code.push_str(self.format_node(i.deref()).as_str());
+                    let i = i.deref();
+                    code.push_str(&self.format_leading_comments(i.read_position().0));
+                    code.push_str(&self.format_statement(i));
                 }
+                code.push_str(&self.format_leading_comments(usize::MAX));
             }
             Node::ImportDeclaration {
                 source,
@@ -79,8 +174,10 @@ impl Formatter {
                 body,
                 return_kind,
                 is_pub,
+                doc,
                 ..
             } => {
+                code.push_str(&self.format_doc(doc));
                 if *is_pub {
                     code.push_str("pub ");
                 }
@@ -109,8 +206,20 @@ impl Formatter {
                 code.push_str(&self.format_node(body));
                 code.push_str("\n");
             }
-            Node::VariableDeclaration { id, init, .. } => {
-                code.push_str("var ");
+            Node::VariableDeclaration {
+                id,
+                init,
+                keyword,
+                is_pub,
+                doc,
+                ..
+            } => {
+                code.push_str(&self.format_doc(doc));
+                if *is_pub {
+                    code.push_str("pub ");
+                }
+                code.push_str(keyword);
+                code.push_str(" ");
                 let (name, kind, ..) = id.deref().read_identifier();
                 code.push_str(name);
                 if kind.is_exact() {
@@ -122,9 +231,7 @@ impl Formatter {
                 code.push_str(&self.format_node(init.deref()));
                 code.push_str(";\n");
             }
-            Node::BlockStatement { body, .. } => {
-                code.push_str(&self.format_block(body, true))
-            }
+            Node::BlockStatement { .. } => code.push_str(&self.format_block(node, true)),
             Node::ReturnStatement { argument, .. } => {
                 code.push_str("return");
                 if let Some(v) = argument {
@@ -146,9 +253,7 @@ impl Formatter {
                 code.push_str("if (");
                 code.push_str(&self.format_node(condition.deref()));
                 code.push_str(") ");
-                code.push_str(
-                    &self.format_block(consequent.deref().read_block_body(), false),
-                );
+                code.push_str(&self.format_block(consequent.deref(), false));
                 if let Some(v) = alternate {
                     code.push_str(" else ");
                     code.push_str(&self.format_node(v.deref()));
@@ -164,6 +269,39 @@ impl Formatter {
                 code.push_str("loop ");
                 code.push_str(&self.format_node(body.deref()));
             }
+            Node::WhileStatement {
+                label,
+                condition,
+                body,
+                ..
+            } => {
+                if let Some(v) = label {
+                    code.push_str(v);
+                    code.push_str(": ")
+                }
+                code.push_str("while ");
+                code.push_str(&self.format_node(condition.deref()));
+                code.push_str(" ");
+                code.push_str(&self.format_node(body.deref()));
+            }
+            Node::ForStatement {
+                label,
+                id,
+                iterable,
+                body,
+                ..
+            } => {
+                if let Some(v) = label {
+                    code.push_str(v);
+                    code.push_str(": ")
+                }
+                code.push_str("for ");
+                code.push_str(&self.format_node(id.deref()));
+                code.push_str(" in ");
+                code.push_str(&self.format_node(iterable.deref()));
+                code.push_str(" ");
+                code.push_str(&self.format_node(body.deref()));
+            }
             Node::BreakStatement { label, .. } => {
                 code.push_str("break");
                 if let Some(v) = label {
@@ -192,8 +330,7 @@ impl Formatter {
             Node::CallExpression {
                 callee, arguments, ..
             } => {
-                let (callee_str, ..) = callee.deref().read_identifier();
-                code.push_str(callee_str);
+                code.push_str(&self.format_node(callee.deref()));
                 code.push_str("(");
                 for (i, arg) in arguments.iter().enumerate() {
                     code.push_str(&self.format_node(arg.deref()));
@@ -203,17 +340,57 @@ impl Formatter {
                 }
                 code.push_str(")");
             }
+            Node::CastExpression {
+                expression, kind, ..
+            } => {
+                let expression = expression.deref();
+                match expression {
+                    Node::BinaryExpression { .. }
+                    | Node::UnaryExpression { .. }
+                    | Node::AssignmentExpression { .. } => {
+                        code.push_str("(");
+                        code.push_str(&self.format_node(expression));
+                        code.push_str(")");
+                    }
+                    _ => code.push_str(&self.format_node(expression)),
+                }
+                code.push_str(" as ");
+                code.push_str(&kind.to_string());
+            }
+            Node::RangeExpression { start, end, .. } => {
+                code.push_str(&self.format_node(start.deref()));
+                code.push_str("..");
+                code.push_str(&self.format_node(end.deref()));
+            }
+            Node::MemberExpression {
+                object, property, ..
+            } => {
+                let object = object.deref();
+                let object_code = self.format_node(object);
+                match object {
+                    Node::BinaryExpression { .. }
+                    | Node::UnaryExpression { .. }
+                    | Node::AssignmentExpression { .. } => {
+                        code.push_str("(");
+                        code.push_str(&object_code);
+                        code.push_str(")");
+                    }
+                    _ => code.push_str(&object_code),
+                }
+                code.push_str(".");
+                code.push_str(&self.format_node(property.deref()));
+            }
             Node::BinaryExpression {
                 left,
                 right,
                 operator,
                 ..
             } => {
-                code.push_str(&self.format_node(left.deref()));
+                code.push_str(&self.format_operand(left.deref(), operator, false));
                 code.push_str(" ");
                 code.push_str(operator);
                 code.push_str(" ");
-                code.push_str(&self.format_node(right.deref()));
+                code.push_str(&self.format_operand(right.deref(), operator, true));
             }
             Node::UnaryExpression {
                 argument, operator, ..
@@ -236,36 +413,52 @@ impl Formatter {
             Node::Identifier { name, .. } => {
                 code.push_str(name);
             }
-            Node::NumberLiteral { value, .. } => {
-                code.push_str(&value.to_string());
+            Node::NumberLiteral { raw, .. } => {
+                code.push_str(raw);
             }
             Node::BooleanLiteral { value, .. } => {
                 code.push_str(&value.to_string());
             }
-            Node::StringLiteral { value, is_raw, .. } => {
+            Node::StringLiteral { raw, is_raw, .. } => {
                 if *is_raw {
                     code.push_str("r");
                 }
                 code.push_str("\"");
-                code.push_str(value);
+                code.push_str(raw);
+                code.push_str("\"");
+            }
+            Node::TemplateLiteral {
+                raw_quasis,
+                expressions,
+                ..
+            } => {
+                code.push_str("\"");
+                for (i, quasi) in raw_quasis.iter().enumerate() {
+                    code.push_str(quasi);
+                    if let Some(expression) = expressions.get(i) {
+                        code.push_str("${");
+                        code.push_str(&self.format_node(expression.deref()));
+                        code.push_str("}");
+                    }
+                }
                 code.push_str("\"");
             }
         };
         code
     }
 
-    fn format_block(
-        &mut self,
-        statements: &Vec<Box<Node>>,
-        tail_newline: bool,
-    ) -> String {
+    fn format_block(&mut self, block: &Node, tail_newline: bool) -> String {
         let mut code = String::new();
         code.push_str("{\n");
         self.push_indent();
-        for i in statements.iter() {
+        for i in block.read_block_body().iter() {
+            let i = i.deref();
+            code.push_str(&self.format_leading_comments(i.read_position().0));
             code.push_str(&self.get_indent_str());
-            code.push_str(&self.format_node(i.deref()));
+            code.push_str(&self.format_statement(i));
         }
+        // 块结尾 `}` 之前的注释
+        code.push_str(&self.format_leading_comments(block.read_position().1));
         self.pop_indent();
         code.push_str(&self.get_indent_str());
         code.push_str("}");
@@ -275,3 +468,43 @@ impl Formatter {
         code
     }
 }
+
+// 作为二元运算的操作数时是否因优先级需要保留括号
+fn needs_parens(node: &Node, parent_operator: &str, is_right: bool) -> bool {
+    match node {
+        Node::BinaryExpression { operator, .. } => {
+            let precedence = binary_precedence(operator);
+            let parent = binary_precedence(parent_operator);
+            precedence < parent || (is_right && precedence == parent)
+        }
+        _ => false,
+    }
+}
+
+// 是否以一元运算结尾，一元运算的参数会解析到表达式结尾（如: `!a && b` 为 `!(a && b)`），
+// 作为左侧操作数时需要保留括号
+fn ends_with_unary(node: &Node) -> bool {
+    match node {
+        Node::UnaryExpression { .. } => true,
+        Node::BinaryExpression {
+            right, operator, ..
+        } => !needs_parens(right, operator, true) && ends_with_unary(right),
+        _ => false,
+    }
+}
+
+// 二元运算符的优先级（与解析时一致）
+fn binary_precedence(operator: &str) -> i8 {
+    match operator {
+        "||" => 6,
+        "&&" => 7,
+        "|" => 8,
+        "^" => 9,
+        "&" => 10,
+        "==" | "!=" => 11,
+        "<" | "<=" | ">" | ">=" => 12,
+        "+" | "-" => 14,
+        "*" | "/" | "%" => 15,
+        _ => 0,
+    }
+}
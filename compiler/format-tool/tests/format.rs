@@ -0,0 +1,96 @@
+use x_lang_format_tool::format;
+
+// 格式化 `fn main() { <code> }` 中的单条语句
+fn format_statement(code: &str) -> String {
+    let formatted = format(&format!("fn main() {{ {} }}", code));
+    formatted
+        .lines()
+        .nth(1)
+        .map(|line| line.trim().to_string())
+        .unwrap_or(formatted)
+}
+
+#[test]
+fn keep_required_parens() {
+    assert_eq!(
+        format_statement("print((a + b) * c);"),
+        "print((a + b) * c);"
+    );
+    assert_eq!(
+        format_statement("print(a - (b - c));"),
+        "print(a - (b - c));"
+    );
+    assert_eq!(
+        format_statement("print(a / (b * c));"),
+        "print(a / (b * c));"
+    );
+    // 一元运算的参数会解析到表达式结尾，`!a && b` 为 `!(a && b)`
+    assert_eq!(format_statement("print((!a) && b);"), "print((!a) && b);");
+    assert_eq!(format_statement("print(!(a && b));"), "print(!a && b);");
+}
+
+#[test]
+fn remove_redundant_parens() {
+    assert_eq!(format_statement("print((a * b) + c);"), "print(a * b + c);");
+    assert_eq!(format_statement("print((a - b) - c);"), "print(a - b - c);");
+    assert_eq!(format_statement("print(a + (b * c));"), "print(a + b * c);");
+}
+
+#[test]
+fn format_without_comments() {
+    assert_eq!(
+        format("fn main() {var a = 1\nprint(a)}"),
+        "fn main() {\n    var a = 1;\n    print(a);\n}\n\n"
+    );
+}
+
+#[test]
+fn keep_comments() {
+    let code = "// 入口\nfn main() {\n// 初始值\nvar a = 1 // 行尾注释\n\
+                print(a); /* 块注释 */\n/* 结尾注释 */ }\n// 文件结尾";
+    assert_eq!(
+        format(code),
+        "// 入口\nfn main() {\n    // 初始值\n    var a = 1; // 行尾注释\n    \
+         print(a); /* 块注释 */\n    /* 结尾注释 */\n}\n\n// 文件结尾\n"
+    );
+}
+
+#[test]
+fn keep_comments_in_nested_blocks() {
+    let code =
+        "fn main() {\nif (true) { // 条件成立\nprint(1) } else {\n// 不会执行\n}\n}";
+    let formatted = format(code);
+    assert_eq!(
+        formatted,
+        "fn main() {\n    if (true) {\n        // 条件成立\n        print(1);\n    } \
+         else {\n        // 不会执行\n    }\n}\n\n"
+    );
+    // 再次格式化结果不变
+    assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn keep_comment_after_function() {
+    assert_eq!(
+        format("fn main() {} // 空函数\nfn foo() {}"),
+        "fn main() {\n} // 空函数\n\nfn foo() {\n}\n\n"
+    );
+}
+
+#[test]
+fn keep_doc_comments_once() {
+    let code = "/// 两数之和\nfn add(a: num, b: num) -> num { return a + b }";
+    assert_eq!(
+        format(code),
+        "/// 两数之和\nfn add(a: num, b: num) -> num {\n    return a + b;\n}\n\n"
+    );
+}
+
+#[test]
+fn move_comments_inside_expressions() {
+    // 表达式中间的注释放到下一条语句之前，不会丢失
+    assert_eq!(
+        format("fn main() {\nprint(1 /* 参数 */ + 2)\nprint(3)\n}"),
+        "fn main() {\n    print(1 + 2);\n    /* 参数 */\n    print(3);\n}\n\n"
+    );
+}
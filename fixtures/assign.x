@@ -0,0 +1,21 @@
+fn main() {
+    var a = 1;
+    a += 2;
+    a -= 1;
+    a *= 6;
+    a /= 4;
+    a %= 2;
+    print(a);
+
+    var flags = 12;
+    flags &= 10;
+    flags |= 1;
+    flags ^= 3;
+    print(flags, 6 & 3, 6 | 3, 6 ^ 3);
+
+    var b = 0;
+    var c = 0;
+    a = b = c = 3;
+    a += b *= 2;
+    print(a, b, c, (c = 4) + 1);
+}
@@ -1,4 +1,6 @@
 // comment
+/* block comment /* nested */ */
+/// Returns `b`, or `b + 1` when `c` is false.
 fn a(b :num, c :bool) -> num {
     if (c) {
         return b; // comment
@@ -7,5 +9,5 @@ fn a(b :num, c :bool) -> num {
 }
 
 fn main() {
-    a(0.5, false);
+    a(0.5, /* inline */ false);
 }
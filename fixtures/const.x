@@ -0,0 +1,14 @@
+const MAX = 3;
+const NAME: str = "x-lang";
+
+fn main() {
+    const step = 1;
+    let limit = MAX * 2;
+    var i = 0;
+    while i < limit {
+        i += step;
+    }
+    print(NAME, i, ENABLED);
+}
+
+const ENABLED = true;
@@ -0,0 +1,11 @@
+fn main() {
+    let n = parse_num(" 3.5 ");
+    print(n + 1, parse_num("abc"));
+
+    let s = to_str(n);
+    print(s, to_str(true), to_str(42 as i32), to_str(s));
+
+    let flag = 2 as bool;
+    let count: i32 = flag as i32 + (false as i32);
+    print(flag, count, true as num, 0.0 as bool);
+}
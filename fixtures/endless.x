@@ -0,0 +1,64 @@
+// 只通过 return 结束的 loop，函数末尾不需要 return
+fn first_even(start: num) -> num {
+    var i = start;
+    loop {
+        if i % 2 == 0 {
+            return i;
+        }
+        i = i + 1;
+    }
+}
+
+// 从内层循环跳出外层循环
+fn find(target: num) -> num {
+    var i = 0;
+    var found = 0;
+    outer: loop {
+        loop {
+            i = i + 1;
+            if i == target {
+                found = i;
+                break outer;
+            }
+            if i % 10 == 0 {
+                break;
+            }
+        }
+        print(i);
+    }
+    return found;
+}
+
+// 内层循环不会正常结束，只能继续外层循环
+fn skip() {
+    var i = 0;
+    outer: loop {
+        i = i + 1;
+        if i > 3 {
+            break;
+        }
+        loop {
+            print(i);
+            continue outer;
+        }
+    }
+}
+
+// 不会正常结束的循环之后的语句不可达
+fn spin() {
+    var i = 0;
+    loop {
+        i = i + 1;
+        if i == 3 {
+            return;
+        }
+    }
+    print("never");
+}
+
+fn main() {
+    print(first_even(3));
+    print(find(15));
+    skip();
+    spin();
+}
@@ -0,0 +1,43 @@
+fn sum(n: num) -> num {
+    var total = 0;
+    for i in 0..n {
+        total = total + i;
+    }
+    return total;
+}
+
+fn find(target: num) -> num {
+    var i = 0;
+    while i < 100 {
+        if i * i >= target {
+            return i;
+        }
+        i = i + 1;
+    }
+    return 0 - 1;
+}
+
+fn main() {
+    print(sum(10), find(50));
+
+    var n = 0;
+    while n < 10 {
+        n = n + 1;
+        if n % 2 == 0 {
+            continue;
+        }
+        print(n);
+    }
+
+    outer: for i in 0..5 {
+        for j in 0..5 {
+            if j > i {
+                continue outer;
+            }
+            if i == 4 {
+                break outer;
+            }
+            print(i, j);
+        }
+    }
+}
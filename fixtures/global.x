@@ -0,0 +1,19 @@
+const BASE = 10;
+var counter = BASE * 2;
+let greeting = "hello";
+pub var total = add(counter, 1);
+
+fn add(a: num, b: num) -> num {
+    return a + b;
+}
+
+fn tick() {
+    counter += 1;
+    total = add(total, counter);
+}
+
+fn main() {
+    tick();
+    tick();
+    print(greeting, counter, total);
+}
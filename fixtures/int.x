@@ -0,0 +1,31 @@
+const MASK: u8 = 15;
+
+fn fib(n: i32) -> i64 {
+    var a: i64 = 0;
+    var b: i64 = 1;
+    for i in 0..n {
+        let next = a + b;
+        a = b;
+        b = next;
+    }
+    return a;
+}
+
+fn main() {
+    let a: i32 = 7;
+    print(a / 2, a % 2, a * 3 - 1);
+    print(fib(50));
+
+    var flags: u8 = 200;
+    flags &= MASK;
+    flags |= 1;
+    print(flags, ~flags);
+
+    let n = 7.9 as i32;
+    print(n, n as num / 2, 300.5 as u8, 0 - 1 as i64);
+    print((a as i64) * 1000000000, 255 as u8 as i32 + 1);
+
+    // 超过 2^53 的整数字面量不会丢失精度
+    let big: i64 = 9007199254740993;
+    print(big, big * 2 + 0xff);
+}
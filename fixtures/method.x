@@ -0,0 +1,8 @@
+fn main() {
+    var s = "hello";
+    print(s.len(), s.at(1), s.substr(1, 3));
+
+    var n = 3.6;
+    print(n.floor(), n.to_str().len());
+    print((n + 1).floor().to_str());
+}
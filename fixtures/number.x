@@ -0,0 +1,8 @@
+fn main() {
+    print(0xff, 0o17, 0b1010, 1_000_000);
+    print(1e3, 2.5E-1, 0.125);
+
+    let mask: u8 = 0b1111_0000;
+    let big: i64 = 0x7fff_ffff;
+    print(mask, big, 3.7.floor());
+}
@@ -5,4 +5,10 @@ fn welcome(name: str) {
 fn main() {
     var name = "chen";
     welcome(name);
-}
\ No newline at end of file
+    escape();
+}
+
+fn escape() {
+    print("tab:\tend\nline \u{4F60}\u{597D} \"quoted\" \\ \${raw}");
+    print(r"raw \n stays");
+}
@@ -0,0 +1,12 @@
+fn describe(n: num) -> str {
+    return "${n} is ${n % 2 == 0}";
+}
+
+fn main() {
+    let a = 1;
+    let b: i32 = 2;
+    let name = "x";
+    print("a + b = ${a + b as num}");
+    print("${name}-${b * 3}-${describe(4)}!");
+    print("\${a} is not interpolated");
+}
@@ -0,0 +1,11 @@
+/// 标识符支持 Unicode 字符
+fn 面积(宽: num, 高: num) -> num {
+    return 宽 * 高;
+}
+
+fn main() {
+    let café = "☕";
+    var größe = 面积(3, 4);
+    größe += 1;
+    print(café, größe, "😀 ${größe}");
+}
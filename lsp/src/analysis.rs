@@ -0,0 +1,145 @@
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+use std::ops::Deref;
+use x_lang_ast::infer::InferredKinds;
+use x_lang_ast::line_index::{LineCol, LineIndex};
+use x_lang_ast::lint::{lint_program, LintConfig, LintDiagnostic, LintLevel};
+use x_lang_ast::node::Node;
+use x_lang_ast::shared::BUILD_IN_FN_SIGNATURES;
+use x_lang_ast::state::{ParseError, Parser};
+use x_lang_ast::symbols::{Symbol, SymbolTable};
+use x_lang_ast::visitor::Visitor;
+
+// 单个文档的分析结果
+pub struct Analysis {
+    pub line_index: LineIndex,
+    pub ast: Result<Node, ParseError>,
+    pub symbols: Option<SymbolTable>,
+    pub kinds: Option<InferredKinds>, // 未标注类型的变量及表达式推断出的类型
+    pub lints: Vec<LintDiagnostic>,   // 按 `x-lint.json` 配置检查出的问题
+}
+
+impl Analysis {
+    pub fn new(source: &str, lint_config: &LintConfig) -> Self {
+        let line_index = LineIndex::new(source);
+        let ast = Parser::try_parse(source);
+        let symbols = ast.as_ref().ok().map(SymbolTable::new);
+        let kinds = match (&ast, &symbols) {
+            (Ok(node), Some(table)) => Some(InferredKinds::new(node, table)),
+            _ => None,
+        };
+        let lints = match &ast {
+            Ok(node) => lint_program(source, node, lint_config),
+            Err(_) => vec![],
+        };
+        Analysis {
+            line_index,
+            ast,
+            symbols,
+            kinds,
+            lints,
+        }
+    }
+
+    // 某个位置（字符索引）上的标识符对应的声明及其下标
+    pub fn symbol_at(&self, offset: usize) -> Option<(usize, &Symbol)> {
+        let table = self.symbols.as_ref()?;
+        let index = table.symbol_at(offset)?;
+        Some((index, &table.symbols[index]))
+    }
+
+    // 声明的签名，未标注类型的变量会带上推断出的类型
+    pub fn detail(&self, index: usize, symbol: &Symbol) -> String {
+        let kind_name = self
+            .kinds
+            .as_ref()
+            .and_then(|kinds| kinds.symbol_kind(index));
+        match kind_name {
+            Some(kind_name) => format!("{}: {}", symbol.detail, kind_name.to_string()),
+            None => symbol.detail.to_string(),
+        }
+    }
+
+    // LSP 位置（UTF-16 列） -> 字符索引
+    pub fn to_offset(&self, position: Position) -> Option<usize> {
+        self.line_index.index_of_utf16(LineCol {
+            line: position.line as usize,
+            column: position.character as usize,
+        })
+    }
+
+    // 字符索引区间 -> LSP 区间
+    pub fn to_range(&self, (start, end): (usize, usize)) -> Range {
+        let to_position = |index: usize| {
+            let position = self.line_index.line_col_utf16(index);
+            Position::new(position.line as u32, position.column as u32)
+        };
+        Range::new(to_position(start), to_position(end))
+    }
+
+    // 编译时会报错的语义问题: 类型不匹配、未定义的函数及变量
+    pub fn semantic_diagnostics(&self) -> Vec<Diagnostic> {
+        let (program, table, kinds) = match (&self.ast, &self.symbols, &self.kinds) {
+            (Ok(program), Some(table), Some(kinds)) => (program, table, kinds),
+            _ => return vec![],
+        };
+        let error = |position: (usize, usize), message: String| Diagnostic {
+            range: self.to_range(position),
+            severity: Some(DiagnosticSeverity::ERROR),
+            source: Some(String::from("x-lang")),
+            message,
+            ..Diagnostic::default()
+        };
+        let mut diagnostics = vec![];
+        for mismatch in kinds.mismatches.iter() {
+            let message = format!(
+                "Expected `{}`, found `{}`",
+                mismatch.expected.to_string(),
+                mismatch.found.to_string()
+            );
+            diagnostics.push(error(mismatch.position, message));
+        }
+
+        // 通配导入的名称没有声明，无法判断引用是否有效
+        let mut has_glob_import = false;
+        let mut callees = vec![];
+        Visitor::walk(program, &mut |node, _| match node {
+            Node::ImportSpecifier { imported, .. } if imported == "*" => {
+                has_glob_import = true;
+            }
+            Node::CallExpression { callee, .. } => {
+                if let Node::Identifier { position, .. } = callee.deref() {
+                    callees.push(*position);
+                }
+            }
+            _ => {}
+        });
+        if has_glob_import {
+            return diagnostics;
+        }
+        for (name, position) in table.unresolved.iter() {
+            let message = match callees.contains(position) {
+                true if BUILD_IN_FN_SIGNATURES.iter().any(|(n, _)| n == name) => continue,
+                true => format!("Function `{}` is not found", name),
+                false => format!("Variable `{}` is not found", name),
+            };
+            diagnostics.push(error(*position, message));
+        }
+        diagnostics
+    }
+
+    // 检查出的问题 -> LSP 诊断信息，规则名称作为诊断的 code
+    pub fn lint_diagnostic(&self, lint: &LintDiagnostic) -> Diagnostic {
+        let severity = match lint.level {
+            LintLevel::Deny => DiagnosticSeverity::ERROR,
+            _ => DiagnosticSeverity::WARNING,
+        };
+        Diagnostic {
+            range: self.to_range(lint.position),
+            severity: Some(severity),
+            code: Some(NumberOrString::String(lint.rule.to_string())),
+            source: Some(String::from("x-lang lint")),
+            message: lint.message.clone(),
+            ..Diagnostic::default()
+        }
+    }
+}
@@ -0,0 +1,11 @@
+mod analysis;
+mod server;
+
+use std::process;
+
+fn main() {
+    if let Err(err) = server::run() {
+        eprintln!("x-lang-lsp: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,528 @@
+use crate::analysis::Analysis;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{
+    CodeActionRequest, Completion, DocumentSymbolRequest, Formatting, GotoDefinition,
+    HoverRequest, InlayHintRequest, References, Rename, Request as _,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, CompletionItem, CompletionItemKind, CompletionOptions,
+    CompletionParams, CompletionResponse, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentFormattingParams, DocumentSymbol, DocumentSymbolParams,
+    DocumentSymbolResponse, Documentation, GotoDefinitionParams, GotoDefinitionResponse,
+    Hover, HoverContents, HoverParams, HoverProviderCapability, InlayHint, InlayHintKind,
+    InlayHintLabel, InlayHintParams, Location, MarkupContent, MarkupKind, OneOf,
+    PublishDiagnosticsParams, ReferenceParams, RenameParams, ServerCapabilities,
+    SymbolKind, TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url,
+    WorkspaceEdit,
+};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::ops::Deref;
+use x_lang_ast::completion::{complete_in_modules, CompletionKind};
+use x_lang_ast::lint::{non_overlapping_fixes, Fix, LintConfig};
+use x_lang_ast::module::Module;
+use x_lang_ast::node::Node;
+use x_lang_ast::rename::rename_in_modules;
+use x_lang_format_tool::format;
+
+// lint 配置文件名，与命令行工具一致
+const LINT_CONFIG_FILE: &str = "x-lint.json";
+
+type ServerResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+// 已打开的文档
+struct Document {
+    source: String,
+    analysis: Analysis,
+}
+
+impl Document {
+    fn new(uri: &Url, source: String) -> Self {
+        let analysis = Analysis::new(&source, &read_lint_config(uri));
+        Document { source, analysis }
+    }
+}
+
+// 从文档所在目录向上查找 `x-lint.json`，找不到或配置无效时使用默认配置
+fn read_lint_config(uri: &Url) -> LintConfig {
+    let path = match uri.to_file_path() {
+        Ok(path) => path,
+        Err(_) => return LintConfig::default(),
+    };
+    for dir in path.ancestors().skip(1) {
+        let config_path = dir.join(LINT_CONFIG_FILE);
+        let content = match fs::read_to_string(&config_path) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        let config = serde_json::from_str::<LintConfig>(&content)
+            .map_err(|err| err.to_string())
+            .and_then(|config| config.validate().map(|_| config));
+        return config.unwrap_or_else(|message| {
+            eprintln!(
+                "Invalid lint config `{}`: {}",
+                config_path.display(),
+                message
+            );
+            LintConfig::default()
+        });
+    }
+    LintConfig::default()
+}
+
+pub struct Server {
+    connection: Connection,
+    documents: HashMap<Url, Document>,
+}
+
+// 通过标准输入输出启动服务
+pub fn run() -> ServerResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::FULL,
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        inlay_hint_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server {
+        connection,
+        documents: HashMap::new(),
+    };
+    server.main_loop()?;
+    drop(server);
+    io_threads.join()?;
+    Ok(())
+}
+
+impl Server {
+    fn main_loop(&mut self) -> ServerResult<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    self.handle_notification(notification)?;
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            HoverRequest::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.hover(params))),
+            GotoDefinition::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.definition(params))),
+            DocumentSymbolRequest::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.document_symbols(params))),
+            Completion::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.completion(params))),
+            References::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.references(params))),
+            Formatting::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.formatting(params))),
+            InlayHintRequest::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.inlay_hints(params))),
+            CodeActionRequest::METHOD => serde_json::from_value(request.params)
+                .map(|params| serde_json::to_value(self.code_actions(params))),
+            Rename::METHOD => match serde_json::from_value(request.params) {
+                Ok(params) => match self.rename(params) {
+                    Ok(edit) => Ok(serde_json::to_value(edit)),
+                    Err(message) => {
+                        return Response::new_err(
+                            id,
+                            ErrorCode::RequestFailed as i32,
+                            message,
+                        )
+                    }
+                },
+                Err(err) => Err(err),
+            },
+            _ => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unknown method: {}", request.method),
+                )
+            }
+        };
+
+        match result {
+            Ok(Ok(value)) => Response::new_ok(id, value),
+            Ok(Err(err)) | Err(err) => {
+                Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string())
+            }
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> ServerResult<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                self.update_document(
+                    document.uri,
+                    document.text,
+                    Some(document.version),
+                )?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                // 全量同步，最后一次变更即为完整的文档内容
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    let document = params.text_document;
+                    self.update_document(
+                        document.uri,
+                        change.text,
+                        Some(document.version),
+                    )?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.publish_diagnostics(uri, vec![], None)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // 更新文档内容并发布诊断信息
+    fn update_document(
+        &mut self,
+        uri: Url,
+        source: String,
+        version: Option<i32>,
+    ) -> ServerResult<()> {
+        let document = Document::new(&uri, source);
+        let mut diagnostics = vec![];
+        if let Err(err) = &document.analysis.ast {
+            let analysis = &document.analysis;
+            let end = (err.pos + 1).min(analysis.line_index.len()).max(err.pos);
+            diagnostics.push(Diagnostic {
+                range: analysis.to_range((err.pos, end)),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some(String::from("x-lang")),
+                message: err.message.clone(),
+                ..Diagnostic::default()
+            });
+        }
+        diagnostics.extend(document.analysis.semantic_diagnostics());
+        for lint in document.analysis.lints.iter() {
+            diagnostics.push(document.analysis.lint_diagnostic(lint));
+        }
+        self.documents.insert(uri.clone(), document);
+        self.publish_diagnostics(uri, diagnostics, version)
+    }
+
+    fn publish_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<Diagnostic>,
+        version: Option<i32>,
+    ) -> ServerResult<()> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        };
+        let notification =
+            Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection
+            .sender
+            .send(Message::Notification(notification))?;
+        Ok(())
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let analysis = &self.documents.get(&position.text_document.uri)?.analysis;
+        let offset = analysis.to_offset(position.position)?;
+        let (value, range) = match analysis.symbol_at(offset) {
+            Some((index, symbol)) => {
+                let mut value =
+                    format!("```x-lang\n{}\n```", analysis.detail(index, symbol));
+                if let Some(doc) = &symbol.doc {
+                    value.push_str("\n\n---\n\n");
+                    value.push_str(doc);
+                }
+                (value, None)
+            }
+            // 不是标识符时显示所在表达式的类型
+            None => {
+                let expr = analysis.kinds.as_ref()?.expression_at(offset)?;
+                let value = format!("```x-lang\n{}\n```", expr.kind.to_string());
+                (value, Some(analysis.to_range(expr.position)))
+            }
+        };
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range,
+        })
+    }
+
+    // 与请求区间相交的问题的快速修复，以及修复所有问题
+    fn code_actions(&self, params: CodeActionParams) -> Option<Vec<CodeActionOrCommand>> {
+        let uri = params.text_document.uri;
+        let analysis = &self.documents.get(&uri)?.analysis;
+        let start = analysis.to_offset(params.range.start)?;
+        let end = analysis.to_offset(params.range.end).unwrap_or(usize::MAX);
+        let workspace_edit = |fixes: &[&Fix]| {
+            let edits = fixes
+                .iter()
+                .flat_map(|fix| fix.edits.iter())
+                .map(|edit| TextEdit {
+                    range: analysis.to_range(edit.position),
+                    new_text: edit.new_text.clone(),
+                })
+                .collect();
+            WorkspaceEdit {
+                changes: Some(HashMap::from([(uri.clone(), edits)])),
+                ..WorkspaceEdit::default()
+            }
+        };
+
+        let mut actions = vec![];
+        for lint in analysis.lints.iter() {
+            let fix = match &lint.fix {
+                Some(fix) if lint.position.0 <= end && start <= lint.position.1 => fix,
+                _ => continue,
+            };
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: fix.title.clone(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![analysis.lint_diagnostic(lint)]),
+                edit: Some(workspace_edit(&[fix])),
+                is_preferred: Some(true),
+                ..CodeAction::default()
+            }));
+        }
+
+        let fixes = non_overlapping_fixes(&analysis.lints);
+        if !fixes.is_empty() {
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: String::from("Fix all auto-fixable problems"),
+                kind: Some(CodeActionKind::SOURCE_FIX_ALL),
+                edit: Some(workspace_edit(&fixes)),
+                ..CodeAction::default()
+            }));
+        }
+        Some(actions)
+    }
+
+    // 在未标注类型的变量名后显示推断出的类型
+    fn inlay_hints(&self, params: InlayHintParams) -> Option<Vec<InlayHint>> {
+        let analysis = &self.documents.get(&params.text_document.uri)?.analysis;
+        let start = analysis.to_offset(params.range.start)?;
+        let end = analysis.to_offset(params.range.end).unwrap_or(usize::MAX);
+        let hints = analysis
+            .kinds
+            .as_ref()?
+            .variables
+            .iter()
+            .filter(|variable| start <= variable.position.1 && variable.position.1 <= end)
+            .map(|variable| InlayHint {
+                position: analysis.to_range(variable.position).end,
+                label: InlayHintLabel::String(format!(": {}", variable.kind.to_string())),
+                kind: Some(InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: None,
+                data: None,
+            })
+            .collect();
+        Some(hints)
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let analysis = &self.documents.get(&uri)?.analysis;
+        let offset = analysis.to_offset(position.position)?;
+        let (_, symbol) = analysis.symbol_at(offset)?;
+        Some(GotoDefinitionResponse::Scalar(Location {
+            uri,
+            range: analysis.to_range(symbol.position),
+        }))
+    }
+
+    fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        let analysis = &self.documents.get(&uri)?.analysis;
+        let offset = analysis.to_offset(position.position)?;
+        let (index, _) = analysis.symbol_at(offset)?;
+        let positions = analysis
+            .symbols
+            .as_ref()?
+            .references_of(index, params.context.include_declaration);
+        let locations = positions
+            .into_iter()
+            .map(|position| Location {
+                uri: uri.clone(),
+                range: analysis.to_range(position),
+            })
+            .collect();
+        Some(locations)
+    }
+
+    // 已打开的所有文档，作为模块参与分析
+    fn modules(&self) -> (Vec<&Url>, Vec<Module<'_>>) {
+        let uris = self.documents.keys().collect::<Vec<&Url>>();
+        let modules = uris
+            .iter()
+            .map(|uri| Module {
+                path: uri.path(),
+                source: &self.documents[*uri].source,
+            })
+            .collect();
+        (uris, modules)
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        let analysis = &self.documents.get(&uri)?.analysis;
+        let offset = analysis.to_offset(position.position)?;
+        let (uris, modules) = self.modules();
+        let module = uris.iter().position(|item| **item == uri)?;
+
+        let items = complete_in_modules(&modules, module, offset)
+            .into_iter()
+            .map(|item| CompletionItem {
+                label: item.label,
+                kind: Some(match item.kind {
+                    CompletionKind::Keyword => CompletionItemKind::KEYWORD,
+                    CompletionKind::Kind => CompletionItemKind::TYPE_PARAMETER,
+                    CompletionKind::Function => CompletionItemKind::FUNCTION,
+                    CompletionKind::Argument | CompletionKind::Variable => {
+                        CompletionItemKind::VARIABLE
+                    }
+                    CompletionKind::Label => CompletionItemKind::REFERENCE,
+                    CompletionKind::Import => CompletionItemKind::VALUE,
+                }),
+                detail: item.detail,
+                documentation: item.doc.map(Documentation::String),
+                ..CompletionItem::default()
+            })
+            .collect();
+        Some(CompletionResponse::Array(items))
+    }
+
+    // 重命名符号，已打开的其他文档中对它的导入一并修改
+    fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>, String> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        let analysis = match self.documents.get(&uri) {
+            Some(document) => &document.analysis,
+            None => return Ok(None),
+        };
+        let offset = match analysis.to_offset(position.position) {
+            Some(offset) => offset,
+            None => return Ok(None),
+        };
+
+        let (uris, modules) = self.modules();
+        let module = uris.iter().position(|item| **item == uri).unwrap();
+        let edits = rename_in_modules(&modules, module, offset, &params.new_name)
+            .map_err(|err| err.to_string())?;
+
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+        for (module, edit) in edits {
+            let uri = uris[module];
+            let analysis = &self.documents[uri].analysis;
+            changes.entry(uri.clone()).or_default().push(TextEdit {
+                range: analysis.to_range(edit.position),
+                new_text: edit.new_text,
+            });
+        }
+        Ok(Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..WorkspaceEdit::default()
+        }))
+    }
+
+    fn document_symbols(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Option<DocumentSymbolResponse> {
+        let analysis = &self.documents.get(&params.text_document.uri)?.analysis;
+        let body = match analysis.ast.as_ref().ok()? {
+            Node::Program { body, .. } => body,
+            _ => return None,
+        };
+
+        let mut symbols = vec![];
+        for stat in body.iter() {
+            if let Node::FunctionDeclaration { id, position, .. } = stat.deref() {
+                let (name, _, start) = id.read_identifier();
+                let detail = analysis
+                    .symbol_at(start)
+                    .map(|(index, symbol)| analysis.detail(index, symbol));
+                #[allow(deprecated)]
+                symbols.push(DocumentSymbol {
+                    name: name.to_string(),
+                    detail,
+                    kind: SymbolKind::FUNCTION,
+                    tags: None,
+                    deprecated: None,
+                    range: analysis.to_range(*position),
+                    selection_range: analysis.to_range(id.read_position()),
+                    children: None,
+                });
+            }
+        }
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+
+    // 格式化整个文档，存在语法错误时不处理
+    fn formatting(&self, params: DocumentFormattingParams) -> Option<Vec<TextEdit>> {
+        let document = self.documents.get(&params.text_document.uri)?;
+        // 存在语法错误时不格式化
+        document.analysis.ast.as_ref().ok()?;
+        let new_text = format(&document.source);
+        if new_text == document.source {
+            return Some(vec![]);
+        }
+        let len = document.analysis.line_index.len();
+        Some(vec![TextEdit {
+            range: document.analysis.to_range((0, len)),
+            new_text,
+        }])
+    }
+}
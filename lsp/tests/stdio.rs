@@ -0,0 +1,477 @@
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+const URI: &str = "file:///tmp/main.x";
+
+// 通过标准输入输出与语言服务通信的简易客户端
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_x-lang-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut client = Client {
+            child,
+            stdin,
+            stdout,
+            next_id: 0,
+        };
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            self.stdout.read_line(&mut line).unwrap();
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    // 发送请求并等待对应的响应，忽略期间收到的通知
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(
+            json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }),
+        );
+        loop {
+            let message = self.receive();
+            if message["id"] == json!(id) {
+                return message["result"].clone();
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    // 等待服务端发送指定的通知
+    fn wait_notification(&mut self, method: &str) -> Value {
+        loop {
+            let message = self.receive();
+            if message["method"] == json!(method) {
+                return message["params"].clone();
+            }
+        }
+    }
+
+    fn open(&mut self, text: &str) -> Value {
+        self.open_uri(URI, text)
+    }
+
+    fn open_uri(&mut self, uri: &str, text: &str) -> Value {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": uri, "languageId": "x", "version": 1, "text": text }
+            }),
+        );
+        self.wait_notification("textDocument/publishDiagnostics")
+    }
+
+    fn change(&mut self, version: u32, text: &str) -> Value {
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": version },
+                "contentChanges": [{ "text": text }]
+            }),
+        );
+        self.wait_notification("textDocument/publishDiagnostics")
+    }
+
+    fn position_request(&mut self, method: &str, line: u32, character: u32) -> Value {
+        self.request(
+            method,
+            json!({
+                "textDocument": { "uri": URI },
+                "position": { "line": line, "character": character }
+            }),
+        )
+    }
+
+    fn shutdown(mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+const SOURCE: &str = r#"/// 两数之和
+fn add(a: num, b: num) -> num {
+    return a + b;
+}
+
+fn main() {
+    let 结果 = add(1, 2);
+    print(结果);
+}
+"#;
+
+#[test]
+fn diagnostics() {
+    let mut client = Client::start();
+    let params = client.open("fn main() {\n    let = 1;\n}\n");
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 1, "character": 8 })
+    );
+    assert_eq!(diagnostics[0]["severity"], json!(1));
+
+    let params = client.change(
+        2,
+        "fn main() {\n    let a: str = 1;\n    print(a, b);\n    show();\n}\n",
+    );
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    let errors: Vec<(&str, &Value)> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic["severity"] == json!(1))
+        .map(|diagnostic| {
+            (
+                diagnostic["message"].as_str().unwrap(),
+                &diagnostic["range"]["start"],
+            )
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            (
+                "Expected `str`, found `num`",
+                &json!({ "line": 1, "character": 17 })
+            ),
+            (
+                "Variable `b` is not found",
+                &json!({ "line": 2, "character": 13 })
+            ),
+            (
+                "Function `show` is not found",
+                &json!({ "line": 3, "character": 4 })
+            ),
+        ]
+    );
+
+    let params = client.change(3, SOURCE);
+    assert_eq!(params["diagnostics"], json!([]));
+    client.shutdown();
+}
+
+#[test]
+fn hover_and_definition() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let hover = client.position_request("textDocument/hover", 6, 14);
+    let value = hover["contents"]["value"].as_str().unwrap();
+    assert!(value.contains("fn add(a: num, b: num) -> num"));
+    assert!(value.contains("两数之和"));
+
+    let hover = client.position_request("textDocument/hover", 7, 10);
+    assert!(hover["contents"]["value"]
+        .as_str()
+        .unwrap()
+        .contains("let 结果: num"));
+
+    let definition = client.position_request("textDocument/definition", 6, 14);
+    assert_eq!(definition["uri"], json!(URI));
+    assert_eq!(
+        definition["range"],
+        json!({ "start": { "line": 1, "character": 3 }, "end": { "line": 1, "character": 6 } })
+    );
+
+    let definition = client.position_request("textDocument/definition", 2, 11);
+    assert_eq!(
+        definition["range"]["start"],
+        json!({ "line": 1, "character": 7 })
+    );
+    client.shutdown();
+}
+
+#[test]
+fn symbols_and_formatting() {
+    let mut client = Client::start();
+    client.open(
+        "fn add(a: num, b: num) -> num {return a + b}\nfn main() {print(add(1, 2))}",
+    );
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+    let names: Vec<&str> = symbols
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| symbol["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["add", "main"]);
+
+    let edits = client.request(
+        "textDocument/formatting",
+        json!({
+            "textDocument": { "uri": URI },
+            "options": { "tabSize": 4, "insertSpaces": true }
+        }),
+    );
+    let new_text = edits[0]["newText"].as_str().unwrap();
+    assert!(new_text.starts_with("fn add(a: num, b: num) -> num {\n    return a + b;\n}"));
+    client.shutdown();
+}
+
+#[test]
+fn formatting_keeps_comments() {
+    let mut client = Client::start();
+    client.open("// 入口\nfn main() {print(1) // 行尾注释\n}");
+
+    let edits = client.request(
+        "textDocument/formatting",
+        json!({
+            "textDocument": { "uri": URI },
+            "options": { "tabSize": 4, "insertSpaces": true }
+        }),
+    );
+    assert_eq!(
+        edits[0]["newText"],
+        json!("// 入口\nfn main() {\n    print(1); // 行尾注释\n}\n\n")
+    );
+    client.shutdown();
+}
+
+#[test]
+fn references() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let references = client.request(
+        "textDocument/references",
+        json!({
+            "textDocument": { "uri": URI },
+            "position": { "line": 7, "character": 10 },
+            "context": { "includeDeclaration": true }
+        }),
+    );
+    let positions: Vec<(u64, u64)> = references
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|location| {
+            let start = &location["range"]["start"];
+            (
+                start["line"].as_u64().unwrap(),
+                start["character"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(positions, vec![(6, 8), (7, 10)]);
+    client.shutdown();
+}
+
+#[test]
+fn rename() {
+    let mut client = Client::start();
+    client.open(SOURCE);
+
+    let rename = |client: &mut Client, new_name: &str| {
+        client.next_id += 1;
+        let id = client.next_id;
+        client.send(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "textDocument/rename",
+            "params": {
+                "textDocument": { "uri": URI },
+                "position": { "line": 2, "character": 11 },
+                "newName": new_name
+            }
+        }));
+        loop {
+            let message = client.receive();
+            if message["id"] == json!(id) {
+                return message;
+            }
+        }
+    };
+
+    let response = rename(&mut client, "x");
+    let edits = response["result"]["changes"][URI].as_array().unwrap();
+    let ranges: Vec<&Value> = edits.iter().map(|edit| &edit["range"]["start"]).collect();
+    assert_eq!(
+        ranges,
+        vec![
+            &json!({ "line": 1, "character": 7 }),
+            &json!({ "line": 2, "character": 11 })
+        ]
+    );
+    assert!(edits.iter().all(|edit| edit["newText"] == json!("x")));
+
+    let response = rename(&mut client, "b");
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("conflicts"));
+    let response = rename(&mut client, "return");
+    assert!(response["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("keyword"));
+    client.shutdown();
+}
+
+#[test]
+fn completion() {
+    let mut client = Client::start();
+    client.open("fn add(a: num, b: num) -> num {\n    return a + b;\n}\nfn main() {\n    let total = 1;\n    print(t\n}\n");
+
+    let items = client.position_request("textDocument/completion", 5, 11);
+    let labels: Vec<&str> = items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect();
+    assert_eq!(labels, vec!["total", "to_str", "true"]);
+    assert_eq!(items[0]["detail"], json!("let total"));
+    assert_eq!(
+        items[1]["detail"],
+        json!("fn to_str(value: num | i32 | i64 | u8 | bool | str) -> str")
+    );
+
+    let items = client.position_request("textDocument/completion", 0, 10);
+    assert_eq!(items.as_array().unwrap().len(), 6);
+    assert_eq!(items[0]["label"], json!("num"));
+    client.shutdown();
+}
+
+#[test]
+fn inlay_hints() {
+    let mut client = Client::start();
+    client.open(
+        "fn main() {\n    let a = 1;\n    let b: i64 = 2;\n    let c = b * 3;\n}\n",
+    );
+
+    let hints = client.request(
+        "textDocument/inlayHint",
+        json!({
+            "textDocument": { "uri": URI },
+            "range": {
+                "start": { "line": 0, "character": 0 },
+                "end": { "line": 5, "character": 0 }
+            }
+        }),
+    );
+    assert_eq!(
+        hints,
+        json!([
+            { "position": { "line": 1, "character": 9 }, "label": ": num", "kind": 1 },
+            { "position": { "line": 3, "character": 9 }, "label": ": i64", "kind": 1 }
+        ])
+    );
+
+    let hover = client.position_request("textDocument/hover", 3, 14);
+    assert_eq!(hover["contents"]["value"], json!("```x-lang\ni64\n```"));
+    assert_eq!(
+        hover["range"],
+        json!({ "start": { "line": 3, "character": 12 }, "end": { "line": 3, "character": 17 } })
+    );
+    client.shutdown();
+}
+
+#[test]
+fn lint_code_actions() {
+    let mut client = Client::start();
+    let params = client.open("fn main() {\n    let unused = 1;\n    print(2);\n}\n");
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], json!("unused-variable"));
+    assert_eq!(diagnostics[0]["severity"], json!(2));
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({ "start": { "line": 1, "character": 8 }, "end": { "line": 1, "character": 14 } })
+    );
+
+    let actions = client.request(
+        "textDocument/codeAction",
+        json!({
+            "textDocument": { "uri": URI },
+            "range": {
+                "start": { "line": 1, "character": 10 },
+                "end": { "line": 1, "character": 10 }
+            },
+            "context": { "diagnostics": [] }
+        }),
+    );
+    let actions = actions.as_array().unwrap();
+    assert_eq!(actions.len(), 2);
+    assert_eq!(
+        actions[0]["title"],
+        json!("Remove unused variable `unused`")
+    );
+    assert_eq!(actions[0]["kind"], json!("quickfix"));
+    assert_eq!(
+        actions[0]["edit"]["changes"][URI],
+        json!([{
+            "range": {
+                "start": { "line": 1, "character": 0 },
+                "end": { "line": 2, "character": 0 }
+            },
+            "newText": ""
+        }])
+    );
+    assert_eq!(actions[1]["kind"], json!("source.fixAll"));
+    client.shutdown();
+}
+
+#[test]
+fn lint_config() {
+    // 使用文档所在目录的上层目录中的 `x-lint.json`
+    let root = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("lint_config");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("x-lint.json"),
+        r#"{ "rules": { "unused-variable": "deny", "empty-block": "allow" } }"#,
+    )
+    .unwrap();
+    let uri = format!("file://{}/src/main.x", root.display());
+
+    let mut client = Client::start();
+    let params =
+        client.open_uri(&uri, "fn main() {\n    let unused = 1;\n    loop {}\n}\n");
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], json!("unused-variable"));
+    assert_eq!(diagnostics[0]["severity"], json!(1));
+    client.shutdown();
+}
@@ -0,0 +1,12 @@
+use napi::{CallContext, JsNumber, JsString, JsUnknown};
+use x_lang_ast::completion;
+
+// 某个位置（字符索引）的补全项
+#[js_function(2)]
+pub fn complete(ctx: CallContext) -> napi::Result<JsUnknown> {
+    let input = ctx.get::<JsString>(0)?.into_utf8()?;
+    let input = input.as_str()?;
+    let offset = ctx.get::<JsNumber>(1)?.get_uint32()? as usize;
+    let items = completion::complete(input, offset);
+    ctx.env.to_js_value(&items)
+}
@@ -4,6 +4,9 @@ pub fn to_kind_str(kind: &Kind) -> Option<&str> {
     match kind {
         Kind::Some(kind_name) => match kind_name {
             KindName::Number => Some("number"),
+            KindName::Int32 => Some("i32"),
+            KindName::Int64 => Some("i64"),
+            KindName::UInt8 => Some("u8"),
             KindName::Boolean => Some("boolean"),
             KindName::String => Some("string"),
             KindName::Void => Some("void"),
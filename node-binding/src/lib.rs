@@ -1,13 +1,19 @@
+mod complete;
 mod helper;
 mod parse;
+mod tokenize;
 
 #[macro_use]
 extern crate napi_derive;
 
+use crate::complete::complete;
 use crate::parse::parse;
+use crate::tokenize::tokenize;
 
 #[module_exports]
 fn init(mut exports: napi::JsObject) -> napi::Result<()> {
     exports.create_named_method("parse", parse)?;
+    exports.create_named_method("tokenize", tokenize)?;
+    exports.create_named_method("complete", complete)?;
     Ok(())
 }
@@ -29,6 +29,18 @@ fn set_node_position(
     Ok(())
 }
 
+// 设置声明语句的文档注释，没有时为 null
+fn set_doc(
+    ctx: &CallContext,
+    root: &mut JsObject,
+    doc: &Option<String>,
+) -> napi::Result<()> {
+    match doc {
+        Some(v) => root.set_named_property("doc", ctx.env.create_string(v)?),
+        None => root.set_named_property("doc", ctx.env.get_null()?),
+    }
+}
+
 fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)> {
     // let set_position = |root: &mut JsObject,
     //                     position: (usize, usize)|
@@ -88,6 +100,7 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
             body,
             return_kind,
             is_pub,
+            doc,
             position,
         } => {
             let mut ast_root = ctx.env.create_object()?;
@@ -110,10 +123,18 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
                 ast_root.set_named_property("returnKind", ctx.env.get_null()?);
             }
             ast_root.set_named_property("isPub", ctx.env.get_boolean(*is_pub)?);
+            set_doc(ctx, &mut ast_root, doc)?;
             set_node_position(ctx, &mut ast_root, node);
             Ok(ast_root)
         }
-        Node::VariableDeclaration { id, init, position } => {
+        Node::VariableDeclaration {
+            id,
+            init,
+            keyword,
+            is_pub,
+            doc,
+            position,
+        } => {
             let mut ast_root = ctx.env.create_object()?;
 
             ast_root.set_named_property(
@@ -122,6 +143,9 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
             );
             ast_root.set_named_property("id", transform_js_ast(ctx, id.deref())?);
             ast_root.set_named_property("init", transform_js_ast(ctx, init.deref())?);
+            ast_root.set_named_property("keyword", ctx.env.create_string(keyword)?);
+            ast_root.set_named_property("isPub", ctx.env.get_boolean(*is_pub)?);
+            set_doc(ctx, &mut ast_root, doc)?;
             set_node_position(ctx, &mut ast_root, node);
             Ok(ast_root)
         }
@@ -212,6 +236,50 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
             set_node_position(ctx, &mut ast_root, node);
             Ok(ast_root)
         }
+        Node::WhileStatement {
+            label,
+            condition,
+            body,
+            position,
+        } => {
+            let mut ast_root = ctx.env.create_object()?;
+
+            ast_root.set_named_property("type", ctx.env.create_string("WhileStatement")?);
+            if let Some(v) = label {
+                ast_root.set_named_property("label", ctx.env.create_string(v)?);
+            } else {
+                ast_root.set_named_property("label", ctx.env.get_null()?);
+            }
+            ast_root.set_named_property(
+                "condition",
+                transform_js_ast(ctx, condition.deref())?,
+            );
+            ast_root.set_named_property("body", transform_js_ast(ctx, body.deref())?);
+            set_node_position(ctx, &mut ast_root, node);
+            Ok(ast_root)
+        }
+        Node::ForStatement {
+            label,
+            id,
+            iterable,
+            body,
+            position,
+        } => {
+            let mut ast_root = ctx.env.create_object()?;
+
+            ast_root.set_named_property("type", ctx.env.create_string("ForStatement")?);
+            if let Some(v) = label {
+                ast_root.set_named_property("label", ctx.env.create_string(v)?);
+            } else {
+                ast_root.set_named_property("label", ctx.env.get_null()?);
+            }
+            ast_root.set_named_property("id", transform_js_ast(ctx, id.deref())?);
+            ast_root
+                .set_named_property("iterable", transform_js_ast(ctx, iterable.deref())?);
+            ast_root.set_named_property("body", transform_js_ast(ctx, body.deref())?);
+            set_node_position(ctx, &mut ast_root, node);
+            Ok(ast_root)
+        }
         Node::BreakStatement { label, position } => {
             let mut ast_root = ctx.env.create_object()?;
 
@@ -273,6 +341,54 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
             set_node_position(ctx, &mut ast_root, node);
             Ok(ast_root)
         }
+        Node::CastExpression {
+            expression,
+            kind,
+            position,
+        } => {
+            let mut ast_root = ctx.env.create_object()?;
+
+            ast_root.set_named_property("type", ctx.env.create_string("CastExpression")?);
+            ast_root.set_named_property(
+                "expression",
+                transform_js_ast(ctx, expression.deref())?,
+            );
+            ast_root.set_named_property(
+                "kind",
+                ctx.env.create_string(to_kind_str(kind).unwrap())?,
+            );
+            set_node_position(ctx, &mut ast_root, node);
+            Ok(ast_root)
+        }
+        Node::RangeExpression {
+            start,
+            end,
+            position,
+        } => {
+            let mut ast_root = ctx.env.create_object()?;
+
+            ast_root
+                .set_named_property("type", ctx.env.create_string("RangeExpression")?);
+            ast_root.set_named_property("start", transform_js_ast(ctx, start.deref())?);
+            ast_root.set_named_property("end", transform_js_ast(ctx, end.deref())?);
+            set_node_position(ctx, &mut ast_root, node);
+            Ok(ast_root)
+        }
+        Node::MemberExpression {
+            object,
+            property,
+            position,
+        } => {
+            let mut ast_root = ctx.env.create_object()?;
+
+            ast_root
+                .set_named_property("type", ctx.env.create_string("MemberExpression")?);
+            ast_root.set_named_property("object", transform_js_ast(ctx, object.deref())?);
+            ast_root
+                .set_named_property("property", transform_js_ast(ctx, property.deref())?);
+            set_node_position(ctx, &mut ast_root, node);
+            Ok(ast_root)
+        }
         Node::BinaryExpression {
             left,
             right,
@@ -337,11 +453,16 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
             set_node_position(ctx, &mut ast_root, node);
             Ok(ast_root)
         }
-        Node::NumberLiteral { value, position } => {
+        Node::NumberLiteral {
+            value,
+            raw,
+            position,
+        } => {
             let mut ast_root = ctx.env.create_object()?;
 
             ast_root.set_named_property("type", ctx.env.create_string("NumberLiteral")?);
             ast_root.set_named_property("value", ctx.env.create_double(*value)?);
+            ast_root.set_named_property("raw", ctx.env.create_string(raw)?);
             set_node_position(ctx, &mut ast_root, node);
             Ok(ast_root)
         }
@@ -355,6 +476,7 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
         }
         Node::StringLiteral {
             value,
+            raw,
             is_raw,
             position,
         } => {
@@ -363,6 +485,38 @@ fn transform_js_ast(ctx: &CallContext, node: &Node) -> napi::Result<(JsObject)>
             ast_root.set_named_property("type", ctx.env.create_string("StringLiteral")?);
             ast_root.set_named_property("isRaw", ctx.env.get_boolean(*is_raw)?);
             ast_root.set_named_property("value", ctx.env.create_string(value)?);
+            ast_root.set_named_property("raw", ctx.env.create_string(raw)?);
+            set_node_position(ctx, &mut ast_root, node);
+            Ok(ast_root)
+        }
+        Node::TemplateLiteral {
+            quasis,
+            raw_quasis,
+            expressions,
+            position,
+        } => {
+            let mut ast_root = ctx.env.create_object()?;
+            let mut ast_quasis = ctx.env.create_array_with_length(quasis.len())?;
+            for (index, quasi) in quasis.iter().enumerate() {
+                ast_quasis.set_element(index as u32, ctx.env.create_string(quasi)?);
+            }
+            let mut ast_raw_quasis =
+                ctx.env.create_array_with_length(raw_quasis.len())?;
+            for (index, quasi) in raw_quasis.iter().enumerate() {
+                ast_raw_quasis.set_element(index as u32, ctx.env.create_string(quasi)?);
+            }
+            let mut ast_expressions =
+                ctx.env.create_array_with_length(expressions.len())?;
+            for (index, expression) in expressions.iter().enumerate() {
+                let ast_expression = transform_js_ast(ctx, expression.deref())?;
+                ast_expressions.set_element(index as u32, ast_expression);
+            }
+
+            ast_root
+                .set_named_property("type", ctx.env.create_string("TemplateLiteral")?);
+            ast_root.set_named_property("quasis", ast_quasis);
+            ast_root.set_named_property("rawQuasis", ast_raw_quasis);
+            ast_root.set_named_property("expressions", ast_expressions);
             set_node_position(ctx, &mut ast_root, node);
             Ok(ast_root)
         }
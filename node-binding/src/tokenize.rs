@@ -0,0 +1,61 @@
+use napi::{CallContext, JsObject, JsString, JsUnknown};
+use serde::{Deserialize, Serialize};
+use x_lang_ast::lexer::{Lexer, LexerState};
+use x_lang_ast::token::TokenType;
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct TokenizeOptions {
+    trivia: bool,      // 是否包含空白及注释
+    offset: usize,     // 开始分析的位置
+    state: LexerState, // 上次分析结束时的状态
+}
+
+impl Default for TokenizeOptions {
+    fn default() -> Self {
+        TokenizeOptions {
+            trivia: false,
+            offset: 0,
+            state: LexerState::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsToken {
+    #[serde(rename = "type")]
+    token_type: TokenType,
+    value: String,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Serialize)]
+struct TokenizeResult {
+    tokens: Vec<JsToken>,
+    state: LexerState,
+}
+
+// 词法分析，返回 token 列表及结束时的状态
+#[js_function(2)]
+pub fn tokenize(ctx: CallContext) -> napi::Result<JsUnknown> {
+    let input = ctx.get::<JsString>(0)?.into_utf8()?;
+    let input = input.as_str()?;
+    let options: TokenizeOptions = ctx.env.from_js_value(ctx.get::<JsObject>(1)?)?;
+
+    let mut lexer = Lexer::resume(input, options.offset, options.state, options.trivia);
+    let tokens = lexer
+        .by_ref()
+        .map(|token| JsToken {
+            token_type: token.token_type,
+            value: token.value,
+            start: token.start,
+            end: token.end,
+        })
+        .collect();
+    let result = TokenizeResult {
+        tokens,
+        state: lexer.state(),
+    };
+    ctx.env.to_js_value(&result)
+}
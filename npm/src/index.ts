@@ -1,6 +1,6 @@
 import {loadBinding} from '@node-rs/helper';
 import path from 'path';
-import {Node} from './types';
+import {CompletionItem, Node, TokenizeOptions, TokenizeResult} from './types';
 import {walk} from './walk';
 
 const root = process.env.__XLANG_TEST__ ? process.cwd() : path.join(__dirname, '..');
@@ -21,6 +21,24 @@ const xlang = {
         return bindings.parse(String(input || ''));
     },
 
+    /**
+     * 词法分析，返回 token 列表及结束时的状态
+     * @param input
+     * @param options
+     */
+    tokenize: (input: string, options?: TokenizeOptions): TokenizeResult => {
+        return bindings.tokenize(String(input || ''), options || {});
+    },
+
+    /**
+     * 某个位置（字符索引）的补全项，源码可以是不完整的
+     * @param input
+     * @param offset
+     */
+    complete: (input: string, offset: number): CompletionItem[] => {
+        return bindings.complete(String(input || ''), offset);
+    },
+
     /**
      * 遍历 AST
      */
@@ -3,14 +3,14 @@ export interface Position {
     end: number
 }
 
-export type Kind = 'number' | 'boolean' | 'string' | 'void' | 'infer' | null;
+export type Kind = 'number' | 'i32' | 'i64' | 'u8' | 'boolean' | 'string' | 'void' | 'infer' | null;
 
 export type NodeType =
     'Program' | 'ImportDeclaration' | 'FunctionDeclaration' | 'VariableDeclaration' |
     'BlockStatement' | 'ReturnStatement' | 'ExpressionStatement' | 'IfStatement' |
-    'LoopStatement' | 'BreakStatement' | 'ContinueStatement' | 'ImportSpecifier' |
-    'CallExpression' | 'BinaryExpression' | 'UnaryExpression' | 'AssignmentExpression' |
-    'Identifier' | 'NumberLiteral' | 'BooleanLiteral' | 'StringLiteral'
+    'LoopStatement' | 'WhileStatement' | 'ForStatement' | 'BreakStatement' | 'ContinueStatement' |
+    'ImportSpecifier' | 'CastExpression' | 'RangeExpression' | 'CallExpression' | 'MemberExpression' | 'BinaryExpression' | 'UnaryExpression' |
+    'AssignmentExpression' | 'Identifier' | 'NumberLiteral' | 'BooleanLiteral' | 'StringLiteral' | 'TemplateLiteral'
 
 export interface BaseNode {
     type: NodeType
@@ -36,12 +36,16 @@ export interface FunctionDeclaration extends BaseNode {
     body: Node
     returnKind: Kind
     isPub: boolean
+    doc: string | null
 }
 
 export interface VariableDeclaration extends BaseNode {
     type: 'VariableDeclaration'
     id: Node
     init: Node
+    keyword: 'var' | 'let' | 'const'
+    isPub: boolean
+    doc: string | null
 }
 
 export interface BlockStatement extends BaseNode {
@@ -72,6 +76,21 @@ export interface LoopStatement extends BaseNode {
     body: Node
 }
 
+export interface WhileStatement extends BaseNode {
+    type: 'WhileStatement'
+    label: string | null
+    condition: Node
+    body: Node
+}
+
+export interface ForStatement extends BaseNode {
+    type: 'ForStatement'
+    label: string | null
+    id: Node
+    iterable: Node
+    body: Node
+}
+
 export interface BreakStatement extends BaseNode {
     type: 'BreakStatement'
     label: string | null
@@ -88,12 +107,30 @@ export interface ImportSpecifier extends BaseNode {
     local: string | null
 }
 
+export interface CastExpression extends BaseNode {
+    type: 'CastExpression'
+    expression: Node
+    kind: Kind
+}
+
+export interface RangeExpression extends BaseNode {
+    type: 'RangeExpression'
+    start: Node
+    end: Node
+}
+
 export interface CallExpression extends BaseNode {
     type: 'CallExpression'
     callee: Node
     arguments: Node[]
 }
 
+export interface MemberExpression extends BaseNode {
+    type: 'MemberExpression'
+    object: Node
+    property: Node
+}
+
 export interface BinaryExpression extends BaseNode {
     type: 'BinaryExpression'
     left: Node
@@ -123,6 +160,7 @@ export interface Identifier extends BaseNode {
 export interface NumberLiteral extends BaseNode {
     type: 'NumberLiteral'
     value: number
+    raw: string
 }
 
 export interface BooleanLiteral extends BaseNode {
@@ -132,16 +170,70 @@ export interface BooleanLiteral extends BaseNode {
 
 export interface StringLiteral extends BaseNode {
     type: 'StringLiteral'
-    value: number
+    value: string
+    raw: string
     isRaw: boolean
 }
 
+export interface TemplateLiteral extends BaseNode {
+    type: 'TemplateLiteral'
+    quasis: string[]
+    rawQuasis: string[]
+    expressions: Node[]
+}
+
 export type Node =
     Program | ImportDeclaration | FunctionDeclaration | VariableDeclaration |
     BlockStatement | ReturnStatement | ExpressionStatement | IfStatement |
-    LoopStatement | BreakStatement | ContinueStatement | ImportSpecifier |
-    CallExpression | BinaryExpression | UnaryExpression | AssignmentExpression |
-    Identifier | NumberLiteral | BooleanLiteral | StringLiteral
+    LoopStatement | WhileStatement | ForStatement | BreakStatement | ContinueStatement |
+    ImportSpecifier | CastExpression | RangeExpression | CallExpression | MemberExpression | BinaryExpression | UnaryExpression |
+    AssignmentExpression | Identifier | NumberLiteral | BooleanLiteral | StringLiteral | TemplateLiteral
+
+export type TokenType =
+    'Whitespace' | 'Comment' | 'DocComment' | 'Error' | 'Keyword' | 'Identifier' | 'Number' | 'Boolean' |
+    'String' | 'Template' | 'Assign' | 'PlusAssign' | 'SubAssign' | 'MulAssign' | 'DivAssign' |
+    'REMAssign' | 'BitAndAssign' | 'BitOrAssign' | 'BitXorAssign' | 'Plus' | 'Sub' | 'Mul' | 'Div' |
+    'REM' | 'LT' | 'LE' | 'GT' | 'GE' | 'EQ' | 'NE' | 'LogicAnd' | 'LogicOr' | 'LogicNot' |
+    'BitAnd' | 'BitOr' | 'BitNot' | 'BitXor' | 'ParenL' | 'ParenR' | 'BracketL' | 'BracketR' |
+    'BraceL' | 'BraceR' | 'Comma' | 'Dot' | 'Range' | 'Semi' | 'Colon' | 'Star' | 'ReturnSym'
+
+export interface Token {
+    type: TokenType
+    value: string
+    start: number
+    end: number
+}
+
+export interface LexerState {
+    allowExpr: boolean
+    templateDepths: number[]
+    commentDepth: number
+}
+
+export interface TokenizeOptions {
+    /** 是否包含空白及注释 token */
+    trivia?: boolean
+    /** 开始分析的位置（字符索引） */
+    offset?: number
+    /** 上次分析结束时返回的状态，用于增量分析 */
+    state?: LexerState
+}
+
+export interface TokenizeResult {
+    tokens: Token[]
+    state: LexerState
+}
+
+export type CompletionKind =
+    'Keyword' | 'Kind' | 'Function' | 'Argument' | 'Variable' | 'Label' | 'Import'
+
+export interface CompletionItem {
+    label: string
+    kind: CompletionKind
+    /** 签名，如: `fn add(a: num) -> num` */
+    detail: string | null
+    doc: string | null
+}
 
 export interface WalkContext {
     /**
@@ -163,8 +255,8 @@ export interface WalkContext {
 export type WalkVisitorType = NodeType |
     'Program:exit' | 'ImportDeclaration:exit' | 'FunctionDeclaration:exit' | 'VariableDeclaration:exit' |
     'BlockStatement:exit' | 'ReturnStatement:exit' | 'ExpressionStatement:exit' | 'IfStatement:exit' |
-    'LoopStatement:exit' | 'BreakStatement:exit' | 'ContinueStatement:exit' | 'ImportSpecifier:exit' |
-    'CallExpression:exit' | 'BinaryExpression:exit' | 'UnaryExpression:exit' | 'AssignmentExpression:exit' |
-    'Identifier:exit' | 'NumberLiteral:exit' | 'BooleanLiteral:exit' | 'StringLiteral:exit'
+    'LoopStatement:exit' | 'WhileStatement:exit' | 'ForStatement:exit' | 'BreakStatement:exit' |
+    'ContinueStatement:exit' | 'ImportSpecifier:exit' | 'CastExpression:exit' | 'RangeExpression:exit' | 'CallExpression:exit' | 'MemberExpression:exit' | 'BinaryExpression:exit' | 'UnaryExpression:exit' | 'AssignmentExpression:exit' |
+    'Identifier:exit' | 'NumberLiteral:exit' | 'BooleanLiteral:exit' | 'StringLiteral:exit' | 'TemplateLiteral:exit'
 
 export type WalkVisitor = Record<WalkVisitorType, (node: Node, context: WalkContext) => void>
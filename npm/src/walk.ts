@@ -74,12 +74,28 @@ export function walk(node: Node, visitor: WalkVisitor): void {
             case 'LoopStatement':
                 walkNode(node.body, node);
                 break;
+            case 'WhileStatement':
+                walkNode(node.condition, node);
+                walkNode(node.body, node);
+                break;
+            case 'ForStatement':
+                walkNode(node.id, node);
+                walkNode(node.iterable, node);
+                walkNode(node.body, node);
+                break;
             case 'BreakStatement':
                 break;
             case 'ContinueStatement':
                 break;
             case 'ImportSpecifier':
                 break;
+            case 'CastExpression':
+                walkNode(node.expression, node);
+                break;
+            case 'RangeExpression':
+                walkNode(node.start, node);
+                walkNode(node.end, node);
+                break;
             case 'CallExpression':
                 walkNode(node.callee, node);
                 for (const argument of node.arguments) {
@@ -87,6 +103,10 @@ export function walk(node: Node, visitor: WalkVisitor): void {
                     walkNode(argument, node);
                 }
                 break;
+            case 'MemberExpression':
+                walkNode(node.object, node);
+                walkNode(node.property, node);
+                break;
             case 'BinaryExpression':
                 walkNode(node.left, node);
                 walkNode(node.right, node);
@@ -106,6 +126,12 @@ export function walk(node: Node, visitor: WalkVisitor): void {
                 break;
             case 'StringLiteral':
                 break;
+            case 'TemplateLiteral':
+                for (const expression of node.expressions) {
+                    if (isStop) break;
+                    walkNode(expression, node);
+                }
+                break;
             default:
                 throw new Error('Unexpected type: ' + type);
         }
@@ -21,3 +21,55 @@ describe("parse", () => {
         });
     })
 })
+
+describe("tokenize", () => {
+    test("trivia", () => {
+        const {tokens} = xlang.tokenize('let a = 1; // x', {trivia: true});
+        expect(tokens.map(t => t.type)).toEqual([
+            'Keyword', 'Whitespace', 'Identifier', 'Whitespace', 'Assign', 'Whitespace',
+            'Number', 'Semi', 'Whitespace', 'Comment'
+        ]);
+        expect(tokens[9]).toEqual({type: 'Comment', value: '// x', start: 11, end: 15});
+    });
+
+    test("resume", () => {
+        const first = xlang.tokenize('/* a');
+        expect(first.tokens).toEqual([]);
+        expect(first.state.commentDepth).toBe(1);
+        const second = xlang.tokenize('b */ c', {state: first.state});
+        expect(second.tokens).toEqual([{type: 'Identifier', value: 'c', start: 5, end: 6}]);
+    });
+
+    test("errors", () => {
+        const {tokens} = xlang.tokenize('a = "abc\nb = 1.2.3 @ c');
+        expect(tokens.map(t => [t.type, t.value])).toEqual([
+            ['Identifier', 'a'], ['Assign', '='], ['Error', '"abc'],
+            ['Identifier', 'b'], ['Assign', '='], ['Error', '1.2.3'],
+            ['Error', '@'], ['Identifier', 'c']
+        ]);
+    });
+
+    test("invalid state", () => {
+        expect(() => xlang.tokenize('a', {state: {commentDepth: 'x'} as any})).toThrow();
+    });
+})
+
+describe("complete", () => {
+    test("variables and keywords", () => {
+        const input = 'fn main() {\n    let total = 1;\n    print(t\n}\n';
+        const items = xlang.complete(input, input.indexOf('(t') + 2);
+        expect(items.map(item => item.label)).toEqual(['total', 'to_str', 'true']);
+        expect(items[0]).toEqual({label: 'total', kind: 'Variable', detail: 'let total', doc: null});
+    });
+
+    test("built-in functions", () => {
+        const input = 'fn main() {\n    pr\n}\n';
+        const items = xlang.complete(input, input.indexOf('pr') + 2);
+        expect(items).toEqual([{label: 'print', kind: 'Function', detail: 'fn print(...values)', doc: null}]);
+    });
+
+    test("kinds", () => {
+        const items = xlang.complete('let a: ', 7);
+        expect(items.map(item => item.label)).toEqual(['num', 'i32', 'i64', 'u8', 'bool', 'str']);
+    });
+})
@@ -1,10 +1,22 @@
 use std::env;
 use std::fs;
+use std::path::Path;
+use std::process;
 use std::time::Instant;
-use x_lang_codegen::compile;
+use x_lang_ast::cfg::{build_cfg, to_dot};
+use x_lang_ast::code_frame::{print_error_frame, print_warn_frame};
+use x_lang_ast::diagnostics::Severity;
+use x_lang_ast::lint::{fix, lint, LintConfig, LintLevel};
+use x_lang_codegen::{compile_with_options, CompileOptions};
+use x_lang_format_tool::format;
+
+// 默认的 lint 配置文件（位于当前目录）
+const LINT_CONFIG_FILE: &str = "x-lint.json";
 
 fn print_help_info() {
-    println!("Usage: x-lang <path/example.x> [--debug]");
+    println!("Usage: x-lang <path/example.x> [--debug] [--deny-warnings]");
+    println!("       x-lang lint <path/example.x> [--fix] [--config <path/x-lint.json>]");
+    println!("       x-lang cfg --dot <path/example.x>");
 }
 
 pub fn handle_commander() {
@@ -17,19 +29,43 @@ pub fn handle_commander() {
         return;
     }
 
+    if args[0] == "lint" {
+        handle_lint(&args[1..]);
+        return;
+    }
+
+    if args[0] == "cfg" {
+        handle_cfg(&args[1..]);
+        return;
+    }
+
     let mut args = args.iter();
     let filename = args.next().expect("Missing filename");
-    let is_debug = match args.next() {
-        Some(v) => {
-            if v == "--debug" {
-                true
-            } else {
-                panic!("Invalid argument: {}", v);
-            }
+    let mut options = CompileOptions::default();
+    for arg in args {
+        match arg.as_str() {
+            "--debug" => options.is_debug = true,
+            "--deny-warnings" => options.deny_warnings = true,
+            _ => panic!("Invalid argument: {}", arg),
         }
-        None => false,
-    };
+    }
+
+    let input_content = read_source(filename);
 
+    // compile x-lang，警告被视为错误时以非零状态码退出
+    let diagnostics = compile_with_options(&input_content, options);
+    let error_count = diagnostics.count(Severity::Error);
+    if error_count > 0 {
+        eprintln!(
+            "Aborting due to {} warnings treated as errors (`--deny-warnings`)",
+            error_count
+        );
+        process::exit(1);
+    }
+}
+
+// 读取源文件
+fn read_source(filename: &str) -> String {
     let mut input_content = fs::read_to_string(filename).unwrap();
     let content: Vec<char> = input_content.chars().collect();
 
@@ -38,7 +74,122 @@ pub fn handle_commander() {
         let content = input_content.lines().skip(1).collect::<Vec<&str>>();
         input_content = content.join("\n");
     }
+    input_content
+}
+
+// 读取 lint 配置，未指定配置文件时使用当前目录下的 `x-lint.json`（如果存在）
+fn read_lint_config(path: Option<&String>) -> LintConfig {
+    let path = match path {
+        Some(path) => path.as_str(),
+        None if Path::new(LINT_CONFIG_FILE).exists() => LINT_CONFIG_FILE,
+        None => return LintConfig::default(),
+    };
+    let content = fs::read_to_string(path).unwrap();
+    let config: LintConfig = serde_json::from_str(&content)
+        .unwrap_or_else(|err| panic!("Invalid lint config `{}`: {}", path, err));
+    if let Err(message) = config.validate() {
+        panic!("Invalid lint config `{}`: {}", path, message);
+    }
+    config
+}
+
+// x-lang lint <path/example.x> [--fix] [--config <path/x-lint.json>]
+fn handle_lint(args: &[String]) {
+    let mut filename = None;
+    let mut config_path = None;
+    let mut is_fix = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fix" => is_fix = true,
+            "--config" => config_path = Some(args.next().expect("Missing config path")),
+            _ if arg.starts_with("--") => panic!("Invalid argument: {}", arg),
+            _ => filename = Some(arg),
+        }
+    }
+    let filename = filename.expect("Missing filename");
+    let config = read_lint_config(config_path);
+    let mut source = read_source(filename);
+
+    // 应用自动修复并格式化后写回文件，之后报告剩余的问题
+    if is_fix {
+        let (fixed, count) = match fix(&source, &config) {
+            Ok(result) => result,
+            Err(err) => {
+                print_error_frame(&source, err.pos, &err.message);
+                process::exit(1);
+            }
+        };
+        if count > 0 {
+            source = format(&fixed);
+            let content = fs::read_to_string(filename).unwrap();
+            let header = match content.lines().next() {
+                Some(line) if line.starts_with("#!") => format!("{}\n", line),
+                _ => String::new(),
+            };
+            fs::write(filename, format!("{}{}", header, source)).unwrap();
+        }
+        println!("Fixed {} problems in {}\n", count, filename);
+    }
+
+    let diagnostics = match lint(&source, &config) {
+        Ok(diagnostics) => diagnostics,
+        Err(err) => {
+            print_error_frame(&source, err.pos, &err.message);
+            process::exit(1);
+        }
+    };
+
+    let mut warn_count = 0;
+    let mut deny_count = 0;
+    for diagnostic in diagnostics.iter() {
+        let message = format!("{} [{}]", diagnostic.message, diagnostic.rule);
+        let pos = diagnostic.position.0;
+        let position = match diagnostic.level {
+            LintLevel::Deny => {
+                deny_count += 1;
+                print_error_frame(&source, pos, &message)
+            }
+            _ => {
+                warn_count += 1;
+                print_warn_frame(&source, pos, &message)
+            }
+        };
+        if let Some((line, column)) = position {
+            println!("  --> {}:{}:{}", filename, line, column);
+        }
+        println!();
+    }
+
+    println!("{} warnings, {} errors", warn_count, deny_count);
+    if deny_count > 0 {
+        process::exit(1);
+    }
+}
 
-    // compile x-lang
-    compile(&input_content, is_debug);
+// x-lang cfg --dot <path/example.x>
+fn handle_cfg(args: &[String]) {
+    let mut filename = None;
+    let mut is_dot = false;
+    for arg in args.iter() {
+        match arg.as_str() {
+            "--dot" => is_dot = true,
+            _ if arg.starts_with("--") => panic!("Invalid argument: {}", arg),
+            _ => filename = Some(arg),
+        }
+    }
+    let filename = filename.expect("Missing filename");
+    if !is_dot {
+        panic!("Missing output format: --dot");
+    }
+    let source = read_source(filename);
+
+    // 输出每个函数的控制流图（Graphviz DOT 格式）
+    match build_cfg(&source) {
+        Ok(cfgs) => print!("{}", to_dot(&cfgs)),
+        Err(err) => {
+            print_error_frame(&source, err.pos, &err.message);
+            process::exit(1);
+        }
+    }
 }
@@ -1,6 +1,11 @@
 mod utils;
 
 use wasm_bindgen::prelude::*;
+use x_lang_ast::completion;
+use x_lang_ast::infer::InferredKinds;
+use x_lang_ast::lexer::{Lexer, LexerState};
+use x_lang_ast::state::Parser;
+use x_lang_ast::symbols::SymbolTable;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -23,3 +28,47 @@ pub fn parse(input: &str) -> String {
     let ast = x_lang_ast::state::Parser::new(input).parse();
     serde_json::to_string(&ast).unwrap()
 }
+
+// 词法分析，`state` 为上次分析结束时返回的状态（JSON），为空时从初始状态开始，
+// 状态无效时抛出 JS 异常
+#[wasm_bindgen]
+pub fn tokenize(
+    input: &str,
+    trivia: bool,
+    offset: usize,
+    state: &str,
+) -> Result<String, JsValue> {
+    let state = match state {
+        "" => LexerState::new(),
+        _ => serde_json::from_str(state)
+            .map_err(|err| JsValue::from_str(&format!("Invalid lexer state: {}", err)))?,
+    };
+    let mut lexer = Lexer::resume(input, offset, state, trivia);
+    let tokens: Vec<serde_json::Value> = lexer
+        .by_ref()
+        .map(|token| {
+            serde_json::json!({
+                "type": token.token_type,
+                "value": token.value,
+                "start": token.start,
+                "end": token.end,
+            })
+        })
+        .collect();
+    Ok(serde_json::json!({ "tokens": tokens, "state": lexer.state() }).to_string())
+}
+
+// 某个位置（字符索引）的补全项（JSON）
+#[wasm_bindgen]
+pub fn complete(input: &str, offset: usize) -> String {
+    serde_json::to_string(&completion::complete(input, offset)).unwrap()
+}
+
+// 推断出的变量及表达式类型（JSON），存在语法错误时返回 `null`
+#[wasm_bindgen]
+pub fn infer_kinds(input: &str) -> String {
+    let kinds = Parser::try_parse(input)
+        .ok()
+        .map(|ast| InferredKinds::new(&ast, &SymbolTable::new(&ast)));
+    serde_json::to_string(&kinds).unwrap()
+}